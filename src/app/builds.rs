@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::jobs::{JobKind, JobPhase, JobReporter};
+use crate::app::services::run_phase;
+use crate::app::state::App;
+use crate::docker::build::{BuildStep, context_fingerprint};
+use crate::docker::client::DockerClient;
+use crate::docker::compose::ComposeProject;
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::overrides;
+use crate::docker::process::{LineFilter, run_stream_with_line_filter};
+use crate::docker::volumes::{self, MountSource};
+use crate::log_buffer::SharedLogBuffer;
+use crate::status::{Status, ToastState};
+
+/// Files and directories the manager writes into a project directory; they
+/// sit inside the usual `build: .` context but are never build input.
+const MANAGER_PATHS: [&str; 6] = [
+    ".build-state.json",
+    ".pull-state.json",
+    ".seed-state.json",
+    "images.lock.json",
+    overrides::DIR,
+    "snapshots",
+];
+
+/// Build-context fingerprints from the last successful build of each
+/// service, kept next to the project.
+#[derive(Serialize, Deserialize, Default)]
+struct BuildState {
+    fingerprints: BTreeMap<String, String>,
+}
+
+impl BuildState {
+    fn path(project: &str) -> String {
+        format!("containers/{}/.build-state.json", project)
+    }
+
+    async fn load(project: &str) -> Self {
+        tokio::fs::read_to_string(Self::path(project))
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    async fn save(&self, project: &str) {
+        if let Ok(content) = serde_json::to_string_pretty(self) {
+            tokio::fs::write(Self::path(project), content).await.ok();
+        }
+    }
+}
+
+impl App {
+    pub fn rebuild_service(&mut self, no_cache: bool) {
+        if let Some(i) = self.state.selected() {
+            self.rebuild_service_at(i, no_cache);
+        }
+    }
+
+    /// Rebuilds every service with a `build:` section and, if the project was
+    /// running, recreates its containers from the new images.
+    pub fn rebuild_service_at(&mut self, i: usize, no_cache: bool) {
+        if !self.docker_daemon_running {
+            self.set_toast(
+                ToastState::Error,
+                "Cannot build: Docker daemon not responding",
+                5,
+            );
+            return;
+        }
+
+        let service_name = self.services[i].name.clone();
+        let current_status = self.services[i].status.clone();
+        if matches!(
+            current_status,
+            Status::Pulling | Status::Building | Status::Starting | Status::Stopping
+        ) || self.active_job(&service_name).is_some()
+        {
+            self.set_toast(
+                ToastState::Warning,
+                format!(
+                    "{} is busy, press {} to cancel",
+                    service_name, self.keybinds.services.cancel_job
+                ),
+                3,
+            );
+            return;
+        }
+
+        let logs = Arc::clone(&self.services[i].logs);
+        let name = service_name.clone();
+        self.spawn_job(
+            i,
+            JobKind::Rebuild,
+            JobPhase::Build,
+            Status::Building,
+            |job| async move {
+                let result = rebuild_project(&name, no_cache, current_status, &logs, &job).await;
+                job.progress(None);
+                if result.is_err() {
+                    job.status(Status::Error);
+                }
+                result
+            },
+        );
+        self.services[i].pull_progress = Some("queued".to_string());
+        self.services[i].pull_layers = None;
+
+        self.reset_watchdog(&service_name);
+        let message = if no_cache {
+            format!("Rebuilding {} without cache", service_name)
+        } else {
+            format!("Rebuilding {}", service_name)
+        };
+        self.set_toast(ToastState::Success, message, 3);
+    }
+}
+
+async fn rebuild_project(
+    project: &str,
+    no_cache: bool,
+    prior_status: Status,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    logs.lock().unwrap().clear();
+    let built = ComposeFile::load(project)
+        .await
+        .map(|compose| compose.built_services(project))
+        .unwrap_or_default();
+    if built.is_empty() {
+        logs.lock()
+            .unwrap()
+            .push_str("No services with a build section.\n");
+        job.toast(
+            ToastState::Info,
+            format!("{} has nothing to build", project),
+        );
+        job.status(prior_status);
+        return Ok(());
+    }
+
+    run_build(project, &built, no_cache, logs, job).await?;
+    if prior_status != Status::Running {
+        job.status(prior_status);
+        return Ok(());
+    }
+
+    job.phase(JobPhase::Up);
+    job.status(Status::Starting);
+    let compose = ComposeProject::new(project.to_string());
+    run_phase(compose.up_detached_cmd(false), logs, "Up output:\n", "Up").await?;
+    if DockerClient::get_status(project).await == Status::Running {
+        job.status(Status::Running);
+    }
+    Ok(())
+}
+
+/// Picks the services whose build context changed since their last build,
+/// or that were never built by the manager, logging the reason for each.
+pub async fn services_to_build(project: &str, logs: &SharedLogBuffer) -> Vec<(String, PathBuf)> {
+    let Some(compose) = ComposeFile::load(project).await else {
+        return Vec::new();
+    };
+    let state = BuildState::load(project).await;
+    let excluded = fingerprint_exclusions(project, &compose).await;
+
+    let mut selected = Vec::new();
+    for (name, context) in compose.built_services(project) {
+        let fingerprint = fingerprint(&context, &excluded).await;
+        let reason = match (state.fingerprints.get(&name), &fingerprint) {
+            (None, _) => Some("never built"),
+            (Some(_), None) => Some("context unreadable"),
+            (Some(last), Some(current)) if last != current => Some("context changed"),
+            _ => None,
+        };
+        logs.lock().unwrap().push_str(&format!(
+            "{} ({}): {}\n",
+            name,
+            context.display(),
+            match reason {
+                Some(reason) => format!("build [{}]", reason),
+                None => "skip [unchanged]".to_string(),
+            }
+        ));
+        if reason.is_some() {
+            selected.push((name, context));
+        }
+    }
+    selected
+}
+
+/// Builds the `built` services, reporting step progress to the job, and
+/// records their context fingerprints.
+pub async fn run_build(
+    project: &str,
+    built: &[(String, PathBuf)],
+    no_cache: bool,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    let last_step: Arc<Mutex<Option<BuildStep>>> = Arc::new(Mutex::new(None));
+    let filter: LineFilter = {
+        let job = job.clone();
+        let last_step = Arc::clone(&last_step);
+        Arc::new(move |line: &str| {
+            if let Some(step) = BuildStep::parse(line) {
+                let mut last_step = last_step.lock().unwrap();
+                if last_step.as_ref() != Some(&step) {
+                    job.progress(Some(step.summary()));
+                    *last_step = Some(step);
+                }
+            }
+            Some(line.to_string())
+        })
+    };
+
+    let services: Vec<String> = built.iter().map(|(name, _)| name.clone()).collect();
+    let compose = ComposeProject::new(project.to_string());
+    match run_stream_with_line_filter(
+        compose.build_cmd(no_cache, &services),
+        Arc::clone(logs),
+        Some("Build output:\n"),
+        Some(filter),
+    )
+    .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            let reason = "build exited with non-zero status".to_string();
+            logs.lock()
+                .unwrap()
+                .push_str(&format!("Build failed: {}\n", reason));
+            return Err(reason);
+        }
+        Err(e) => {
+            logs.lock()
+                .unwrap()
+                .push_str(&format!("Build failed: {}\n", e));
+            return Err(e.to_string());
+        }
+    }
+
+    let compose = ComposeFile::load(project).await.unwrap_or_default();
+    let excluded = fingerprint_exclusions(project, &compose).await;
+    let mut state = BuildState::load(project).await;
+    for (name, context) in built {
+        if let Some(fingerprint) = fingerprint(context, &excluded).await {
+            state.fingerprints.insert(name.clone(), fingerprint);
+        }
+    }
+    state.save(project).await;
+    Ok(())
+}
+
+/// Paths left out of context fingerprints: the manager's own files and the
+/// sources of bind mounts, which containers write to while they run.
+async fn fingerprint_exclusions(project: &str, compose: &ComposeFile) -> Vec<PathBuf> {
+    let project_dir = Path::new("containers").join(project);
+    let manager = MANAGER_PATHS.iter().map(|path| project_dir.join(path));
+    let binds = volumes::data_mounts(project, compose)
+        .into_iter()
+        .filter_map(|mount| match mount.source {
+            MountSource::Bind(path) => Some(path),
+            MountSource::Volume(_) => None,
+        });
+    // Canonical paths so they compare with the walked context whatever the
+    // compose file wrote (`./db`, `db/`, absolute); missing paths hold no files.
+    let mut excluded = Vec::new();
+    for path in manager.chain(binds) {
+        if let Ok(path) = tokio::fs::canonicalize(path).await {
+            excluded.push(path);
+        }
+    }
+    excluded
+}
+
+async fn fingerprint(context: &Path, excluded: &[PathBuf]) -> Option<String> {
+    let context = tokio::fs::canonicalize(context).await.ok()?;
+    let excluded = excluded.to_vec();
+    tokio::task::spawn_blocking(move || context_fingerprint(&context, &excluded))
+        .await
+        .ok()
+        .flatten()
+        .map(|fingerprint| format!("{:016x}", fingerprint))
+}
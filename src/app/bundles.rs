@@ -0,0 +1,303 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::jobs::{JobKind, JobPhase, JobReporter};
+use crate::app::services::run_phase;
+use crate::app::state::App;
+use crate::docker::archive;
+use crate::docker::bundle::{
+    self, BundleImage, BundleManifest, IMAGES_FILE, MANIFEST_FILE, ScratchDir,
+};
+use crate::docker::client::DockerClient;
+use crate::docker::compose_file::ComposeFile;
+use crate::log_buffer::SharedLogBuffer;
+use crate::status::ToastState;
+
+impl App {
+    pub fn toggle_mark(&mut self) {
+        if let Some(i) = self.state.selected() {
+            let service = &mut self.services[i];
+            service.marked = !service.marked;
+        }
+    }
+
+    /// Marked projects, or the selected one when nothing is marked.
+    fn marked_or_selected(&self) -> Vec<String> {
+        let marked: Vec<String> = self
+            .services
+            .iter()
+            .filter(|service| service.marked)
+            .map(|service| service.name.clone())
+            .collect();
+        if !marked.is_empty() {
+            return marked;
+        }
+        self.state
+            .selected()
+            .map(|i| vec![self.services[i].name.clone()])
+            .unwrap_or_default()
+    }
+
+    /// Saves every image of the marked projects into one bundle in the bundle
+    /// directory. Output goes to the selected project's log.
+    pub fn export_bundle(&mut self) {
+        let Some(i) = self.bundle_job_index() else {
+            return;
+        };
+        let projects = self.marked_or_selected();
+        let dir = PathBuf::from(&self.settings.bundles.dir);
+        let logs = Arc::clone(&self.services[i].logs);
+        let status = self.services[i].status.clone();
+        let count = projects.len();
+        self.spawn_job(
+            i,
+            JobKind::ExportBundle,
+            JobPhase::Save,
+            status,
+            |job| async move { export(projects, &dir, &logs, &job).await },
+        );
+        self.set_toast(
+            ToastState::Info,
+            format!("Exporting images of {} projects", count),
+            3,
+        );
+    }
+
+    pub fn open_bundles(&mut self) {
+        self.bundles = archive::list(Path::new(&self.settings.bundles.dir));
+        self.bundles_selected = 0;
+        self.bundles_mode = true;
+    }
+
+    /// Loads the bundle highlighted in the bundles overlay.
+    pub fn import_selected_bundle(&mut self) {
+        let Some(path) = self
+            .bundles
+            .get(self.bundles_selected)
+            .map(|bundle| bundle.path.clone())
+        else {
+            return;
+        };
+        let Some(i) = self.bundle_job_index() else {
+            return;
+        };
+        self.bundles_mode = false;
+
+        let logs = Arc::clone(&self.services[i].logs);
+        let status = self.services[i].status.clone();
+        let name = path.display().to_string();
+        self.spawn_job(
+            i,
+            JobKind::ImportBundle,
+            JobPhase::Load,
+            status,
+            |job| async move { import(&path, &logs, &job).await },
+        );
+        self.set_toast(ToastState::Info, format!("Importing {}", name), 3);
+    }
+
+    /// Bundle jobs run under the selected project, which must be idle.
+    fn bundle_job_index(&mut self) -> Option<usize> {
+        if !self.docker_daemon_running {
+            self.set_toast(
+                ToastState::Error,
+                "Cannot use bundles: Docker daemon not responding",
+                5,
+            );
+            return None;
+        }
+        let i = self.state.selected()?;
+        let service_name = self.services[i].name.clone();
+        if self.active_job(&service_name).is_some() {
+            self.set_toast(
+                ToastState::Warning,
+                format!(
+                    "{} is busy, press {} to cancel",
+                    service_name, self.keybinds.services.cancel_job
+                ),
+                3,
+            );
+            return None;
+        }
+        Some(i)
+    }
+}
+
+async fn export(
+    projects: Vec<String>,
+    dir: &Path,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    logs.lock().unwrap().clear();
+
+    let mut images = BTreeSet::new();
+    for project in &projects {
+        match ComposeFile::load(project).await {
+            Some(compose) => images.extend(compose.images(project)),
+            None => logs.lock().unwrap().push_str(&format!(
+                "[bundle] {}: cannot read {}\n",
+                project,
+                ComposeFile::path(project)
+            )),
+        }
+    }
+
+    let mut entries = Vec::new();
+    for image in images {
+        let Some(id) = DockerClient::image_id(&image).await else {
+            logs.lock().unwrap().push_str(&format!(
+                "[bundle] {}: not present locally, skipped\n",
+                image
+            ));
+            continue;
+        };
+        let digest = DockerClient::image_digest(&image)
+            .await
+            .filter(|digest| *digest != id);
+        entries.push(BundleImage { image, id, digest });
+    }
+    if entries.is_empty() {
+        return Err("no images to export".to_string());
+    }
+
+    tokio::fs::create_dir_all(dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    let scratch = ScratchDir::create(dir).await?;
+    let archive = scratch.path().join(IMAGES_FILE);
+    let names: Vec<String> = entries.iter().map(|entry| entry.image.clone()).collect();
+    job.progress(Some(format!("saving {} images", names.len())));
+    run_phase(
+        bundle::save_cmd(&names, &archive),
+        logs,
+        "Save output:\n",
+        "Save",
+    )
+    .await?;
+
+    let manifest = BundleManifest {
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or(0),
+        projects: projects.clone(),
+        images: entries,
+        images_sha256: bundle::sha256(&archive).await?,
+    };
+    let content = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    tokio::fs::write(scratch.path().join(MANIFEST_FILE), content)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let name = bundle::bundle_name(&projects);
+    let packed = scratch.path().join(&name);
+    job.progress(Some("packing".to_string()));
+    run_phase(
+        bundle::pack_cmd(scratch.path(), &packed),
+        logs,
+        "Pack output:\n",
+        "Pack",
+    )
+    .await?;
+    // Only a complete bundle ever shows up in the bundles directory.
+    let output = dir.join(&name);
+    tokio::fs::rename(&packed, &output)
+        .await
+        .map_err(|e| e.to_string())?;
+    job.progress(None);
+
+    let mut logs_lock = logs.lock().unwrap();
+    logs_lock.push_str(&format!("[bundle] wrote {}\n", output.display()));
+    for image in &manifest.images {
+        logs_lock.push_str(&format!("  {} {}\n", image.image, image.id));
+    }
+    drop(logs_lock);
+    job.toast(
+        ToastState::Success,
+        format!(
+            "Bundled {} images into {}",
+            manifest.images.len(),
+            output.display()
+        ),
+    );
+    Ok(())
+}
+
+/// Unpacks a bundle, checks the archive against the manifest checksum, loads
+/// it and then checks every listed image came back with the same id.
+async fn import(
+    bundle_path: &Path,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    logs.lock().unwrap().clear();
+
+    let scratch = ScratchDir::create(bundle_path.parent().unwrap_or(Path::new("."))).await?;
+    job.progress(Some("unpacking".to_string()));
+    run_phase(
+        bundle::unpack_cmd(bundle_path, scratch.path()),
+        logs,
+        "Unpack output:\n",
+        "Unpack",
+    )
+    .await?;
+
+    let content = tokio::fs::read_to_string(scratch.path().join(MANIFEST_FILE))
+        .await
+        .map_err(|e| format!("bundle has no readable {}: {}", MANIFEST_FILE, e))?;
+    let manifest: BundleManifest =
+        serde_json::from_str(&content).map_err(|e| format!("invalid manifest: {}", e))?;
+
+    let archive = scratch.path().join(IMAGES_FILE);
+    job.progress(Some("verifying checksum".to_string()));
+    let checksum = bundle::sha256(&archive).await?;
+    if checksum != manifest.images_sha256 {
+        let reason = format!(
+            "checksum mismatch: archive {} != manifest {}",
+            checksum, manifest.images_sha256
+        );
+        logs.lock()
+            .unwrap()
+            .push_str(&format!("[bundle] {}\n", reason));
+        return Err(reason);
+    }
+
+    job.progress(Some(format!("loading {} images", manifest.images.len())));
+    run_phase(bundle::load_cmd(&archive), logs, "Load output:\n", "Load").await?;
+    job.progress(None);
+
+    let mut mismatches = Vec::new();
+    for image in &manifest.images {
+        match DockerClient::image_id(&image.image).await {
+            Some(id) if id == image.id => {}
+            Some(id) => mismatches.push(format!("{}: loaded {} != {}", image.image, id, image.id)),
+            None => mismatches.push(format!("{}: missing after load", image.image)),
+        }
+    }
+
+    let mut logs_lock = logs.lock().unwrap();
+    if !mismatches.is_empty() {
+        for mismatch in &mismatches {
+            logs_lock.push_str(&format!("[bundle] verify failed: {}\n", mismatch));
+        }
+        return Err(format!("{} images failed verification", mismatches.len()));
+    }
+    logs_lock.push_str(&format!(
+        "[bundle] verified {} images for {}\n",
+        manifest.images.len(),
+        manifest.projects.join(", ")
+    ));
+    drop(logs_lock);
+    job.toast(
+        ToastState::Success,
+        format!(
+            "Imported {} images for {}",
+            manifest.images.len(),
+            manifest.projects.join(", ")
+        ),
+    );
+    Ok(())
+}
@@ -1,11 +1,22 @@
 use crate::app::state::{App, DaemonAction};
+use crate::config::TimeoutSettings;
 use crate::docker::compose::ComposeProject;
 use crate::docker::daemon;
 use crate::docker::process::run_capture;
+use crate::notify::AppMessage;
 use crate::status::{Status, ToastState};
 
 impl App {
     fn require_daemon_password(&mut self, action: &str) -> bool {
+        if self.daemon_action_pending {
+            self.set_toast(
+                ToastState::Info,
+                "A daemon action is already running, please wait",
+                3,
+            );
+            return false;
+        }
+
         if self.password_input.is_empty() {
             self.set_toast(
                 ToastState::Warning,
@@ -19,13 +30,23 @@ impl App {
     }
 
     fn refresh_statuses_now(&mut self) {
-        self.daemon_probe_cooldown_ticks = 0;
+        self.last_daemon_probe = None;
         self.refresh_statuses();
     }
 
-    fn complete_daemon_action(&mut self) {
-        self.password_input.clear();
+    /// Clears the password prompt and hands back what was typed.
+    fn take_daemon_password(&mut self) -> String {
         self.daemon_start_mode = false;
+        std::mem::take(&mut self.password_input)
+    }
+
+    pub fn complete_daemon_action(&mut self, result: Result<String, String>) {
+        self.daemon_action_pending = false;
+        match result {
+            Ok(message) => self.set_toast(ToastState::Success, message, 4),
+            Err(error_msg) => self.set_toast(ToastState::Error, error_msg, 5),
+        }
+        self.refresh_statuses_now();
     }
 
     pub fn start_daemon(&mut self) {
@@ -33,57 +54,34 @@ impl App {
             return;
         }
 
-        match daemon::start(&self.password_input) {
-            Ok(()) => {
-                self.set_toast(ToastState::Success, "Docker daemon started", 3);
-                self.refresh_statuses_now();
-            }
-            Err(error_msg) => {
-                self.set_toast(ToastState::Error, error_msg, 5);
-            }
-        }
-
-        self.complete_daemon_action();
+        let password = self.take_daemon_password();
+        self.run_daemon_action("Starting Docker daemon", async move {
+            daemon::start(&password).await?;
+            Ok("Docker daemon started".to_string())
+        });
     }
 
-    pub fn stop_all_services(&mut self) -> Result<usize, String> {
+    /// Names of the projects that have to be brought down before the daemon
+    /// is stopped or restarted.
+    fn services_to_stop(&self) -> Vec<String> {
         let mut services_to_stop: Vec<String> = self
             .services
             .iter()
             .filter(|s| {
-                let status = s.status.lock().unwrap();
                 matches!(
-                    *status,
-                    Status::Running | Status::Starting | Status::Stopping | Status::Pulling
+                    s.status,
+                    Status::Running
+                        | Status::Paused
+                        | Status::Starting
+                        | Status::Stopping
+                        | Status::Pulling
+                        | Status::Building
                 )
             })
             .map(|s| s.name.clone())
             .collect();
-
-        if services_to_stop.is_empty() {
-            return Ok(0);
-        }
-
-        let total = services_to_stop.len();
         services_to_stop.sort();
-
-        for service_name in services_to_stop {
-            let project = ComposeProject::new(service_name.clone());
-            let cmd = project.down_cmd();
-            match run_capture(cmd) {
-                Ok(out) => {
-                    if !out.status.success() {
-                        return Err(format!("Failed to stop service {}", service_name));
-                    }
-                }
-                Err(e) => {
-                    return Err(format!("Error stopping service {}: {}", service_name, e));
-                }
-            }
-        }
-
-        self.refresh_statuses_now();
-        Ok(total)
+        services_to_stop
     }
 
     pub fn restart_daemon(&mut self) {
@@ -91,41 +89,15 @@ impl App {
             return;
         }
 
-        match self.stop_all_services() {
-            Ok(count) if count > 0 => {
-                self.set_toast(
-                    ToastState::Info,
-                    format!("Stopped {} service(s) before restart", count),
-                    2,
-                );
-            }
-            Ok(_) => {}
-            Err(e) => {
-                self.set_toast(
-                    ToastState::Error,
-                    format!("Failed to stop services: {}", e),
-                    5,
-                );
-                self.complete_daemon_action();
-                return;
-            }
-        }
-
-        match daemon::restart(&self.password_input) {
-            Ok(()) => {
-                self.set_toast(
-                    ToastState::Success,
-                    "Docker daemon restarted (services stopped first)",
-                    4,
-                );
-                self.refresh_statuses_now();
-            }
-            Err(error_msg) => {
-                self.set_toast(ToastState::Error, error_msg, 5);
-            }
-        }
-
-        self.complete_daemon_action();
+        let password = self.take_daemon_password();
+        let services = self.services_to_stop();
+        self.run_daemon_action("Restarting Docker daemon", async move {
+            stop_all_services(services)
+                .await
+                .map_err(|e| format!("Failed to stop services: {}", e))?;
+            daemon::restart(&password).await?;
+            Ok("Docker daemon restarted (services stopped first)".to_string())
+        });
     }
 
     pub fn stop_daemon(&mut self) {
@@ -133,41 +105,41 @@ impl App {
             return;
         }
 
-        match self.stop_all_services() {
-            Ok(count) if count > 0 => {
-                self.set_toast(
-                    ToastState::Info,
-                    format!("Stopped {} service(s) before stopping daemon", count),
-                    2,
-                );
-            }
-            Ok(_) => {}
-            Err(e) => {
-                self.set_toast(
-                    ToastState::Error,
-                    format!("Failed to stop services: {}", e),
-                    5,
-                );
-                self.complete_daemon_action();
-                return;
-            }
-        }
-
-        match daemon::stop(&self.password_input) {
-            Ok(()) => {
-                self.set_toast(
-                    ToastState::Success,
-                    "Docker daemon stopped (services stopped first)",
-                    4,
-                );
-                self.refresh_statuses_now();
-            }
-            Err(error_msg) => {
-                self.set_toast(ToastState::Error, error_msg, 5);
-            }
-        }
+        let password = self.take_daemon_password();
+        let services = self.services_to_stop();
+        self.run_daemon_action("Stopping Docker daemon", async move {
+            stop_all_services(services)
+                .await
+                .map_err(|e| format!("Failed to stop services: {}", e))?;
+            daemon::stop(&password).await?;
+            Ok("Docker daemon stopped (services stopped first)".to_string())
+        });
+    }
 
-        self.complete_daemon_action();
+    fn run_daemon_action(
+        &mut self,
+        progress: &str,
+        action: impl Future<Output = Result<String, String>> + Send + 'static,
+    ) {
+        self.daemon_action_pending = true;
+        self.set_toast(ToastState::Info, format!("{}...", progress), 30);
+
+        let notifier = self.notifier.clone();
+        let limit = TimeoutSettings::limit(self.settings.timeouts.daemon_secs);
+        tokio::spawn(async move {
+            let result = match limit {
+                Some(limit) => tokio::time::timeout(limit, action)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(format!(
+                            "Daemon action timed out after {}s",
+                            limit.as_secs()
+                        ))
+                    }),
+                None => action.await,
+            };
+            notifier.send(AppMessage::DaemonAction(result));
+        });
     }
 
     pub fn execute_daemon_action(&mut self) {
@@ -178,3 +150,20 @@ impl App {
         }
     }
 }
+
+async fn stop_all_services(services: Vec<String>) -> Result<(), String> {
+    for service_name in services {
+        let project = ComposeProject::new(service_name.clone());
+        match run_capture(project.down_cmd()).await {
+            Ok(out) => {
+                if !out.status.success() {
+                    return Err(format!("Failed to stop service {}", service_name));
+                }
+            }
+            Err(e) => {
+                return Err(format!("Error stopping service {}: {}", service_name, e));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,483 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::app::jobs::{JobKind, JobPhase, JobReporter};
+use crate::app::seeds::SeedState;
+use crate::app::services::run_phase;
+use crate::app::state::App;
+use crate::docker::archive::{self, ArchiveInfo};
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::dumps::{self, DbService};
+use crate::docker::volumes::{self, DataMount};
+use crate::log_buffer::SharedLogBuffer;
+use crate::notify::AppMessage;
+use crate::status::{Status, ToastState};
+
+/// A destructive data action waiting for the user to press `y`.
+#[derive(Clone)]
+pub enum DataConfirm {
+    Restore(PathBuf),
+    /// Replay a database dump into the service it was taken from.
+    LoadDump {
+        path: PathBuf,
+        service: String,
+    },
+    Wipe,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum BackupKind {
+    Snapshot,
+    /// Logical dump of one database service.
+    Dump(String),
+}
+
+/// A volume snapshot or database dump listed in the data overlay.
+pub struct Backup {
+    pub kind: BackupKind,
+    pub archive: ArchiveInfo,
+}
+
+/// What a data job needs the project to be doing.
+pub(crate) enum Needs {
+    Anything,
+    Stopped,
+    Running,
+}
+
+/// Volumes, bind mounts and snapshots of the project the data overlay is
+/// open for.
+pub struct DataView {
+    pub project: String,
+    /// `None` until the compose file has been read.
+    pub mounts: Option<Vec<DataMount>>,
+    /// `None` while sizes are being measured.
+    pub sizes: Option<BTreeMap<String, u64>>,
+    /// Services running a known database; `None` until resolved.
+    pub databases: Option<Vec<DbService>>,
+    /// Snapshots and dumps, newest first.
+    pub backups: Vec<Backup>,
+    pub selected: usize,
+    pub confirm: Option<DataConfirm>,
+}
+
+/// Directory a project's snapshots are written to, relative to the working
+/// directory.
+pub fn snapshot_dir(project: &str) -> PathBuf {
+    Path::new("containers").join(project).join("snapshots")
+}
+
+/// Directory a project's database dumps are written to.
+pub fn dump_dir(backups_dir: &str, project: &str) -> PathBuf {
+    Path::new(backups_dir).join(project)
+}
+
+fn list_backups(project: &str, backups_dir: &str) -> Vec<Backup> {
+    let snapshots = archive::list(&snapshot_dir(project))
+        .into_iter()
+        .map(|archive| Backup {
+            kind: BackupKind::Snapshot,
+            archive,
+        });
+    let dumps = archive::list_with_extensions(&dump_dir(backups_dir, project), &["sql", "rdb"])
+        .into_iter()
+        .filter_map(|archive| {
+            let service = dumps::dump_service(&archive.name)?.to_string();
+            Some(Backup {
+                kind: BackupKind::Dump(service),
+                archive,
+            })
+        });
+    let mut backups: Vec<Backup> = snapshots.chain(dumps).collect();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.archive.modified));
+    backups
+}
+
+impl App {
+    pub fn open_data_view(&mut self) {
+        let Some(i) = self.state.selected() else {
+            return;
+        };
+        let project = self.services[i].name.clone();
+        self.data_view = Some(DataView {
+            project: project.clone(),
+            mounts: None,
+            sizes: None,
+            databases: None,
+            backups: list_backups(&project, &self.settings.backups.dir),
+            selected: 0,
+            confirm: None,
+        });
+        self.load_data_mounts(project);
+    }
+
+    pub fn close_data_view(&mut self) {
+        self.data_view = None;
+    }
+
+    /// Reloads mounts, sizes and snapshots if the overlay shows `project`.
+    pub fn refresh_data_view(&mut self, project: &str) {
+        let Some(view) = self
+            .data_view
+            .as_mut()
+            .filter(|view| view.project == project)
+        else {
+            return;
+        };
+        view.sizes = None;
+        view.backups = list_backups(project, &self.settings.backups.dir);
+        view.selected = view.selected.min(view.backups.len().saturating_sub(1));
+        self.load_data_mounts(project.to_string());
+    }
+
+    fn load_data_mounts(&self, project: String) {
+        let notifier = self.notifier.clone();
+        let image = self.settings.data.helper_image.clone();
+        tokio::spawn(async move {
+            let mounts = match ComposeFile::load(&project).await {
+                Some(compose) => volumes::data_mounts(&project, &compose),
+                None => Vec::new(),
+            };
+            let databases = match ComposeFile::resolved(&project).await {
+                Some(compose) => dumps::db_services(&compose),
+                None => Vec::new(),
+            };
+            notifier.send(AppMessage::DataMounts {
+                project: project.clone(),
+                mounts: mounts.clone(),
+                databases,
+            });
+            let present = volumes::present_mounts(&mounts).await;
+            let sizes = volumes::mount_sizes(&image, &present).await;
+            notifier.send(AppMessage::DataSizes { project, sizes });
+        });
+    }
+
+    pub fn apply_data_mounts(
+        &mut self,
+        project: &str,
+        mounts: Vec<DataMount>,
+        databases: Vec<DbService>,
+    ) {
+        if let Some(view) = self
+            .data_view
+            .as_mut()
+            .filter(|view| view.project == project)
+        {
+            view.mounts = Some(mounts);
+            view.databases = Some(databases);
+        }
+    }
+
+    pub fn apply_data_sizes(&mut self, project: &str, sizes: BTreeMap<String, u64>) {
+        if let Some(view) = self
+            .data_view
+            .as_mut()
+            .filter(|view| view.project == project)
+        {
+            view.sizes = Some(sizes);
+        }
+    }
+
+    pub fn data_select_next(&mut self) {
+        if let Some(view) = self.data_view.as_mut()
+            && view.selected + 1 < view.backups.len()
+        {
+            view.selected += 1;
+        }
+    }
+
+    pub fn data_select_previous(&mut self) {
+        if let Some(view) = self.data_view.as_mut() {
+            view.selected = view.selected.saturating_sub(1);
+        }
+    }
+
+    /// Archives every present mount of the project into a timestamped tar.
+    pub fn snapshot_data(&mut self) {
+        let Some(i) = self.data_job_target(Needs::Anything) else {
+            return;
+        };
+        let Some(mounts) = self.data_view_mounts() else {
+            return;
+        };
+        let project = self.services[i].name.clone();
+        let status = self.services[i].status.clone();
+        let logs = Arc::clone(&self.services[i].logs);
+        let image = self.settings.data.helper_image.clone();
+        let running = status != Status::Stopped;
+        self.spawn_job(
+            i,
+            JobKind::Snapshot,
+            JobPhase::Save,
+            status,
+            |job| async move { snapshot(&project, mounts, &image, running, &logs, &job).await },
+        );
+        self.set_toast(ToastState::Info, "Snapshotting data", 3);
+    }
+
+    /// Asks for confirmation before restoring the highlighted snapshot or
+    /// loading the highlighted dump.
+    pub fn request_restore(&mut self) {
+        let Some(view) = self.data_view.as_mut() else {
+            return;
+        };
+        let Some(backup) = view.backups.get(view.selected) else {
+            return;
+        };
+        let path = backup.archive.path.clone();
+        view.confirm = Some(match &backup.kind {
+            BackupKind::Snapshot => DataConfirm::Restore(path),
+            BackupKind::Dump(service) => DataConfirm::LoadDump {
+                path,
+                service: service.clone(),
+            },
+        });
+    }
+
+    pub fn request_wipe(&mut self) {
+        if let Some(view) = self.data_view.as_mut() {
+            view.confirm = Some(DataConfirm::Wipe);
+        }
+    }
+
+    pub fn cancel_data_confirm(&mut self) {
+        if let Some(view) = self.data_view.as_mut() {
+            view.confirm = None;
+        }
+    }
+
+    pub fn confirm_data_action(&mut self) {
+        let Some(confirm) = self.data_view.as_mut().and_then(|view| view.confirm.take()) else {
+            return;
+        };
+        if let DataConfirm::LoadDump { path, service } = &confirm {
+            self.load_dump(path.clone(), service.clone());
+            return;
+        }
+        let Some(i) = self.data_job_target(Needs::Stopped) else {
+            return;
+        };
+        let Some(mounts) = self.data_view_mounts() else {
+            return;
+        };
+
+        let project = self.services[i].name.clone();
+        let status = self.services[i].status.clone();
+        let logs = Arc::clone(&self.services[i].logs);
+        let image = self.settings.data.helper_image.clone();
+        match confirm {
+            DataConfirm::Restore(archive) => {
+                let name = project.clone();
+                self.spawn_job(
+                    i,
+                    JobKind::Restore,
+                    JobPhase::Load,
+                    status,
+                    |job| async move { restore(&name, mounts, &archive, &image, &logs, &job).await },
+                );
+                self.set_toast(ToastState::Info, format!("Restoring {}", project), 3);
+            }
+            DataConfirm::LoadDump { .. } => {}
+            DataConfirm::Wipe => {
+                let name = project.clone();
+                self.spawn_job(i, JobKind::Wipe, JobPhase::Wipe, status, |job| async move {
+                    wipe(&name, mounts, &image, &logs, &job).await
+                });
+                self.set_toast(ToastState::Warning, format!("Wiping {}", project), 3);
+            }
+        }
+    }
+
+    /// Index of the project in the data overlay, if a data job may run on
+    /// it now.
+    pub(crate) fn data_job_target(&mut self, needs: Needs) -> Option<usize> {
+        let project = self.data_view.as_ref()?.project.clone();
+        let i = self.service_index(&project)?;
+
+        if !self.docker_daemon_running {
+            self.set_toast(ToastState::Error, "Docker daemon not responding", 5);
+            return None;
+        }
+        if self.active_job(&project).is_some() {
+            self.set_toast(
+                ToastState::Warning,
+                format!(
+                    "{} is busy, press {} to cancel",
+                    project, self.keybinds.services.cancel_job
+                ),
+                3,
+            );
+            return None;
+        }
+        match needs {
+            Needs::Stopped
+                if !matches!(self.services[i].status, Status::Stopped | Status::Error) =>
+            {
+                self.set_toast(ToastState::Warning, format!("Stop {} first", project), 3);
+                return None;
+            }
+            Needs::Running if self.services[i].status != Status::Running => {
+                self.set_toast(ToastState::Warning, format!("Start {} first", project), 3);
+                return None;
+            }
+            _ => {}
+        }
+        Some(i)
+    }
+
+    fn data_view_mounts(&mut self) -> Option<Vec<DataMount>> {
+        let view = self.data_view.as_ref()?;
+        let project = view.project.clone();
+        let mounts = view.mounts.clone().unwrap_or_default();
+        if mounts.is_empty() {
+            self.set_toast(
+                ToastState::Info,
+                format!("{} has no volumes or bind mounts", project),
+                3,
+            );
+            return None;
+        }
+        Some(mounts)
+    }
+}
+
+async fn snapshot(
+    project: &str,
+    mounts: Vec<DataMount>,
+    image: &str,
+    running: bool,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    let mounts = volumes::present_mounts(&mounts).await;
+    if mounts.is_empty() {
+        return Err("no data to snapshot yet".to_string());
+    }
+    if running {
+        logs.lock()
+            .unwrap()
+            .push_str("[data] project is running; the snapshot may not be consistent\n");
+    }
+
+    let dir = std::env::current_dir()
+        .map_err(|e| e.to_string())?
+        .join(snapshot_dir(project));
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    let name = format!("{}-{}.tar", project, archive::timestamp(SystemTime::now()));
+
+    job.progress(Some(format!("archiving {} mounts", mounts.len())));
+    run_phase(
+        volumes::snapshot_cmd(image, &mounts, &dir, &name),
+        logs,
+        "Snapshot output:\n",
+        "Snapshot",
+    )
+    .await?;
+    job.progress(None);
+    SeedState::save_for_snapshot(project, &dir.join(&name)).await?;
+
+    logs.lock()
+        .unwrap()
+        .push_str(&format!("[data] wrote {}\n", dir.join(&name).display()));
+    job.toast(ToastState::Success, format!("Snapshot {} written", name));
+    Ok(())
+}
+
+/// Empties the mounts the snapshot covers and unpacks it over them.
+async fn restore(
+    project: &str,
+    mounts: Vec<DataMount>,
+    archive: &Path,
+    image: &str,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    let keys = volumes::archive_keys(archive)
+        .await
+        .ok_or_else(|| format!("cannot read {}", archive.display()))?;
+    let mounts: Vec<DataMount> = mounts
+        .into_iter()
+        .filter(|mount| keys.contains(&mount.key))
+        .collect();
+    if mounts.is_empty() {
+        return Err("snapshot holds none of the project's mounts".to_string());
+    }
+    let (mounts, kept) = volumes::partition_erasable(mounts);
+    log_kept_mounts(&kept, logs);
+    if mounts.is_empty() {
+        return Err("snapshot only covers mounts outside the project directory".to_string());
+    }
+
+    let dir = std::env::current_dir()
+        .map_err(|e| e.to_string())?
+        .join(archive.parent().unwrap_or(Path::new(".")));
+    let name = archive
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    job.progress(Some(format!("restoring {} mounts", mounts.len())));
+    run_phase(
+        volumes::restore_cmd(image, &mounts, &dir, &name)?,
+        logs,
+        "Restore output:\n",
+        "Restore",
+    )
+    .await?;
+    job.progress(None);
+    SeedState::reset(project, Some(archive)).await?;
+
+    logs.lock()
+        .unwrap()
+        .push_str(&format!("[data] restored {}\n", name));
+    job.toast(ToastState::Success, format!("Restored {}", name));
+    Ok(())
+}
+
+async fn wipe(
+    project: &str,
+    mounts: Vec<DataMount>,
+    image: &str,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    let (mounts, kept) = volumes::partition_erasable(mounts);
+    log_kept_mounts(&kept, logs);
+    let mounts = volumes::present_mounts(&mounts).await;
+    if mounts.is_empty() {
+        return SeedState::reset(project, None).await;
+    }
+    run_phase(
+        volumes::wipe_cmd(image, &mounts),
+        logs,
+        "Wipe output:\n",
+        "Wipe",
+    )
+    .await?;
+    SeedState::reset(project, None).await?;
+
+    let labels: Vec<&str> = mounts.iter().map(|mount| mount.label.as_str()).collect();
+    logs.lock()
+        .unwrap()
+        .push_str(&format!("[data] wiped {}\n", labels.join(", ")));
+    job.toast(ToastState::Success, "Data wiped".to_string());
+    Ok(())
+}
+
+/// Notes the bind mounts a wipe or restore leaves untouched because they
+/// point outside the project directory.
+fn log_kept_mounts(kept: &[DataMount], logs: &SharedLogBuffer) {
+    let mut logs = logs.lock().unwrap();
+    for mount in kept {
+        logs.push_str(&format!(
+            "[data] warning: skipped {} ({}): outside the project directory\n",
+            mount.label,
+            mount.target()
+        ));
+    }
+}
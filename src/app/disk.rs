@@ -0,0 +1,226 @@
+use crate::app::state::App;
+use crate::config::TimeoutSettings;
+use crate::docker::disk::{self, DiskUsage, PruneOptions, UsageItem, UsageKind};
+use crate::docker::process::run_capture;
+use crate::docker::units::format_bytes;
+use crate::notify::AppMessage;
+use crate::status::ToastState;
+
+/// The disk usage overlay. With a `plan`, it previews a prune instead of
+/// listing everything.
+pub struct DiskView {
+    pub usage: Option<DiskUsage>,
+    pub error: Option<String>,
+    pub selected: usize,
+    pub options: PruneOptions,
+    pub plan: Option<Vec<UsageItem>>,
+    pub pruning: bool,
+}
+
+impl DiskView {
+    /// Rows the overlay lists: the prune preview or every item.
+    pub fn rows(&self) -> &[UsageItem] {
+        match (&self.plan, &self.usage) {
+            (Some(plan), _) => plan,
+            (None, Some(usage)) => &usage.items,
+            (None, None) => &[],
+        }
+    }
+}
+
+impl App {
+    pub fn open_disk_usage(&mut self) {
+        self.disk_view = Some(DiskView {
+            usage: None,
+            error: None,
+            selected: 0,
+            options: PruneOptions::default(),
+            plan: None,
+            pruning: false,
+        });
+        self.refresh_disk_usage();
+    }
+
+    pub fn close_disk_usage(&mut self) {
+        self.disk_view = None;
+    }
+
+    pub fn refresh_disk_usage(&mut self) {
+        if !self.docker_daemon_running {
+            if let Some(view) = self.disk_view.as_mut() {
+                view.error = Some("Docker daemon not responding".to_string());
+            }
+            return;
+        }
+        let projects: Vec<String> = self
+            .services
+            .iter()
+            .map(|service| service.name.clone())
+            .collect();
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            notifier.send(AppMessage::DiskUsage(disk::usage(&projects).await));
+        });
+    }
+
+    pub fn apply_disk_usage(&mut self, result: Result<DiskUsage, String>) {
+        let Some(view) = self.disk_view.as_mut() else {
+            return;
+        };
+        match result {
+            Ok(usage) => {
+                if view.plan.is_some() {
+                    view.plan = Some(disk::prune_plan(&usage, view.options));
+                }
+                view.usage = Some(usage);
+                view.error = None;
+                view.selected = view.selected.min(view.rows().len().saturating_sub(1));
+            }
+            Err(reason) => view.error = Some(reason),
+        }
+    }
+
+    pub fn disk_select_next(&mut self) {
+        if let Some(view) = self.disk_view.as_mut()
+            && view.selected + 1 < view.rows().len()
+        {
+            view.selected += 1;
+        }
+    }
+
+    pub fn disk_select_previous(&mut self) {
+        if let Some(view) = self.disk_view.as_mut() {
+            view.selected = view.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn toggle_prune_unused_images(&mut self) {
+        self.update_prune_options(|options| options.unused_images = !options.unused_images);
+    }
+
+    pub fn toggle_prune_managed_volumes(&mut self) {
+        self.update_prune_options(|options| options.managed_volumes = !options.managed_volumes);
+    }
+
+    fn update_prune_options(&mut self, change: impl FnOnce(&mut PruneOptions)) {
+        let Some(view) = self.disk_view.as_mut() else {
+            return;
+        };
+        change(&mut view.options);
+        if let (Some(_), Some(usage)) = (&view.plan, &view.usage) {
+            view.plan = Some(disk::prune_plan(usage, view.options));
+            view.selected = view.selected.min(view.rows().len().saturating_sub(1));
+        }
+    }
+
+    /// Lists what a prune would remove, for confirmation.
+    pub fn preview_prune(&mut self) {
+        let Some(view) = self.disk_view.as_mut() else {
+            return;
+        };
+        let Some(usage) = &view.usage else {
+            return;
+        };
+        view.plan = Some(disk::prune_plan(usage, view.options));
+        view.selected = 0;
+    }
+
+    pub fn cancel_prune_preview(&mut self) {
+        if let Some(view) = self.disk_view.as_mut() {
+            view.plan = None;
+            view.selected = 0;
+        }
+    }
+
+    /// Removes exactly the previewed items.
+    pub fn confirm_prune(&mut self) {
+        if !self.docker_daemon_running {
+            self.set_toast(ToastState::Error, "Docker daemon not responding", 5);
+            return;
+        }
+        let Some(view) = self.disk_view.as_mut() else {
+            return;
+        };
+        if view.pruning {
+            return;
+        }
+        let Some(plan) = view.plan.take() else {
+            return;
+        };
+        view.selected = 0;
+        if plan.is_empty() {
+            self.set_toast(ToastState::Info, "Nothing to prune", 3);
+            return;
+        }
+        view.pruning = true;
+        self.set_toast(
+            ToastState::Info,
+            format!("Pruning {} items...", plan.len()),
+            30,
+        );
+
+        let notifier = self.notifier.clone();
+        let limit = TimeoutSettings::limit(self.settings.timeouts.down_secs);
+        tokio::spawn(async move {
+            let result = match limit {
+                Some(limit) => tokio::time::timeout(limit, prune(plan))
+                    .await
+                    .unwrap_or_else(|_| Err(format!("Prune timed out after {}s", limit.as_secs()))),
+                None => prune(plan).await,
+            };
+            notifier.send(AppMessage::DiskPrune(result));
+        });
+    }
+
+    pub fn complete_prune(&mut self, result: Result<String, String>) {
+        if let Some(view) = self.disk_view.as_mut() {
+            view.pruning = false;
+        }
+        match result {
+            Ok(message) => self.set_toast(ToastState::Success, message, 5),
+            Err(reason) => self.set_toast(ToastState::Error, reason, 5),
+        }
+        self.refresh_disk_usage();
+        self.refresh_images();
+    }
+}
+
+/// Removes `plan` kind by kind and measures what was reclaimed from the
+/// `docker system df` totals before and after.
+async fn prune(plan: Vec<UsageItem>) -> Result<String, String> {
+    let used = |totals: Vec<(UsageKind, disk::UsageTotal)>| -> u64 {
+        totals.iter().map(|(_, total)| total.size).sum()
+    };
+    let before = used(disk::totals().await?);
+
+    let mut failures = Vec::new();
+    for (kind, cmd) in disk::remove_cmds(&plan) {
+        let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+        if !out.status.success() {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            let reason = stderr
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or("command failed")
+                .to_string();
+            failures.push(format!("{}: {}", kind.label(), reason));
+        }
+    }
+
+    let after = used(disk::totals().await?);
+    let reclaimed = format_bytes(before.saturating_sub(after) as f64);
+    if failures.is_empty() {
+        Ok(format!(
+            "Pruned {} items, reclaimed {}",
+            plan.len(),
+            reclaimed
+        ))
+    } else {
+        Err(format!(
+            "Reclaimed {}, but {}",
+            reclaimed,
+            failures.join("; ")
+        ))
+    }
+}
@@ -0,0 +1,285 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::app::data::{Needs, dump_dir};
+use crate::app::jobs::{JobKind, JobPhase, JobReporter};
+use crate::app::services::run_phase;
+use crate::app::state::App;
+use crate::docker::archive;
+use crate::docker::compose::ComposeProject;
+use crate::docker::dumps::{self, DbService};
+use crate::docker::process::run_with_files;
+use crate::log_buffer::SharedLogBuffer;
+use crate::status::ToastState;
+
+impl App {
+    /// Dumps every database service of the project in the data overlay.
+    pub fn dump_databases(&mut self) {
+        let Some(i) = self.data_job_target(Needs::Running) else {
+            return;
+        };
+        let databases = self
+            .data_view
+            .as_ref()
+            .and_then(|view| view.databases.clone())
+            .unwrap_or_default();
+        let project = self.services[i].name.clone();
+        if databases.is_empty() {
+            self.set_toast(
+                ToastState::Info,
+                format!("{} runs no known database", project),
+                3,
+            );
+            return;
+        }
+
+        let status = self.services[i].status.clone();
+        let logs = Arc::clone(&self.services[i].logs);
+        let dir = dump_dir(&self.settings.backups.dir, &project);
+        let keep = self.settings.backups.keep;
+        self.spawn_job(i, JobKind::Dump, JobPhase::Save, status, |job| async move {
+            dump_all(&project, databases, &dir, keep, &logs, &job).await
+        });
+        self.set_toast(ToastState::Info, "Dumping databases", 3);
+    }
+
+    /// Streams a dump back into the service it was taken from.
+    pub(crate) fn load_dump(&mut self, path: PathBuf, service: String) {
+        let Some(i) = self.data_job_target(Needs::Running) else {
+            return;
+        };
+        let Some(database) = self
+            .data_view
+            .as_ref()
+            .and_then(|view| view.databases.as_ref())
+            .and_then(|databases| databases.iter().find(|db| db.service == service))
+            .cloned()
+        else {
+            self.set_toast(
+                ToastState::Warning,
+                format!("{} is no longer a known database service", service),
+                3,
+            );
+            return;
+        };
+
+        let project = self.services[i].name.clone();
+        let status = self.services[i].status.clone();
+        let logs = Arc::clone(&self.services[i].logs);
+        self.spawn_job(
+            i,
+            JobKind::Restore,
+            JobPhase::Load,
+            status,
+            |job| async move { load(&project, &database, &path, &logs, &job).await },
+        );
+        self.set_toast(
+            ToastState::Info,
+            format!("Loading dump into {}", service),
+            3,
+        );
+    }
+}
+
+async fn dump_all(
+    project: &str,
+    databases: Vec<DbService>,
+    dir: &Path,
+    keep: usize,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    let dir = std::env::current_dir()
+        .map_err(|e| e.to_string())?
+        .join(dir);
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    let compose = ComposeProject::new(project);
+    let timestamp = archive::timestamp(SystemTime::now());
+
+    for database in &databases {
+        job.progress(Some(format!("dumping {}", database.service)));
+        let name = dumps::dump_name(&database.service, &timestamp, database.engine);
+        let output = dir.join(&name);
+        // Written under a temporary name so a failed or cancelled dump never
+        // shows up as a backup.
+        let partial = dir.join(format!("{}.partial", name));
+        let result = dump_one(&compose, database, &partial, logs).await;
+        if let Err(reason) = result {
+            tokio::fs::remove_file(&partial).await.ok();
+            return Err(format!("{}: {}", database.service, reason));
+        }
+        tokio::fs::rename(&partial, &output)
+            .await
+            .map_err(|e| e.to_string())?;
+        logs.lock()
+            .unwrap()
+            .push_str(&format!("[backup] wrote {}\n", output.display()));
+        prune_dumps(&dir, &database.service, keep, logs).await;
+    }
+    job.progress(None);
+
+    job.toast(
+        ToastState::Success,
+        format!("Dumped {} databases", databases.len()),
+    );
+    Ok(())
+}
+
+async fn dump_one(
+    compose: &ComposeProject,
+    database: &DbService,
+    output: &Path,
+    logs: &SharedLogBuffer,
+) -> Result<(), String> {
+    if let Some(cmd) = database.dump_cmd(compose) {
+        let file = tokio::fs::File::create(output)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_std()
+            .await;
+        let header = format!("Dump output ({}):\n", database.service);
+        return match run_with_files(cmd, None, Some(file), Arc::clone(logs), Some(&header)).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err("dump exited with non-zero status".to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+    }
+
+    // Redis: write the RDB file now, then copy it out of the container.
+    run_phase(
+        database.save_cmd(compose),
+        logs,
+        &format!("Save output ({}):\n", database.service),
+        "Save",
+    )
+    .await?;
+    let rdb = database
+        .rdb_path(compose)
+        .await
+        .ok_or_else(|| "cannot read the RDB location".to_string())?;
+    run_phase(
+        dumps::copy_cmd(
+            compose,
+            &format!("{}:{}", database.service, rdb),
+            &output.display().to_string(),
+        ),
+        logs,
+        "Copy output:\n",
+        "Copy",
+    )
+    .await
+}
+
+/// Deletes all but the newest `keep` dumps of `service`.
+async fn prune_dumps(dir: &Path, service: &str, keep: usize, logs: &SharedLogBuffer) {
+    if keep == 0 {
+        return;
+    }
+    let stale = archive::list_with_extensions(dir, &["sql", "rdb"])
+        .into_iter()
+        .filter(|dump| dumps::dump_service(&dump.name) == Some(service))
+        .skip(keep);
+    for dump in stale {
+        if tokio::fs::remove_file(&dump.path).await.is_ok() {
+            logs.lock()
+                .unwrap()
+                .push_str(&format!("[backup] removed old dump {}\n", dump.name));
+        }
+    }
+}
+
+async fn load(
+    project: &str,
+    database: &DbService,
+    path: &Path,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    let compose = ComposeProject::new(project);
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    job.progress(Some(format!("loading {} into {}", name, database.service)));
+
+    if let Some(cmd) = database.load_cmd(&compose) {
+        let file = tokio::fs::File::open(path)
+            .await
+            .map_err(|e| e.to_string())?
+            .into_std()
+            .await;
+        let header = format!("Load output ({}):\n", database.service);
+        match run_with_files(cmd, Some(file), None, Arc::clone(logs), Some(&header)).await {
+            Ok(true) => {}
+            Ok(false) => return Err("load exited with non-zero status".to_string()),
+            Err(e) => return Err(e.to_string()),
+        }
+    } else {
+        load_rdb(&compose, database, path, logs).await?;
+    }
+    job.progress(None);
+
+    logs.lock().unwrap().push_str(&format!(
+        "[backup] loaded {} into {}\n",
+        name, database.service
+    ));
+    job.toast(
+        ToastState::Success,
+        format!("Loaded {} into {}", name, database.service),
+    );
+    Ok(())
+}
+
+/// Redis reads its RDB file only on startup, so the service is stopped
+/// (which saves the current data), the file replaced, and started again.
+async fn load_rdb(
+    compose: &ComposeProject,
+    database: &DbService,
+    path: &Path,
+    logs: &SharedLogBuffer,
+) -> Result<(), String> {
+    let rdb = database
+        .rdb_path(compose)
+        .await
+        .ok_or_else(|| "cannot read the RDB location".to_string())?;
+    if database.appendonly(compose).await {
+        logs.lock().unwrap().push_str(
+            "[backup] appendonly is on; Redis will load its AOF instead of this RDB file\n",
+        );
+    }
+    let source = std::env::current_dir()
+        .map_err(|e| e.to_string())?
+        .join(path);
+
+    run_phase(
+        dumps::stop_service_cmd(compose, &database.service),
+        logs,
+        "Stop output:\n",
+        "Stop",
+    )
+    .await?;
+    let copied = run_phase(
+        dumps::copy_cmd(
+            compose,
+            &source.display().to_string(),
+            &format!("{}:{}", database.service, rdb),
+        ),
+        logs,
+        "Copy output:\n",
+        "Copy",
+    )
+    .await;
+    // Start again even if the copy failed, so the project is not left
+    // half-stopped.
+    run_phase(
+        dumps::start_service_cmd(compose, &database.service),
+        logs,
+        "Start output:\n",
+        "Start",
+    )
+    .await?;
+    copied
+}
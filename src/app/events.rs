@@ -1,7 +1,15 @@
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::app::state::App;
-use crate::docker::events::{spawn_projects_listener, ProjectEventTargets};
+use crate::docker::crash::capture_crash_report;
+use crate::docker::events::{
+    ContainerCrash, ContainerEvent, died_from_kill, spawn_projects_listener,
+};
+use crate::docker::stats::spawn_stats_listener;
+use crate::notify::AppMessage;
+use crate::status::Status;
 
 impl App {
     pub fn start_event_listeners(&mut self) {
@@ -13,21 +21,29 @@ impl App {
             return;
         }
 
-        let mut project_targets = HashMap::new();
-        for service in &self.services {
-            project_targets.insert(
-                service.name.clone(),
-                ProjectEventTargets {
-                    status: std::sync::Arc::clone(&service.status),
-                    events: std::sync::Arc::clone(&service.events),
-                    pull_progress: std::sync::Arc::clone(&service.pull_progress),
-                },
-            );
-        }
+        let project_events: HashMap<_, _> = self
+            .services
+            .iter()
+            .map(|service| (service.name.clone(), Arc::clone(&service.events)))
+            .collect();
 
-        self.event_listener_handle = Some(spawn_projects_listener(project_targets));
+        self.stop_event_listeners();
+        self.event_listener_handle = Some(spawn_projects_listener(
+            project_events,
+            self.notifier.clone(),
+        ));
         self.event_listener_running = true;
 
+        if self.settings.stats.enabled {
+            let projects = self
+                .services
+                .iter()
+                .map(|service| service.name.clone())
+                .collect();
+            self.stats_listener_handle =
+                Some(spawn_stats_listener(projects, self.notifier.clone()));
+        }
+
         for service in &self.services {
             let mut events = service.events.lock().unwrap();
             if events.is_empty() {
@@ -40,6 +56,88 @@ impl App {
         if let Some(mut handle) = self.event_listener_handle.take() {
             handle.signal_shutdown();
         }
+        if let Some(mut handle) = self.stats_listener_handle.take() {
+            handle.signal_shutdown();
+        }
         self.event_listener_running = false;
     }
+
+    pub fn apply_container_event(&mut self, event: ContainerEvent) {
+        let Some(index) = self.service_index(&event.project) else {
+            return;
+        };
+        let service = &mut self.services[index];
+        let action = event.action.as_str();
+
+        let mut crashed = false;
+        let next_status = match action {
+            "create" | "restart" => Some(Status::Starting),
+            "start" | "unpause" => Some(Status::Running),
+            "pause" => Some(Status::Paused),
+            "stop" | "destroy" => Some(Status::Stopped),
+            "kill" => {
+                service
+                    .kills
+                    .insert(event.container.clone(), Instant::now());
+                None
+            }
+            "die" => {
+                let killed_at = service.kills.remove(&event.container);
+                if matches!(service.status, Status::Stopping | Status::Stopped)
+                    || event.exit_code == "0"
+                    || died_from_kill(killed_at, &event.exit_code, Instant::now())
+                {
+                    Some(Status::Stopped)
+                } else {
+                    if !event.exit_code.is_empty() {
+                        crashed = true;
+                        service.crashes.push(ContainerCrash {
+                            container: event.container.clone(),
+                            exit_code: event.exit_code.clone(),
+                            at: Instant::now(),
+                        });
+                    }
+                    Some(Status::Error)
+                }
+            }
+            _ if action.starts_with("health_status: ") => {
+                if action.ends_with("healthy") {
+                    Some(Status::Running)
+                } else if action.ends_with("unhealthy") {
+                    Some(Status::Error)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(next_status) = next_status {
+            if !matches!(next_status, Status::Pulling | Status::Building) {
+                service.pull_progress = None;
+            }
+
+            let keep_crash_loop =
+                service.status == Status::CrashLoop && next_status != Status::Running;
+            if !keep_crash_loop
+                && (!matches!(service.status, Status::Pulling | Status::Building)
+                    || matches!(next_status, Status::Running | Status::Error))
+            {
+                service.status = next_status;
+            }
+        }
+
+        if crashed {
+            let notifier = self.notifier.clone();
+            let log_lines = self.settings.crash_reports.log_lines;
+            tokio::spawn(async move {
+                let report =
+                    capture_crash_report(&event.container, &event.exit_code, log_lines).await;
+                notifier.send(AppMessage::CrashReport {
+                    service: event.project,
+                    report,
+                });
+            });
+        }
+    }
 }
@@ -0,0 +1,159 @@
+use std::time::{Duration, Instant};
+
+use crate::app::state::App;
+use crate::config::IdleAction;
+use crate::docker::events::append_event_log;
+use crate::status::{Status, ToastState};
+
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const IDLE_WARNING_LEAD: Duration = Duration::from_secs(60);
+
+pub struct IdleTracker {
+    idle_since: Instant,
+    last_check: Instant,
+    last_net_bytes: f64,
+    warned: bool,
+}
+
+impl App {
+    pub fn check_idle_projects(&mut self) {
+        if !self.settings.stats.enabled || self.last_idle_check.elapsed() < IDLE_CHECK_INTERVAL {
+            return;
+        }
+        self.last_idle_check = Instant::now();
+
+        for index in 0..self.services.len() {
+            let name = self.services[index].name.clone();
+            let Some(policy) = self
+                .settings
+                .project(&name)
+                .and_then(|project| project.idle.clone())
+            else {
+                continue;
+            };
+
+            if self.services[index].status != Status::Running {
+                self.idle_trackers.remove(&name);
+                continue;
+            }
+
+            let Some(totals) = self.services[index].stats.totals() else {
+                continue;
+            };
+
+            let now = Instant::now();
+            let net_bytes = totals.net_rx + totals.net_tx;
+            let tracker = self
+                .idle_trackers
+                .entry(name.clone())
+                .or_insert_with(|| IdleTracker {
+                    idle_since: now,
+                    last_check: now,
+                    last_net_bytes: net_bytes,
+                    warned: false,
+                });
+
+            let elapsed = now.duration_since(tracker.last_check).as_secs_f64();
+            let net_rate = if elapsed > 0.0 {
+                (net_bytes - tracker.last_net_bytes).max(0.0) / elapsed
+            } else {
+                0.0
+            };
+            tracker.last_check = now;
+            tracker.last_net_bytes = net_bytes;
+
+            let idle = totals.cpu_percent < policy.max_cpu_percent
+                && net_rate < policy.max_net_bytes_per_sec;
+            if !idle {
+                tracker.idle_since = now;
+                tracker.warned = false;
+                continue;
+            }
+
+            let limit = Duration::from_secs(policy.minutes.max(1) * 60);
+            let idle_for = now.duration_since(tracker.idle_since);
+
+            if idle_for >= limit {
+                // A project busy with another job stays pending until it is done.
+                if self.active_job(&name).is_none()
+                    && self.auto_stop_idle_project(index, policy.action, policy.minutes)
+                {
+                    self.idle_trackers.remove(&name);
+                }
+            } else if idle_for + IDLE_WARNING_LEAD >= limit && !tracker.warned {
+                tracker.warned = true;
+                let verb = match policy.action {
+                    IdleAction::Down => "stop",
+                    IdleAction::Pause => "pause",
+                };
+                self.set_toast(
+                    ToastState::Warning,
+                    format!(
+                        "{} idle, auto-{} in 1 min. Press {} to snooze",
+                        name, verb, self.keybinds.services.snooze_idle
+                    ),
+                    10,
+                );
+            }
+        }
+    }
+
+    /// When the next idle check is due, or `None` if no project has an idle policy.
+    pub fn next_idle_check(&self) -> Option<Instant> {
+        let has_policy = self.services.iter().any(|service| {
+            self.settings
+                .project(&service.name)
+                .is_some_and(|project| project.idle.is_some())
+        });
+        (self.settings.stats.enabled && has_policy)
+            .then(|| self.last_idle_check + IDLE_CHECK_INTERVAL)
+    }
+
+    pub fn snooze_idle_projects(&mut self) {
+        let mut snoozed = Vec::new();
+        for (name, tracker) in &mut self.idle_trackers {
+            if tracker.warned {
+                tracker.idle_since = Instant::now();
+                tracker.warned = false;
+                snoozed.push(name.clone());
+            }
+        }
+
+        if snoozed.is_empty() {
+            self.set_toast(ToastState::Info, "No pending idle auto-stop", 2);
+            return;
+        }
+
+        snoozed.sort();
+        for name in &snoozed {
+            if let Some(service) = self.services.iter().find(|service| &service.name == name) {
+                append_event_log(&service.events, name, "", "idle-snoozed");
+            }
+        }
+        self.set_toast(
+            ToastState::Info,
+            format!("Snoozed idle auto-stop for {}", snoozed.join(", ")),
+            3,
+        );
+    }
+
+    /// Stops or pauses an idle project; returns whether the job was started.
+    fn auto_stop_idle_project(&mut self, index: usize, action: IdleAction, minutes: u64) -> bool {
+        let name = self.services[index].name.clone();
+        let (acted, event) = match action {
+            IdleAction::Down => (self.stop_service_at(index), "auto-stop"),
+            IdleAction::Pause => (self.pause_service_at(index), "auto-pause"),
+        };
+        if !acted {
+            return false;
+        }
+
+        append_event_log(&self.services[index].events, &name, "", event);
+        self.set_toast(
+            ToastState::Warning,
+            format!("{}: {} after {} min idle", name, event, minutes),
+            5,
+        );
+        true
+    }
+}
@@ -0,0 +1,180 @@
+use crate::app::state::App;
+use crate::config::TimeoutSettings;
+use crate::docker::images::{self, ImageInfo};
+use crate::docker::process::run_capture;
+use crate::notify::AppMessage;
+use crate::status::{Status, ToastState};
+
+impl App {
+    /// Relists local images in the background.
+    pub fn refresh_images(&mut self) {
+        if !self.docker_daemon_running {
+            self.images_error = Some("Docker daemon not responding".to_string());
+            return;
+        }
+        self.images_loading = true;
+        let projects: Vec<String> = self
+            .services
+            .iter()
+            .map(|service| service.name.clone())
+            .collect();
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            notifier.send(AppMessage::Images(images::list(&projects).await));
+        });
+    }
+
+    pub fn apply_images(&mut self, result: Result<Vec<ImageInfo>, String>) {
+        self.images_loading = false;
+        match result {
+            Ok(images) => {
+                self.images = images;
+                self.images_error = None;
+                self.images_selected = self
+                    .images_selected
+                    .min(self.images.len().saturating_sub(1));
+            }
+            Err(reason) => self.images_error = Some(reason),
+        }
+    }
+
+    pub fn images_select_next(&mut self) {
+        if self.images_selected + 1 < self.images.len() {
+            self.images_selected += 1;
+        }
+    }
+
+    pub fn images_select_previous(&mut self) {
+        self.images_selected = self.images_selected.saturating_sub(1);
+    }
+
+    pub fn pull_selected_image(&mut self) {
+        let Some(image) = self.images.get(self.images_selected) else {
+            return;
+        };
+        if image.is_dangling() {
+            self.set_toast(
+                ToastState::Warning,
+                "Dangling images have no tag to pull",
+                3,
+            );
+            return;
+        }
+        let reference = image.name().to_string();
+        let cmd = images::pull_cmd(&reference);
+        self.run_image_action(format!("Pulling {}", reference), async move {
+            run_image_cmd(cmd).await?;
+            Ok(format!("Pulled {}", reference))
+        });
+    }
+
+    /// Removes the highlighted image unless a project that uses it is up.
+    pub fn remove_selected_image(&mut self) {
+        let Some(image) = self.images.get(self.images_selected).cloned() else {
+            return;
+        };
+        let active: Vec<&str> = image
+            .projects
+            .iter()
+            .filter(|project| {
+                self.service_index(project).is_some_and(|i| {
+                    !matches!(
+                        self.services[i].status,
+                        Status::Stopped | Status::Error | Status::DaemonNotRunning
+                    )
+                })
+            })
+            .map(String::as_str)
+            .collect();
+        if !active.is_empty() {
+            self.set_toast(
+                ToastState::Warning,
+                format!(
+                    "{} is used by {}; stop it first",
+                    image.name(),
+                    active.join(", ")
+                ),
+                4,
+            );
+            return;
+        }
+
+        let name = image.name().to_string();
+        let cmd = images::remove_cmd(&image);
+        self.run_image_action(format!("Removing {}", name), async move {
+            run_image_cmd(cmd).await?;
+            Ok(format!("Removed {}", name))
+        });
+    }
+
+    pub fn prune_dangling_images(&mut self) {
+        let cmd = images::prune_dangling_cmd();
+        self.run_image_action("Pruning dangling images".to_string(), async move {
+            let output = run_image_cmd(cmd).await?;
+            // Docker ends with "Total reclaimed space: <size>".
+            let reclaimed = output
+                .lines()
+                .rev()
+                .find_map(|line| line.strip_prefix("Total reclaimed space:"))
+                .map(str::trim)
+                .unwrap_or("0B");
+            Ok(format!("Pruned dangling images, reclaimed {}", reclaimed))
+        });
+    }
+
+    fn run_image_action(
+        &mut self,
+        progress: String,
+        action: impl Future<Output = Result<String, String>> + Send + 'static,
+    ) {
+        if self.image_action_pending {
+            self.set_toast(ToastState::Warning, "An image action is already running", 3);
+            return;
+        }
+        if !self.docker_daemon_running {
+            self.set_toast(ToastState::Error, "Docker daemon not responding", 5);
+            return;
+        }
+        self.image_action_pending = true;
+        self.set_toast(ToastState::Info, format!("{}...", progress), 30);
+
+        let notifier = self.notifier.clone();
+        let limit = TimeoutSettings::limit(self.settings.timeouts.pull_secs);
+        tokio::spawn(async move {
+            let result = match limit {
+                Some(limit) => tokio::time::timeout(limit, action)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(format!("{} timed out after {}s", progress, limit.as_secs()))
+                    }),
+                None => action.await,
+            };
+            notifier.send(AppMessage::ImageAction(result));
+        });
+    }
+
+    pub fn complete_image_action(&mut self, result: Result<String, String>) {
+        self.image_action_pending = false;
+        match result {
+            Ok(message) => self.set_toast(ToastState::Success, message, 4),
+            Err(reason) => self.set_toast(ToastState::Error, reason, 5),
+        }
+        self.refresh_images();
+    }
+}
+
+/// Runs an image command, returning its output or the last line of its
+/// error output.
+async fn run_image_cmd(cmd: tokio::process::Command) -> Result<String, String> {
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if out.status.success() {
+        return Ok(String::from_utf8_lossy(&out.stdout).into_owned());
+    }
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    Err(stderr
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("command failed")
+        .to_string())
+}
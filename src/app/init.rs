@@ -1,75 +1,58 @@
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::time::{Duration, Instant};
 
 use crate::app::state::{App, DaemonAction, Focus, LogTab};
-use crate::config::Keybinds;
+use crate::config::{Keybinds, Settings};
 use crate::docker::client::DockerClient;
+use crate::docker::stats::ProjectStats;
+use crate::log_buffer::LogBuffer;
+use crate::notify::{AppMessage, Notifier};
 use crate::service::Service;
 use crate::status::{Status, ToastState};
+use crate::ui::log_cache::StyledLogCache;
 
 impl App {
-    pub fn new(keybinds: Keybinds) -> Self {
+    /// Builds the app without touching Docker; daemon and tool checks run in
+    /// the background and report back as messages.
+    pub fn new(keybinds: Keybinds, settings: Settings, notifier: Notifier) -> Self {
         let service_names = get_service_names();
 
-        let docker_running = DockerClient::docker_info_ok();
-        let docker_command_available = DockerClient::docker_cli_ok();
-        let docker_compose_available = DockerClient::compose_cli_ok();
-
-        let (toast, toast_timer) = if !docker_compose_available {
-            (
-                Some(crate::toast::Toast {
-                    state: ToastState::Error,
-                    message: "Docker Compose not found. Services may not work.".to_string(),
-                }),
-                5,
-            )
-        } else if !docker_command_available {
-            (
-                Some(crate::toast::Toast {
-                    state: ToastState::Error,
-                    message: "Docker CLI not found.".to_string(),
-                }),
-                5,
-            )
-        } else if !docker_running {
-            (
-                Some(crate::toast::Toast {
-                    state: ToastState::Warning,
-                    message: "Docker daemon not running.".to_string(),
-                }),
-                4,
-            )
-        } else {
-            (
-                Some(crate::toast::Toast {
-                    state: ToastState::Info,
-                    message: "Welcome to Docker Manager".to_string(),
-                }),
-                3,
-            )
-        };
-
+        let log_limits = settings.log_buffers.limits();
         let mut app = Self {
             state: ratatui::widgets::ListState::default(),
             services: service_names
                 .into_iter()
                 .map(|name| Service {
                     name,
-                    status: Arc::new(Mutex::new(Status::Stopped)),
-                    pull_progress: Arc::new(Mutex::new(None)),
-                    events: Arc::new(Mutex::new(String::new())),
-                    logs: Arc::new(Mutex::new(String::new())),
-                    live_logs: Arc::new(Mutex::new(String::new())),
-                    logs_child: Arc::new(Mutex::new(None)),
+                    status: Status::Stopped,
+                    pull_progress: None,
+                    pull_layers: None,
+                    events: LogBuffer::shared(log_limits, notifier.clone()),
+                    logs: LogBuffer::shared(log_limits, notifier.clone()),
+                    live_logs: LogBuffer::shared(log_limits, notifier.clone()),
+                    live_logs_task: None,
+                    web_watchers: Arc::new(AtomicUsize::new(0)),
+                    stats: ProjectStats::default(),
+                    crashes: Vec::new(),
+                    crash_reports: Vec::new(),
+                    kills: HashMap::new(),
+                    marked: false,
+                    port_remaps: Vec::new(),
                 })
                 .collect(),
-            toast,
-            toast_timer,
+            toast: Some(crate::toast::Toast {
+                state: ToastState::Info,
+                message: "Welcome to Docker Manager".to_string(),
+            }),
+            toast_expires_at: Some(Instant::now() + Duration::from_secs(3)),
 
             search_mode: false,
             search_query: String::new(),
-            docker_daemon_running: docker_running,
-            docker_command_available,
-            docker_compose_available,
+            docker_daemon_running: false,
+            docker_command_available: true,
+            docker_compose_available: true,
             daemon_menu_mode: false,
             daemon_action_selected: DaemonAction::Start,
             daemon_start_mode: false,
@@ -78,20 +61,80 @@ impl App {
             first_status_check: true,
             log_scroll: 0,
             log_auto_scroll: true,
+            log_cache: StyledLogCache::default(),
             log_tab: LogTab::Events,
+            pull_details_expanded: false,
             animation_tick: 0,
-            status_refresh_cooldown_ticks: 0,
-            daemon_probe_cooldown_ticks: 0,
+            started_at: Instant::now(),
+            next_status_refresh: Instant::now(),
+            last_daemon_probe: None,
+            status_refresh_pending: false,
+            status_refresh_requested: false,
+            daemon_action_pending: false,
             event_listener_running: false,
             event_listener_handle: None,
-            toast_tick_accumulator: 0,
+            stats_listener_handle: None,
             keybinds,
+            notifier,
+            settings,
+            web_server: None,
+            web_state: None,
+            idle_trackers: HashMap::new(),
+            last_idle_check: Instant::now(),
+            watchdogs: HashMap::new(),
+            jobs: VecDeque::new(),
+            next_job_id: 1,
+            jobs_mode: false,
+            jobs_selected: 0,
+            timeout_prompt: None,
+            bundles_mode: false,
+            bundles: Vec::new(),
+            bundles_selected: 0,
+            data_view: None,
+            seeds_view: None,
+            disk_view: None,
+            ports_view: None,
+            images: Vec::new(),
+            images_selected: 0,
+            images_loading: false,
+            images_error: None,
+            networks: Vec::new(),
+            networks_loading: false,
+            networks_error: None,
+            image_action_pending: false,
         };
+        app.check_tools();
+        app.load_port_remaps();
         app.refresh_statuses();
-        app.populate_initial_logs();
-        app.start_event_listeners();
+        app.start_web_dashboard();
         app
     }
+
+    fn check_tools(&self) {
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            let docker_cli = DockerClient::docker_cli_ok().await;
+            let docker_compose = DockerClient::compose_cli_ok().await;
+            notifier.send(AppMessage::ToolsChecked {
+                docker_cli,
+                docker_compose,
+            });
+        });
+    }
+
+    pub fn apply_tools_checked(&mut self, docker_cli: bool, docker_compose: bool) {
+        self.docker_command_available = docker_cli;
+        self.docker_compose_available = docker_compose;
+        if !docker_compose {
+            self.set_toast(
+                ToastState::Error,
+                "Docker Compose not found. Services may not work.",
+                5,
+            );
+        } else if !docker_cli {
+            self.set_toast(ToastState::Error, "Docker CLI not found.", 5);
+        }
+    }
 }
 
 fn get_service_names() -> Vec<String> {
@@ -0,0 +1,431 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinHandle;
+
+use crate::app::state::App;
+use crate::docker::pull::PullProgress;
+use crate::log_buffer::SharedLogBuffer;
+use crate::notify::{AppMessage, Notifier};
+use crate::status::{Status, ToastState};
+
+pub type JobId = u64;
+
+/// Finished jobs kept around for the jobs overlay.
+const MAX_FINISHED_JOBS: usize = 50;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum JobKind {
+    Start,
+    Stop,
+    Pause,
+    Resume,
+    Restart,
+    Kill,
+    PullUpdates,
+    Rebuild,
+    UpdateLock,
+    ExportBundle,
+    ImportBundle,
+    Snapshot,
+    Restore,
+    Dump,
+    Seed,
+    Wipe,
+}
+
+impl JobKind {
+    /// Whether the job sets the project status itself as it goes. Jobs that
+    /// only read or write data leave it to the periodic refresh.
+    pub fn reports_status(self) -> bool {
+        matches!(
+            self,
+            JobKind::Start
+                | JobKind::Stop
+                | JobKind::Pause
+                | JobKind::Resume
+                | JobKind::Restart
+                | JobKind::Kill
+                | JobKind::Rebuild
+        )
+    }
+}
+
+impl fmt::Display for JobKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobKind::Start => write!(f, "start"),
+            JobKind::Stop => write!(f, "stop"),
+            JobKind::Pause => write!(f, "pause"),
+            JobKind::Resume => write!(f, "resume"),
+            JobKind::Restart => write!(f, "restart"),
+            JobKind::Kill => write!(f, "kill"),
+            JobKind::PullUpdates => write!(f, "pull updates"),
+            JobKind::Rebuild => write!(f, "rebuild"),
+            JobKind::UpdateLock => write!(f, "update lock"),
+            JobKind::ExportBundle => write!(f, "export bundle"),
+            JobKind::ImportBundle => write!(f, "import bundle"),
+            JobKind::Snapshot => write!(f, "snapshot"),
+            JobKind::Restore => write!(f, "restore"),
+            JobKind::Dump => write!(f, "dump"),
+            JobKind::Seed => write!(f, "seed"),
+            JobKind::Wipe => write!(f, "wipe"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum JobPhase {
+    Pull,
+    Build,
+    Up,
+    Down,
+    Pause,
+    Unpause,
+    Kill,
+    Save,
+    Load,
+    Wipe,
+    Seed,
+}
+
+impl JobPhase {
+    /// Whether the phase keeps printing while it works, so going quiet means
+    /// it stalled. Saves, loads, wipes and the like print nothing until done
+    /// and only answer to their phase limit.
+    pub fn reports_output(self) -> bool {
+        matches!(self, JobPhase::Pull | JobPhase::Build)
+    }
+}
+
+impl fmt::Display for JobPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobPhase::Pull => write!(f, "pull"),
+            JobPhase::Build => write!(f, "build"),
+            JobPhase::Up => write!(f, "up"),
+            JobPhase::Down => write!(f, "down"),
+            JobPhase::Pause => write!(f, "pause"),
+            JobPhase::Unpause => write!(f, "unpause"),
+            JobPhase::Kill => write!(f, "kill"),
+            JobPhase::Save => write!(f, "save"),
+            JobPhase::Load => write!(f, "load"),
+            JobPhase::Wipe => write!(f, "wipe"),
+            JobPhase::Seed => write!(f, "seed"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum JobOutcome {
+    Running,
+    Succeeded,
+    Failed(String),
+    Cancelled,
+}
+
+impl fmt::Display for JobOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobOutcome::Running => write!(f, "running"),
+            JobOutcome::Succeeded => write!(f, "done"),
+            JobOutcome::Failed(reason) => write!(f, "failed: {}", reason),
+            JobOutcome::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum JobTimeout {
+    /// The current phase ran past its configured limit.
+    Deadline(Duration),
+    /// Nothing was written to the project log for this long.
+    Stalled(Duration),
+}
+
+impl fmt::Display for JobTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobTimeout::Deadline(limit) => write!(f, "ran past its {}s limit", limit.as_secs()),
+            JobTimeout::Stalled(limit) => write!(f, "no output for {}s", limit.as_secs()),
+        }
+    }
+}
+
+pub enum JobUpdate {
+    Phase(JobPhase),
+    Progress(Option<String>),
+    Pull(PullProgress),
+    Status(Status),
+    Toast(ToastState, String),
+    Finished(Result<(), String>),
+}
+
+/// One pull, build, start, stop, pause or restart of a project.
+pub struct Job {
+    pub id: JobId,
+    pub service: String,
+    pub kind: JobKind,
+    pub phase: JobPhase,
+    pub progress: Option<String>,
+    pub started_at: Instant,
+    pub finished_at: Option<Instant>,
+    pub outcome: JobOutcome,
+    /// Set once the job exceeds a limit, until the user decides what to do.
+    pub timeout: Option<JobTimeout>,
+    pub phase_started_at: Instant,
+    /// Last time the job printed a log line or reported progress.
+    pub last_output_at: Instant,
+    /// Log position last seen, used to tell whether the job is still printing.
+    pub last_output_index: u64,
+    pub logs: SharedLogBuffer,
+    /// Status the project goes back to if the job is cancelled.
+    rollback_status: Status,
+    task: Option<JoinHandle<()>>,
+}
+
+impl Job {
+    pub fn is_active(&self) -> bool {
+        self.outcome == JobOutcome::Running
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.finished_at
+            .unwrap_or_else(Instant::now)
+            .duration_since(self.started_at)
+    }
+}
+
+/// Handle a job's task uses to report back. Updates are routed by job id, so
+/// anything a cancelled job still had in flight is dropped.
+#[derive(Clone)]
+pub struct JobReporter {
+    id: JobId,
+    notifier: Notifier,
+}
+
+impl JobReporter {
+    pub fn phase(&self, phase: JobPhase) {
+        self.send(JobUpdate::Phase(phase));
+    }
+
+    pub fn progress(&self, progress: Option<String>) {
+        self.send(JobUpdate::Progress(progress));
+    }
+
+    pub fn pull(&self, progress: PullProgress) {
+        self.send(JobUpdate::Pull(progress));
+    }
+
+    pub fn status(&self, status: Status) {
+        self.send(JobUpdate::Status(status));
+    }
+
+    pub fn toast(&self, state: ToastState, message: String) {
+        self.send(JobUpdate::Toast(state, message));
+    }
+
+    fn send(&self, update: JobUpdate) {
+        self.notifier.send(AppMessage::Job {
+            id: self.id,
+            update,
+        });
+    }
+}
+
+impl App {
+    /// Moves the project at `index` to `status` and runs `task` as a job. The
+    /// task's result becomes the job outcome.
+    pub fn spawn_job<F, Fut>(
+        &mut self,
+        index: usize,
+        kind: JobKind,
+        phase: JobPhase,
+        status: Status,
+        task: F,
+    ) -> JobId
+    where
+        F: FnOnce(JobReporter) -> Fut,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let id = self.next_job_id;
+        self.next_job_id += 1;
+
+        let service = &mut self.services[index];
+        let rollback_status = std::mem::replace(&mut service.status, status);
+
+        let reporter = JobReporter {
+            id,
+            notifier: self.notifier.clone(),
+        };
+        let future = task(reporter.clone());
+        let handle = tokio::spawn(async move {
+            let result = future.await;
+            reporter.send(JobUpdate::Finished(result));
+        });
+
+        let now = Instant::now();
+        let last_output_index = service.logs.lock().unwrap().end_index();
+        self.jobs.push_back(Job {
+            id,
+            service: service.name.clone(),
+            kind,
+            phase,
+            progress: None,
+            started_at: now,
+            finished_at: None,
+            outcome: JobOutcome::Running,
+            timeout: None,
+            phase_started_at: now,
+            last_output_at: now,
+            last_output_index,
+            logs: Arc::clone(&service.logs),
+            rollback_status,
+            task: Some(handle),
+        });
+        id
+    }
+
+    pub fn active_job(&self, service: &str) -> Option<&Job> {
+        self.jobs
+            .iter()
+            .rev()
+            .find(|job| job.service == service && job.is_active())
+    }
+
+    pub fn apply_job_update(&mut self, id: JobId, update: JobUpdate) {
+        let Some(job) = self
+            .jobs
+            .iter_mut()
+            .find(|job| job.id == id && job.is_active())
+        else {
+            return;
+        };
+        let Some(index) = self
+            .services
+            .iter()
+            .position(|service| service.name == job.service)
+        else {
+            return;
+        };
+        let service = &mut self.services[index];
+
+        match update {
+            JobUpdate::Phase(phase) => {
+                job.phase = phase;
+                job.phase_started_at = Instant::now();
+                job.timeout = None;
+            }
+            JobUpdate::Progress(progress) => {
+                job.last_output_at = Instant::now();
+                job.progress.clone_from(&progress);
+                service.pull_progress = progress;
+            }
+            JobUpdate::Pull(progress) => {
+                // Byte ticks of a long layer download log nothing but still
+                // show the pull is alive.
+                job.last_output_at = Instant::now();
+                let summary = progress.summary();
+                job.progress = Some(summary.clone());
+                service.pull_progress = Some(summary);
+                service.pull_layers = Some(progress);
+            }
+            JobUpdate::Status(status) => service.status = status,
+            JobUpdate::Toast(state, message) => self.set_toast(state, message, 4),
+            JobUpdate::Finished(result) => {
+                job.outcome = match result {
+                    Ok(()) => JobOutcome::Succeeded,
+                    Err(reason) => JobOutcome::Failed(reason),
+                };
+                job.finished_at = Some(Instant::now());
+                job.task = None;
+                let service = job.service.clone();
+                self.prune_jobs();
+                self.refresh_data_view(&service);
+                self.refresh_seeds_view(&service);
+            }
+        }
+    }
+
+    /// Cancels the running job for the selected project.
+    pub fn cancel_selected_job(&mut self) {
+        let Some(index) = self.state.selected() else {
+            return;
+        };
+        let name = self.services[index].name.clone();
+        match self.active_job(&name).map(|job| job.id) {
+            Some(id) => self.cancel_job(id),
+            None => self.set_toast(ToastState::Info, format!("No running job for {}", name), 2),
+        }
+    }
+
+    /// Aborts the job's task, which kills its child process, and puts the
+    /// project back in the status it had before the job started.
+    pub fn cancel_job(&mut self, id: JobId) {
+        let Some(job) = self
+            .jobs
+            .iter_mut()
+            .find(|job| job.id == id && job.is_active())
+        else {
+            return;
+        };
+
+        if let Some(task) = job.task.take() {
+            task.abort();
+        }
+        job.outcome = JobOutcome::Cancelled;
+        job.finished_at = Some(Instant::now());
+
+        let message = format!(
+            "Cancelled {} of {} during {}",
+            job.kind, job.service, job.phase
+        );
+        let service_name = job.service.clone();
+        let rollback_status = job.rollback_status.clone();
+        if let Some(index) = self.service_index(&service_name) {
+            let service = &mut self.services[index];
+            service.status = rollback_status;
+            service.pull_progress = None;
+            service
+                .logs
+                .lock()
+                .unwrap()
+                .push_str(&format!("[job] {}\n", message));
+        }
+
+        self.prune_jobs();
+        self.set_toast(ToastState::Warning, message, 3);
+    }
+
+    fn prune_jobs(&mut self) {
+        let mut finished = self.jobs.iter().filter(|job| !job.is_active()).count();
+        self.jobs.retain(|job| {
+            if finished > MAX_FINISHED_JOBS && !job.is_active() {
+                finished -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Jobs for the overlay, newest first.
+    pub fn jobs_newest_first(&self) -> impl Iterator<Item = &Job> {
+        self.jobs.iter().rev()
+    }
+
+    pub fn cancel_overlay_selected_job(&mut self) {
+        let id = self
+            .jobs_newest_first()
+            .nth(self.jobs_selected)
+            .map(|job| job.id);
+        match id {
+            Some(id) if self.jobs.iter().any(|job| job.id == id && job.is_active()) => {
+                self.cancel_job(id)
+            }
+            Some(_) => self.set_toast(ToastState::Info, "Job already finished", 2),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::jobs::{JobKind, JobPhase, JobReporter};
+use crate::app::pulls::run_pull;
+use crate::app::state::App;
+use crate::docker::client::DockerClient;
+use crate::docker::compose_file::ComposeFile;
+use crate::log_buffer::SharedLogBuffer;
+use crate::status::ToastState;
+
+/// What the start flow does with a project's lock file.
+#[derive(Clone, Copy)]
+pub struct LockOptions {
+    /// Write a lock file after the pull if the project has none yet.
+    pub write: bool,
+    /// Warn when local images differ from the lock file.
+    pub verify: bool,
+}
+
+/// Resolved digests of every pulled image, by compose service. Meant to be
+/// committed with the project so everyone runs the same images.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ImageLock {
+    pub services: BTreeMap<String, LockedImage>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LockedImage {
+    pub image: String,
+    pub digest: String,
+    /// Image id, which is all an image loaded from a bundle can be matched by.
+    #[serde(default)]
+    pub id: String,
+}
+
+impl ImageLock {
+    pub fn path(project: &str) -> String {
+        format!("containers/{}/images.lock.json", project)
+    }
+
+    pub async fn load(project: &str) -> Option<Self> {
+        let content = tokio::fs::read_to_string(Self::path(project)).await.ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    async fn save(&self, project: &str) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        tokio::fs::write(Self::path(project), content + "\n")
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Lock for the images currently present locally. Services whose image is
+    /// missing are left out.
+    async fn resolve(compose: &ComposeFile) -> Self {
+        let mut services = BTreeMap::new();
+        for (name, image) in compose.pulled_images() {
+            if let Some(digest) = DockerClient::image_digest(image).await {
+                services.insert(
+                    name.clone(),
+                    LockedImage {
+                        image: image.clone(),
+                        digest,
+                        id: DockerClient::image_id(image).await.unwrap_or_default(),
+                    },
+                );
+            }
+        }
+        Self { services }
+    }
+}
+
+impl App {
+    pub fn lock_options(&self) -> LockOptions {
+        LockOptions {
+            write: self.settings.pull.lock,
+            verify: self.settings.pull.verify_lock,
+        }
+    }
+
+    pub fn update_lock(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.update_lock_at(i);
+        }
+    }
+
+    /// Rewrites the project's lock file from the local images, pulling any
+    /// that are missing first.
+    pub fn update_lock_at(&mut self, i: usize) {
+        if !self.docker_daemon_running {
+            self.set_toast(
+                ToastState::Error,
+                "Cannot update lock: Docker daemon not responding",
+                5,
+            );
+            return;
+        }
+
+        let service_name = self.services[i].name.clone();
+        if self.active_job(&service_name).is_some() {
+            self.set_toast(
+                ToastState::Warning,
+                format!(
+                    "{} is busy, press {} to cancel",
+                    service_name, self.keybinds.services.cancel_job
+                ),
+                3,
+            );
+            return;
+        }
+
+        let logs = Arc::clone(&self.services[i].logs);
+        let status = self.services[i].status.clone();
+        let name = service_name.clone();
+        self.spawn_job(
+            i,
+            JobKind::UpdateLock,
+            JobPhase::Pull,
+            status,
+            |job| async move {
+                let result = update_project_lock(&name, &logs, &job).await;
+                job.progress(None);
+                result
+            },
+        );
+
+        self.set_toast(
+            ToastState::Info,
+            format!("Updating lock for {}", service_name),
+            3,
+        );
+    }
+}
+
+async fn update_project_lock(
+    project: &str,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    logs.lock().unwrap().clear();
+    let compose = ComposeFile::load(project)
+        .await
+        .ok_or_else(|| format!("cannot read {}", ComposeFile::path(project)))?;
+
+    let mut missing = Vec::new();
+    for (name, image) in compose.pulled_images() {
+        if !DockerClient::image_exists(image).await {
+            missing.push(name.clone());
+        }
+    }
+    if !missing.is_empty() {
+        run_pull(project, &missing, logs, job).await?;
+    }
+
+    let lock = ImageLock::resolve(&compose).await;
+    lock.save(project).await?;
+
+    let mut logs_lock = logs.lock().unwrap();
+    logs_lock.push_str(&format!("Wrote {}:\n", ImageLock::path(project)));
+    for (name, locked) in &lock.services {
+        logs_lock.push_str(&format!("  {} {} {}\n", name, locked.image, locked.digest));
+    }
+    drop(logs_lock);
+    job.toast(
+        ToastState::Success,
+        format!("{}: locked {} images", project, lock.services.len()),
+    );
+    Ok(())
+}
+
+/// Runs between pull and `up`: writes the lock file for a project that has
+/// none, or compares the local images with it and warns about differences.
+/// Never fails the start; a mismatch is only reported.
+pub async fn check_lock(
+    project: &str,
+    options: LockOptions,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) {
+    let Some(compose) = ComposeFile::load(project).await else {
+        return;
+    };
+
+    let Some(lock) = ImageLock::load(project).await else {
+        if options.write {
+            let lock = ImageLock::resolve(&compose).await;
+            if !lock.services.is_empty() && lock.save(project).await.is_ok() {
+                logs.lock().unwrap().push_str(&format!(
+                    "[lock] wrote {} with {} images\n",
+                    ImageLock::path(project),
+                    lock.services.len()
+                ));
+            }
+        }
+        return;
+    };
+    if !options.verify {
+        return;
+    }
+
+    let mut mismatches = Vec::new();
+    for (name, image) in compose.pulled_images() {
+        let problem = match lock.services.get(name) {
+            None => Some("not in lock".to_string()),
+            Some(locked) if locked.image != *image => {
+                Some(format!("image changed from {}", locked.image))
+            }
+            Some(locked) => match DockerClient::image_digest(image).await {
+                Some(digest) if digest == locked.digest || digest == locked.id => None,
+                Some(digest) => Some(format!("local {} != locked {}", digest, locked.digest)),
+                None => Some("image missing".to_string()),
+            },
+        };
+        if let Some(problem) = problem {
+            mismatches.push(format!("{} ({}): {}", name, image, problem));
+        }
+    }
+
+    if mismatches.is_empty() {
+        logs.lock()
+            .unwrap()
+            .push_str("[lock] local images match the lock file\n");
+        return;
+    }
+    let mut logs_lock = logs.lock().unwrap();
+    for mismatch in &mismatches {
+        logs_lock.push_str(&format!("[lock] mismatch: {}\n", mismatch));
+    }
+    drop(logs_lock);
+    job.toast(
+        ToastState::Warning,
+        format!(
+            "{}: {} images differ from the lock file",
+            project,
+            mismatches.len()
+        ),
+    );
+}
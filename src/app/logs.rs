@@ -1,7 +1,7 @@
-use std::fs;
-use std::io::{BufRead, BufReader};
 use std::sync::Arc;
-use std::thread;
+use std::sync::atomic::Ordering;
+
+use tokio::io::{AsyncBufReadExt, BufReader};
 
 use crate::app::state::App;
 use crate::docker::compose::ComposeProject;
@@ -20,23 +20,24 @@ impl App {
         for service in &self.services {
             let service_name = service.name.clone();
             let logs = Arc::clone(&service.logs);
-            thread::spawn(move || {
+            tokio::spawn(async move {
                 let project = ComposeProject::new(service_name.clone());
-                if let Ok(output) = project.ps_output() {
+                if let Ok(output) = project.ps_output().await {
                     let stdout = String::from_utf8_lossy(&output.stdout);
                     if stdout.contains("Up") {
                         let compose_path =
                             format!("containers/{}/docker-compose.yml", service_name);
                         let mut text = String::new();
-                        if let Ok(content) = fs::read_to_string(&compose_path)
-                            && let Ok(compose) = serde_yaml::from_str::<Compose>(&content) {
-                                let services = compose.services.keys().cloned().collect::<Vec<_>>();
-                                let network = format!("{}_default", service_name);
-                                text = format!("Up output:\nNetwork {} Running\n", network);
-                                for svc in services {
-                                    text.push_str(&format!("Container {} Running\n", svc));
-                                }
+                        if let Ok(content) = tokio::fs::read_to_string(&compose_path).await
+                            && let Ok(compose) = serde_yaml::from_str::<Compose>(&content)
+                        {
+                            let services = compose.services.keys().cloned().collect::<Vec<_>>();
+                            let network = format!("{}_default", service_name);
+                            text = format!("Up output:\nNetwork {} Running\n", network);
+                            for svc in services {
+                                text.push_str(&format!("Container {} Running\n", svc));
                             }
+                        }
                         let mut logs_lock = logs.lock().unwrap();
                         if logs_lock.is_empty() {
                             logs_lock.push_str(&text);
@@ -53,70 +54,67 @@ impl App {
             return;
         }
 
-        let selected_index = self.state.selected();
-        let target_index = selected_index.filter(|&index| {
-            self.log_tab == crate::app::LogTab::LiveLogs
-                && *self.services[index].status.lock().unwrap() == Status::Running
-        });
+        let selected_index = self
+            .state
+            .selected()
+            .filter(|_| self.log_tab == crate::app::LogTab::LiveLogs);
 
         for index in 0..self.services.len() {
-            if Some(index) != target_index {
+            let service = &self.services[index];
+            let watched =
+                Some(index) == selected_index || service.web_watchers.load(Ordering::Relaxed) > 0;
+            if watched && service.status == Status::Running {
+                self.ensure_live_logs_for_service(index);
+            } else {
                 self.stop_live_logs_for_service(index);
             }
         }
-
-        if let Some(index) = target_index {
-            self.ensure_live_logs_for_service(index);
-        }
     }
 
-    fn ensure_live_logs_for_service(&self, index: usize) {
-        let service = &self.services[index];
-        if service.logs_child.lock().unwrap().is_some() {
+    fn ensure_live_logs_for_service(&mut self, index: usize) {
+        let service = &mut self.services[index];
+        if service
+            .live_logs_task
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+        {
             return;
         }
 
         let project = ComposeProject::new(service.name.clone());
         let live_logs = Arc::clone(&service.live_logs);
-        let logs_child = Arc::clone(&service.logs_child);
 
         if let Ok(mut child) = project.logs_follow()
             && let Some(stdout) = child.stdout.take()
         {
-            *logs_child.lock().unwrap() = Some(child);
-            thread::spawn(move || {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines().map_while(Result::ok) {
-                    let mut logs = live_logs.lock().unwrap();
-                    logs.push_str(&line);
-                    logs.push('\n');
-                }
-
-                if let Some(mut child) = logs_child.lock().unwrap().take() {
-                    let _ = child.wait();
+            service.live_logs_task = Some(tokio::spawn(async move {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    live_logs.lock().unwrap().push_line(line);
                 }
-            });
+                let _ = child.wait().await;
+            }));
         }
     }
 
-    fn stop_live_logs_for_service(&self, index: usize) {
-        let service = &self.services[index];
-        if let Some(mut child) = service.logs_child.lock().unwrap().take() {
-            let _ = child.kill();
-            let _ = child.wait();
+    fn stop_live_logs_for_service(&mut self, index: usize) {
+        let service = &mut self.services[index];
+        // Aborting drops the `docker compose logs -f` child, which kills it.
+        if let Some(task) = service.live_logs_task.take() {
+            task.abort();
         }
-        if *service.status.lock().unwrap() != Status::Running {
+        if service.status != Status::Running {
             service.live_logs.lock().unwrap().clear();
         }
     }
 
-    fn stop_live_logs_for_all_services(&self) {
+    fn stop_live_logs_for_all_services(&mut self) {
         for index in 0..self.services.len() {
             self.stop_live_logs_for_service(index);
         }
     }
 
-    pub fn kill_all_live_logs(&self) {
+    pub fn kill_all_live_logs(&mut self) {
         self.stop_live_logs_for_all_services();
     }
 }
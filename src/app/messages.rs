@@ -0,0 +1,60 @@
+use crate::app::state::{App, LogTab};
+use crate::notify::AppMessage;
+
+impl App {
+    /// Applies one update from a background task. This is the only place
+    /// project state changes in response to Docker.
+    pub fn handle_message(&mut self, message: AppMessage) {
+        match message {
+            AppMessage::Changed => {}
+            AppMessage::Job { id, update } => self.apply_job_update(id, update),
+            AppMessage::StatusSnapshot(snapshot) => self.apply_status_snapshot(snapshot),
+            AppMessage::ContainerEvent(event) => {
+                self.apply_container_event(event);
+                // Attachments change as containers start and stop.
+                if self.log_tab == LogTab::Networks {
+                    self.refresh_networks();
+                }
+            }
+            AppMessage::CrashReport { service, report } => {
+                if let Some(index) = self.service_index(&service) {
+                    self.services[index].crash_reports.push(report);
+                }
+            }
+            AppMessage::Stats {
+                service,
+                container,
+                sample,
+            } => {
+                let history = self.settings.stats.history;
+                if let Some(index) = self.service_index(&service) {
+                    self.services[index]
+                        .stats
+                        .record(&container, sample, history);
+                }
+            }
+            AppMessage::DaemonAction(result) => self.complete_daemon_action(result),
+            AppMessage::ToolsChecked {
+                docker_cli,
+                docker_compose,
+            } => self.apply_tools_checked(docker_cli, docker_compose),
+            AppMessage::Web(command) => self.handle_web_command(command),
+            AppMessage::DataMounts {
+                project,
+                mounts,
+                databases,
+            } => self.apply_data_mounts(&project, mounts, databases),
+            AppMessage::Images(result) => self.apply_images(result),
+            AppMessage::ImageAction(result) => self.complete_image_action(result),
+            AppMessage::DiskUsage(result) => self.apply_disk_usage(result),
+            AppMessage::DiskPrune(result) => self.complete_prune(result),
+            AppMessage::Networks(result) => self.apply_networks(result),
+            AppMessage::Ports(result) => self.apply_ports(result),
+            AppMessage::LoadedPortRemaps(remaps) => self.apply_loaded_port_remaps(remaps),
+            AppMessage::PortRemaps { project, result } => {
+                self.complete_port_remap(&project, result)
+            }
+            AppMessage::DataSizes { project, sizes } => self.apply_data_sizes(&project, sizes),
+        }
+    }
+}
@@ -1,8 +1,25 @@
+pub mod builds;
+pub mod bundles;
 pub mod daemon;
+pub mod disk;
+pub mod data;
+pub mod dumps;
 pub mod events;
+pub mod idle;
+pub mod images;
 pub mod init;
+pub mod jobs;
+pub mod lock;
 pub mod logs;
+pub mod messages;
+pub mod networks;
+pub mod ports;
+pub mod pulls;
+pub mod seeds;
 pub mod services;
 pub mod state;
+pub mod timeouts;
+pub mod watchdog;
+pub mod web;
 
 pub use state::{App, DaemonAction, Focus, LogTab};
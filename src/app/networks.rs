@@ -0,0 +1,34 @@
+use crate::app::state::App;
+use crate::docker::networks::{self, NetworkInfo};
+use crate::notify::AppMessage;
+
+impl App {
+    /// Reinspects the networks of managed projects in the background.
+    pub fn refresh_networks(&mut self) {
+        if !self.docker_daemon_running {
+            self.networks_error = Some("Docker daemon not responding".to_string());
+            return;
+        }
+        self.networks_loading = true;
+        let projects: Vec<String> = self
+            .services
+            .iter()
+            .map(|service| service.name.clone())
+            .collect();
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            notifier.send(AppMessage::Networks(networks::list(&projects).await));
+        });
+    }
+
+    pub fn apply_networks(&mut self, result: Result<Vec<NetworkInfo>, String>) {
+        self.networks_loading = false;
+        match result {
+            Ok(networks) => {
+                self.networks = networks;
+                self.networks_error = None;
+            }
+            Err(reason) => self.networks_error = Some(reason),
+        }
+    }
+}
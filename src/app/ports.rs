@@ -0,0 +1,277 @@
+use crate::app::state::App;
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::overrides::{self, PortRemap};
+use crate::docker::ports::{self, PortEntry};
+use crate::notify::AppMessage;
+use crate::status::{Status, ToastState};
+
+/// The port map overlay.
+pub struct PortsView {
+    pub entries: Option<Vec<PortEntry>>,
+    pub error: Option<String>,
+    pub selected: usize,
+    /// New host port being typed for the selected entry.
+    pub remap_input: Option<String>,
+}
+
+impl PortsView {
+    pub fn selected_entry(&self) -> Option<&PortEntry> {
+        self.entries.as_ref()?.get(self.selected)
+    }
+}
+
+impl App {
+    pub fn open_ports(&mut self) {
+        self.ports_view = Some(PortsView {
+            entries: None,
+            error: None,
+            selected: 0,
+            remap_input: None,
+        });
+        self.refresh_ports();
+    }
+
+    pub fn close_ports(&mut self) {
+        self.ports_view = None;
+    }
+
+    pub fn refresh_ports(&mut self) {
+        if !self.docker_daemon_running {
+            if let Some(view) = self.ports_view.as_mut() {
+                view.error = Some("Docker daemon not responding".to_string());
+            }
+            return;
+        }
+        let projects: Vec<String> = self
+            .services
+            .iter()
+            .map(|service| service.name.clone())
+            .collect();
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            notifier.send(AppMessage::Ports(ports::scan(&projects).await));
+        });
+    }
+
+    pub fn apply_ports(&mut self, result: Result<Vec<PortEntry>, String>) {
+        let Some(view) = self.ports_view.as_mut() else {
+            return;
+        };
+        match result {
+            Ok(entries) => {
+                view.selected = view.selected.min(entries.len().saturating_sub(1));
+                view.entries = Some(entries);
+                view.error = None;
+            }
+            Err(reason) => view.error = Some(reason),
+        }
+    }
+
+    pub fn ports_select_next(&mut self) {
+        if let Some(view) = self.ports_view.as_mut()
+            && view.selected + 1 < view.entries.as_ref().map_or(0, Vec::len)
+        {
+            view.selected += 1;
+        }
+    }
+
+    pub fn ports_select_previous(&mut self) {
+        if let Some(view) = self.ports_view.as_mut() {
+            view.selected = view.selected.saturating_sub(1);
+        }
+    }
+
+    /// Asks for a new host port for the selected declared port, suggesting
+    /// the next free one.
+    pub fn start_port_remap(&mut self) {
+        let Some(view) = self.ports_view.as_mut() else {
+            return;
+        };
+        let Some(entry) = view.selected_entry() else {
+            return;
+        };
+        if !entry.declared {
+            self.set_toast(
+                ToastState::Info,
+                "Only ports in a compose file can be remapped",
+                3,
+            );
+            return;
+        }
+        let suggestion = view
+            .entries
+            .as_deref()
+            .and_then(|entries| ports::suggest_port(entries, entry))
+            .map(|port| port.to_string())
+            .unwrap_or_default();
+        view.remap_input = Some(suggestion);
+    }
+
+    pub fn port_remap_push(&mut self, c: char) {
+        if let Some(input) = self
+            .ports_view
+            .as_mut()
+            .and_then(|view| view.remap_input.as_mut())
+            && c.is_ascii_digit()
+            && input.len() < 5
+        {
+            input.push(c);
+        }
+    }
+
+    pub fn port_remap_pop(&mut self) {
+        if let Some(input) = self
+            .ports_view
+            .as_mut()
+            .and_then(|view| view.remap_input.as_mut())
+        {
+            input.pop();
+        }
+    }
+
+    pub fn cancel_port_remap(&mut self) {
+        if let Some(view) = self.ports_view.as_mut() {
+            view.remap_input = None;
+        }
+    }
+
+    /// Records the typed host port in the project's ports override. Mapping
+    /// a port back to its compose file value drops that remap.
+    pub fn confirm_port_remap(&mut self) {
+        let Some(view) = self.ports_view.as_mut() else {
+            return;
+        };
+        let Some(input) = view.remap_input.take() else {
+            return;
+        };
+        let Some(entry) = view.selected_entry().cloned() else {
+            return;
+        };
+        let Some(to) = input.parse::<u16>().ok().filter(|port| *port > 0) else {
+            self.set_toast(ToastState::Error, format!("Invalid port: {}", input), 3);
+            return;
+        };
+        let Some(service) = self
+            .services
+            .iter()
+            .find(|service| service.name == entry.project)
+        else {
+            return;
+        };
+
+        let mut remaps = service.port_remaps.clone();
+        let index = remaps.iter().position(|remap| {
+            remap.matches(&entry.service, &entry.container_port, &entry.protocol)
+        });
+        let current = index.map_or(entry.host_port, |index| Some(remaps[index].to));
+        if current == Some(to) {
+            self.set_toast(
+                ToastState::Info,
+                format!("{} is already on port {}", entry.service, to),
+                3,
+            );
+            return;
+        }
+        match index {
+            Some(index) if remaps[index].from == Some(to) => {
+                remaps.remove(index);
+            }
+            Some(index) => remaps[index].to = to,
+            None => remaps.push(PortRemap {
+                service: entry.service.clone(),
+                container_port: entry.container_port.clone(),
+                protocol: entry.protocol.clone(),
+                from: entry.host_port,
+                to,
+            }),
+        }
+        self.write_port_remaps(entry.project, remaps);
+    }
+
+    /// Removes the ports override of the selected entry's project.
+    pub fn revert_port_remaps(&mut self) {
+        let Some(project) = self
+            .ports_view
+            .as_ref()
+            .and_then(|view| view.selected_entry())
+            .map(|entry| entry.project.clone())
+        else {
+            return;
+        };
+        let remapped = self
+            .services
+            .iter()
+            .any(|service| service.name == project && !service.port_remaps.is_empty());
+        if !remapped {
+            self.set_toast(
+                ToastState::Info,
+                format!("{} has no remapped ports", project),
+                3,
+            );
+            return;
+        }
+        self.write_port_remaps(project, Vec::new());
+    }
+
+    /// Reads the ports override of every project in the background.
+    pub(crate) fn load_port_remaps(&self) {
+        let projects: Vec<String> = self
+            .services
+            .iter()
+            .map(|service| service.name.clone())
+            .collect();
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            let mut loaded = Vec::new();
+            for project in projects {
+                let remaps = overrides::port_remaps(&project).await;
+                loaded.push((project, remaps));
+            }
+            notifier.send(AppMessage::LoadedPortRemaps(loaded));
+        });
+    }
+
+    pub fn apply_loaded_port_remaps(&mut self, loaded: Vec<(String, Vec<PortRemap>)>) {
+        for (project, remaps) in loaded {
+            if let Some(index) = self.service_index(&project) {
+                self.services[index].port_remaps = remaps;
+            }
+        }
+    }
+
+    fn write_port_remaps(&mut self, project: String, remaps: Vec<PortRemap>) {
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            let result = match ComposeFile::load(&project).await {
+                Some(compose) => overrides::write_port_remaps(&project, &compose, &remaps)
+                    .await
+                    .map(|()| remaps),
+                None => Err(format!("Cannot read {}", ComposeFile::path(&project))),
+            };
+            notifier.send(AppMessage::PortRemaps { project, result });
+        });
+    }
+
+    pub fn complete_port_remap(&mut self, project: &str, result: Result<Vec<PortRemap>, String>) {
+        let remaps = match result {
+            Ok(remaps) => remaps,
+            Err(reason) => {
+                self.set_toast(ToastState::Error, reason, 5);
+                return;
+            }
+        };
+        let Some(index) = self.service_index(project) else {
+            return;
+        };
+        let running = self.services[index].status == Status::Running;
+        let message = match (remaps.is_empty(), running) {
+            (true, true) => format!("Port overrides of {} removed; restart it to apply", project),
+            (true, false) => format!("Port overrides of {} removed", project),
+            (false, true) => format!("Ports of {} remapped; restart it to apply", project),
+            (false, false) => format!("Ports of {} remapped", project),
+        };
+        self.services[index].port_remaps = remaps;
+        self.set_toast(ToastState::Success, message, 5);
+        self.refresh_ports();
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::jobs::{JobKind, JobPhase, JobReporter};
+use crate::app::state::App;
+use crate::config::PullPolicy;
+use crate::docker::client::DockerClient;
+use crate::docker::compose::{ComposeProject, supports_json_progress};
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::process::{LineFilter, run_stream_with_line_filter};
+use crate::docker::pull::{PullLine, PullProgress};
+use crate::log_buffer::SharedLogBuffer;
+use crate::status::ToastState;
+
+/// How often byte-count ticks are forwarded to the app during a pull.
+const PULL_UPDATE_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Pull policies for one project, captured from the settings before a job
+/// starts.
+#[derive(Clone)]
+pub struct PullPolicies {
+    project: PullPolicy,
+    services: HashMap<String, PullPolicy>,
+}
+
+impl PullPolicies {
+    /// Settings for the service win over the compose file's `pull_policy`,
+    /// which wins over the project and global defaults.
+    fn resolve(&self, service: &str, compose_policy: Option<&str>) -> PullPolicy {
+        self.services
+            .get(service)
+            .copied()
+            .or_else(|| compose_policy.and_then(PullPolicy::from_compose))
+            .unwrap_or(self.project)
+    }
+}
+
+/// When each image was last pulled, kept next to the project so the daily
+/// and weekly policies survive restarts of the manager.
+#[derive(Serialize, Deserialize, Default)]
+struct PullState {
+    /// Unix seconds of the last successful pull, by image reference.
+    pulled_at: BTreeMap<String, u64>,
+}
+
+impl PullState {
+    fn path(project: &str) -> String {
+        format!("containers/{}/.pull-state.json", project)
+    }
+
+    async fn load(project: &str) -> Self {
+        tokio::fs::read_to_string(Self::path(project))
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    async fn save(&self, project: &str) {
+        if let Ok(content) = serde_json::to_string_pretty(self) {
+            tokio::fs::write(Self::path(project), content).await.ok();
+        }
+    }
+
+    fn age(&self, image: &str) -> Option<Duration> {
+        let pulled_at = *self.pulled_at.get(image)?;
+        Some(Duration::from_secs(unix_now().saturating_sub(pulled_at)))
+    }
+}
+
+impl App {
+    pub fn pull_policies(&self, project: &str) -> PullPolicies {
+        let settings = self.settings.project(project);
+        PullPolicies {
+            project: settings
+                .and_then(|project| project.pull_policy)
+                .unwrap_or(self.settings.pull.policy),
+            services: settings
+                .map(|project| project.pull_policies.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn pull_updates(&mut self) {
+        if let Some(i) = self.state.selected() {
+            self.pull_updates_at(i);
+        }
+    }
+
+    /// Pulls every image of the project regardless of policy and reports which
+    /// ones changed digest. The project keeps running; the new images are used
+    /// on its next start.
+    pub fn pull_updates_at(&mut self, i: usize) {
+        if !self.docker_daemon_running {
+            self.set_toast(
+                ToastState::Error,
+                "Cannot pull: Docker daemon not responding",
+                5,
+            );
+            return;
+        }
+
+        let service_name = self.services[i].name.clone();
+        if self.active_job(&service_name).is_some() {
+            self.set_toast(
+                ToastState::Warning,
+                format!(
+                    "{} is busy, press {} to cancel",
+                    service_name, self.keybinds.services.cancel_job
+                ),
+                3,
+            );
+            return;
+        }
+
+        let logs = Arc::clone(&self.services[i].logs);
+        let status = self.services[i].status.clone();
+        let name = service_name.clone();
+        self.spawn_job(i, JobKind::PullUpdates, JobPhase::Pull, status, |job| {
+            pull_project_updates(name, logs, job)
+        });
+        self.services[i].pull_progress = Some("queued".to_string());
+        self.services[i].pull_layers = None;
+
+        self.set_toast(
+            ToastState::Info,
+            format!("Checking {} for updates", service_name),
+            3,
+        );
+    }
+}
+
+/// Decides which compose services need a pull before `up`, logging the
+/// reason for each. `None` means the compose file could not be read and
+/// everything should be pulled.
+pub async fn services_to_pull(
+    project: &str,
+    policies: &PullPolicies,
+    logs: &SharedLogBuffer,
+) -> Option<Vec<String>> {
+    let compose = ComposeFile::load(project).await?;
+    let state = PullState::load(project).await;
+
+    let mut selected = Vec::new();
+    for (name, image) in compose.pulled_images() {
+        let compose_policy = compose.services[name].pull_policy.as_deref();
+        let policy = policies.resolve(name, compose_policy);
+        let (pull, reason) = match policy {
+            PullPolicy::Always => (true, "always".to_string()),
+            PullPolicy::Never => (false, "never".to_string()),
+            _ if !DockerClient::image_exists(image).await => (true, "missing".to_string()),
+            PullPolicy::Missing => (false, "present".to_string()),
+            PullPolicy::Daily | PullPolicy::Weekly => {
+                let max_age = policy.max_age().unwrap_or_default();
+                match state.age(image) {
+                    Some(age) if age < max_age => (
+                        false,
+                        format!("{}, pulled {}h ago", policy, age.as_secs() / 3600),
+                    ),
+                    _ => (true, format!("{}, due", policy)),
+                }
+            }
+        };
+
+        logs.lock().unwrap().push_str(&format!(
+            "{} ({}): {} [{}]\n",
+            name,
+            image,
+            if pull { "pull" } else { "skip" },
+            reason
+        ));
+        if pull {
+            selected.push(name.clone());
+        }
+    }
+    Some(selected)
+}
+
+/// Pulls `services` (all of them when empty), streaming progress to the job,
+/// and records the pull time of each image on success.
+pub async fn run_pull(
+    project: &str,
+    services: &[String],
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    let tracker = Arc::new(Mutex::new(PullTracker::default()));
+    let filter: LineFilter = {
+        let job = job.clone();
+        let tracker = Arc::clone(&tracker);
+        Arc::new(move |line: &str| tracker.lock().unwrap().apply_line(line, &job))
+    };
+
+    let compose = ComposeProject::new(project.to_string());
+    let json_progress = supports_json_progress().await;
+    let result = match run_stream_with_line_filter(
+        compose.pull_cmd(json_progress, services),
+        Arc::clone(logs),
+        Some("Pull output:\n"),
+        Some(filter),
+    )
+    .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("pull exited with non-zero status".to_string()),
+        Err(e) => {
+            let mut logs_lock = logs.lock().unwrap();
+            logs_lock.push_str(&format!("Pull failed: {}\n", e));
+            Err(e.to_string())
+        }
+    };
+    job.pull(tracker.lock().unwrap().progress.clone());
+    result?;
+
+    if let Some(compose) = ComposeFile::load(project).await {
+        let mut state = PullState::load(project).await;
+        let now = unix_now();
+        for (name, image) in compose.pulled_images() {
+            if services.is_empty() || services.contains(name) {
+                state.pulled_at.insert(image.clone(), now);
+            }
+        }
+        state.save(project).await;
+    }
+    Ok(())
+}
+
+/// Pulls every image of the project and logs which ones changed digest.
+async fn pull_project_updates(
+    project: String,
+    logs: SharedLogBuffer,
+    job: JobReporter,
+) -> Result<(), String> {
+    logs.lock().unwrap().clear();
+    let images: Vec<String> = ComposeFile::load(&project)
+        .await
+        .map(|compose| {
+            compose
+                .pulled_images()
+                .map(|(_, image)| image.clone())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut before = HashMap::new();
+    for image in &images {
+        before.insert(image.clone(), DockerClient::image_digest(image).await);
+    }
+
+    let result = run_pull(&project, &[], &logs, &job).await;
+    job.progress(None);
+    result?;
+
+    let mut changed = Vec::new();
+    for image in &images {
+        let after = DockerClient::image_digest(image).await;
+        if after.is_some() && before.get(image) != Some(&after) {
+            changed.push(image.clone());
+        }
+    }
+
+    let mut logs_lock = logs.lock().unwrap();
+    if changed.is_empty() {
+        logs_lock.push_str("All images up to date.\n");
+        drop(logs_lock);
+        job.toast(ToastState::Info, format!("{}: images up to date", project));
+    } else {
+        logs_lock.push_str("Updated images (restart to use them):\n");
+        for image in &changed {
+            logs_lock.push_str(&format!("  {}\n", image));
+        }
+        drop(logs_lock);
+        job.toast(
+            ToastState::Success,
+            format!(
+                "{}: {} updated: {}",
+                project,
+                changed.len(),
+                changed.join(", ")
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or(0)
+}
+
+/// Pull progress as the task sees it, with the time it last reported.
+#[derive(Default)]
+struct PullTracker {
+    progress: PullProgress,
+    last_sent: Option<Instant>,
+}
+
+impl PullTracker {
+    /// Feeds one line of pull output and returns what to log for it.
+    fn apply_line(&mut self, line: &str, job: &JobReporter) -> Option<String> {
+        let (logged, urgent) = match self.progress.apply_line(line) {
+            PullLine::Other => return Some(line.to_string()),
+            PullLine::Tick => (None, false),
+            PullLine::Changed(text) => (Some(text), true),
+        };
+
+        if urgent
+            || self
+                .last_sent
+                .is_none_or(|at| at.elapsed() >= PULL_UPDATE_INTERVAL)
+        {
+            self.last_sent = Some(Instant::now());
+            job.pull(self.progress.clone());
+        }
+        logged
+    }
+}
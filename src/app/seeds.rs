@@ -0,0 +1,470 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::jobs::{JobKind, JobPhase, JobReporter};
+use crate::app::state::App;
+use crate::docker::compose::ComposeProject;
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::dumps::{self, DbEngine, DbService};
+use crate::docker::process::{run_capture, run_with_files};
+use crate::log_buffer::SharedLogBuffer;
+use crate::status::{Status, ToastState};
+
+/// How often a database is probed while waiting for it to accept connections.
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SeedKind {
+    Sql,
+    Shell,
+}
+
+/// A `.sql` or `.sh` file under the project's `seeds/` directory.
+#[derive(Clone)]
+pub struct Seed {
+    /// Path under `seeds/`, which is also its key in the state file.
+    pub id: String,
+    pub path: PathBuf,
+    /// Service named by a `seeds/<service>/` directory; seeds directly in
+    /// `seeds/` go to the project's database.
+    pub service: Option<String>,
+    pub kind: SeedKind,
+}
+
+/// Seeds that have run against a project, kept next to it so each one is
+/// applied once per database rather than once per manager session.
+#[derive(Serialize, Deserialize, Default)]
+pub struct SeedState {
+    /// Unix seconds of the last successful run, by seed id.
+    pub applied: BTreeMap<String, u64>,
+}
+
+impl SeedState {
+    fn path(project: &str) -> String {
+        format!("containers/{}/.seed-state.json", project)
+    }
+
+    /// Reads the state synchronously, for the overlay.
+    pub fn read(project: &str) -> Self {
+        fs::read_to_string(Self::path(project))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    async fn load(project: &str) -> Self {
+        tokio::fs::read_to_string(Self::path(project))
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    async fn save(&self, project: &str) {
+        if let Ok(content) = serde_json::to_string_pretty(self) {
+            tokio::fs::write(Self::path(project), content).await.ok();
+        }
+    }
+
+    /// Where a snapshot keeps the state of the data it holds.
+    fn snapshot_path(archive: &Path) -> PathBuf {
+        archive.with_extension("seed-state.json")
+    }
+
+    /// Keeps the state next to `archive`, so restoring the snapshot brings
+    /// back which seeds its data already has.
+    pub async fn save_for_snapshot(project: &str, archive: &Path) -> Result<(), String> {
+        replace_file(
+            &Self::snapshot_path(archive),
+            Some(Path::new(&Self::path(project))),
+        )
+        .await
+    }
+
+    /// Replaces the state after the project's data changed underneath it:
+    /// with the one kept for the restored `archive`, or with nothing after a
+    /// wipe or for a snapshot taken without one.
+    pub async fn reset(project: &str, archive: Option<&Path>) -> Result<(), String> {
+        let kept = archive.map(Self::snapshot_path);
+        replace_file(Path::new(&Self::path(project)), kept.as_deref()).await
+    }
+}
+
+/// Copies `source` over `target`, or removes `target` when there is no
+/// `source` file.
+async fn replace_file(target: &Path, source: Option<&Path>) -> Result<(), String> {
+    if let Some(source) = source
+        && tokio::fs::try_exists(source).await.unwrap_or(false)
+    {
+        return tokio::fs::copy(source, target)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+    }
+    match tokio::fs::remove_file(target).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// What the start flow does with a project's seeds.
+#[derive(Clone, Copy)]
+pub struct SeedOptions {
+    /// Run seeds that were never applied.
+    pub auto: bool,
+    pub ready_secs: u64,
+}
+
+/// The seeds overlay: every seed of one project and when each last ran.
+pub struct SeedsView {
+    pub project: String,
+    pub seeds: Vec<Seed>,
+    pub state: SeedState,
+    pub selected: usize,
+}
+
+/// Every seed of the project, ordered by file name so numbered prefixes
+/// decide the order across service directories.
+pub fn discover(project: &str) -> Vec<Seed> {
+    let root = Path::new("containers").join(project).join("seeds");
+    let mut seeds = seed_files(&root, None);
+    if let Ok(entries) = fs::read_dir(&root) {
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                let service = entry.file_name().to_string_lossy().into_owned();
+                seeds.extend(seed_files(&entry.path(), Some(service)));
+            }
+        }
+    }
+    seeds.sort_by(|a, b| {
+        a.path
+            .file_name()
+            .cmp(&b.path.file_name())
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    seeds
+}
+
+fn seed_files(dir: &Path, service: Option<String>) -> Vec<Seed> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .filter_map(|entry| {
+            let path = entry.path();
+            let kind = match path.extension()?.to_str()? {
+                "sql" => SeedKind::Sql,
+                "sh" => SeedKind::Shell,
+                _ => return None,
+            };
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let id = match &service {
+                Some(service) => format!("{}/{}", service, name),
+                None => name,
+            };
+            Some(Seed {
+                id,
+                path,
+                service: service.clone(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+/// Seeds never applied to the project.
+pub async fn pending_seeds(project: &str) -> Vec<Seed> {
+    let state = SeedState::load(project).await;
+    discover(project)
+        .into_iter()
+        .filter(|seed| !state.applied.contains_key(&seed.id))
+        .collect()
+}
+
+impl App {
+    pub fn seed_options(&self) -> SeedOptions {
+        SeedOptions {
+            auto: self.settings.seeds.auto,
+            ready_secs: self.settings.seeds.ready_secs,
+        }
+    }
+
+    pub fn open_seeds(&mut self) {
+        let Some(i) = self.state.selected() else {
+            return;
+        };
+        let project = self.services[i].name.clone();
+        self.seeds_view = Some(SeedsView {
+            seeds: discover(&project),
+            state: SeedState::read(&project),
+            project,
+            selected: 0,
+        });
+    }
+
+    pub fn close_seeds(&mut self) {
+        self.seeds_view = None;
+    }
+
+    /// Rereads seeds and their state if the overlay shows `project`.
+    pub fn refresh_seeds_view(&mut self, project: &str) {
+        if let Some(view) = self
+            .seeds_view
+            .as_mut()
+            .filter(|view| view.project == project)
+        {
+            view.seeds = discover(project);
+            view.state = SeedState::read(project);
+            view.selected = view.selected.min(view.seeds.len().saturating_sub(1));
+        }
+    }
+
+    pub fn seeds_select_next(&mut self) {
+        if let Some(view) = self.seeds_view.as_mut()
+            && view.selected + 1 < view.seeds.len()
+        {
+            view.selected += 1;
+        }
+    }
+
+    pub fn seeds_select_previous(&mut self) {
+        if let Some(view) = self.seeds_view.as_mut() {
+            view.selected = view.selected.saturating_sub(1);
+        }
+    }
+
+    /// Runs the highlighted seed again, whether or not it was applied.
+    pub fn run_selected_seed(&mut self) {
+        let Some(seed) = self
+            .seeds_view
+            .as_ref()
+            .and_then(|view| view.seeds.get(view.selected))
+            .cloned()
+        else {
+            return;
+        };
+        self.spawn_seed_job(vec![seed]);
+    }
+
+    pub fn run_pending_seeds(&mut self) {
+        let Some(view) = self.seeds_view.as_ref() else {
+            return;
+        };
+        let pending: Vec<Seed> = view
+            .seeds
+            .iter()
+            .filter(|seed| !view.state.applied.contains_key(&seed.id))
+            .cloned()
+            .collect();
+        if pending.is_empty() {
+            self.set_toast(ToastState::Info, "All seeds are applied", 3);
+            return;
+        }
+        self.spawn_seed_job(pending);
+    }
+
+    fn spawn_seed_job(&mut self, seeds: Vec<Seed>) {
+        let Some(project) = self.seeds_view.as_ref().map(|view| view.project.clone()) else {
+            return;
+        };
+        let Some(i) = self.service_index(&project) else {
+            return;
+        };
+        if !self.docker_daemon_running {
+            self.set_toast(ToastState::Error, "Docker daemon not responding", 5);
+            return;
+        }
+        if self.active_job(&project).is_some() {
+            self.set_toast(
+                ToastState::Warning,
+                format!(
+                    "{} is busy, press {} to cancel",
+                    project, self.keybinds.services.cancel_job
+                ),
+                3,
+            );
+            return;
+        }
+        if self.services[i].status != Status::Running {
+            self.set_toast(ToastState::Warning, format!("Start {} first", project), 3);
+            return;
+        }
+
+        let status = self.services[i].status.clone();
+        let logs = Arc::clone(&self.services[i].logs);
+        let ready_secs = self.settings.seeds.ready_secs;
+        let count = seeds.len();
+        self.spawn_job(i, JobKind::Seed, JobPhase::Seed, status, |job| async move {
+            run_seeds(&project, seeds, ready_secs, &logs, &job).await
+        });
+        self.set_toast(ToastState::Info, format!("Running {} seeds", count), 3);
+    }
+}
+
+/// Runs `seeds` in order, waiting for each target database to accept
+/// connections first, and records every one that succeeds. Stops at the
+/// first failure.
+pub async fn run_seeds(
+    project: &str,
+    seeds: Vec<Seed>,
+    ready_secs: u64,
+    logs: &SharedLogBuffer,
+    job: &JobReporter,
+) -> Result<(), String> {
+    let compose = ComposeProject::new(project);
+    let databases = match ComposeFile::resolved(project).await {
+        Some(resolved) => dumps::db_services(&resolved),
+        None => Vec::new(),
+    };
+    // Seeds directly in `seeds/` go to the first SQL database, else the first
+    // database of any kind.
+    let default = databases
+        .iter()
+        .find(|db| db.engine != DbEngine::Redis)
+        .or_else(|| databases.first());
+
+    let mut state = SeedState::load(project).await;
+    let mut ready = HashSet::new();
+    let total = seeds.len();
+    for (n, seed) in seeds.iter().enumerate() {
+        job.progress(Some(format!("{}/{} {}", n + 1, total, seed.id)));
+        let (service, database) = match &seed.service {
+            Some(service) => (
+                service.clone(),
+                databases.iter().find(|db| db.service == *service),
+            ),
+            None => {
+                let db = default.ok_or_else(|| {
+                    format!(
+                        "{}: no database service; put it under seeds/<service>/",
+                        seed.id
+                    )
+                })?;
+                (db.service.clone(), Some(db))
+            }
+        };
+
+        if let Some(database) = database
+            && ready.insert(service.clone())
+        {
+            wait_ready(&compose, database, ready_secs, logs).await?;
+        }
+
+        let cmd = match (seed.kind, database) {
+            (SeedKind::Sql, Some(database)) => database
+                .sql_cmd(&compose)
+                .ok_or_else(|| format!("{}: {} does not run SQL", seed.id, service))?,
+            (SeedKind::Sql, None) => {
+                return Err(format!("{}: {} is not a known database", seed.id, service));
+            }
+            (SeedKind::Shell, Some(database)) => database.shell_cmd(&compose),
+            (SeedKind::Shell, None) => compose.exec_shell_cmd(&service),
+        };
+        let file = tokio::fs::File::open(&seed.path)
+            .await
+            .map_err(|e| format!("{}: {}", seed.id, e))?
+            .into_std()
+            .await;
+        let header = format!("Seed {} ({}):\n", seed.id, service);
+        let reason =
+            match run_with_files(cmd, Some(file), None, Arc::clone(logs), Some(&header)).await {
+                Ok(true) => None,
+                Ok(false) => Some("exited with non-zero status".to_string()),
+                Err(e) => Some(e.to_string()),
+            };
+        if let Some(reason) = reason {
+            logs.lock()
+                .unwrap()
+                .push_str(&format!("[seed] {} failed: {}\n", seed.id, reason));
+            return Err(format!("seed {} failed", seed.id));
+        }
+
+        state.applied.insert(seed.id.clone(), unix_now());
+        state.save(project).await;
+        logs.lock()
+            .unwrap()
+            .push_str(&format!("[seed] applied {}\n", seed.id));
+    }
+    job.progress(None);
+
+    job.toast(ToastState::Success, format!("Applied {} seeds", total));
+    Ok(())
+}
+
+/// Polls the database until it accepts connections or `ready_secs` pass.
+async fn wait_ready(
+    compose: &ComposeProject,
+    database: &DbService,
+    ready_secs: u64,
+    logs: &SharedLogBuffer,
+) -> Result<(), String> {
+    let deadline = Instant::now() + Duration::from_secs(ready_secs);
+    loop {
+        if run_capture(database.ready_cmd(compose))
+            .await
+            .is_ok_and(|out| out.status.success())
+        {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            let reason = format!(
+                "{} did not accept connections within {}s",
+                database.service, ready_secs
+            );
+            logs.lock()
+                .unwrap()
+                .push_str(&format!("[seed] {}\n", reason));
+            return Err(reason);
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_keep_the_seed_state_beside_the_archive() {
+        assert_eq!(
+            SeedState::snapshot_path(Path::new("snapshots/app-20260101-120000.tar")),
+            Path::new("snapshots/app-20260101-120000.seed-state.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_file_copies_or_clears_the_state() {
+        let dir = std::env::temp_dir().join(format!("docker-manager-seeds-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let state = dir.join(".seed-state.json");
+        let kept = dir.join("snap.seed-state.json");
+        fs::write(&state, "{\"applied\":{\"a.sql\":1}}").unwrap();
+        fs::write(&kept, "{\"applied\":{}}").unwrap();
+
+        replace_file(&state, Some(&kept)).await.unwrap();
+        assert_eq!(fs::read_to_string(&state).unwrap(), "{\"applied\":{}}");
+
+        replace_file(&state, Some(&dir.join("missing.json")))
+            .await
+            .unwrap();
+        assert!(!state.exists());
+        replace_file(&state, None).await.unwrap();
+        assert!(!state.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,328 +1,404 @@
-use std::fs;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::thread;
+use std::time::{Duration, Instant};
 
-use serde_yaml;
+use tokio::process::Command;
 
+use crate::app::builds::{run_build, services_to_build};
+use crate::app::jobs::{JobKind, JobPhase, JobReporter};
+use crate::app::lock::{LockOptions, check_lock};
+use crate::app::pulls::{PullPolicies, run_pull, services_to_pull};
+use crate::app::seeds::{SeedOptions, pending_seeds, run_seeds};
 use crate::app::state::App;
 use crate::docker::client::DockerClient;
 use crate::docker::compose::ComposeProject;
+use crate::docker::compose_file::ComposeFile;
 use crate::docker::daemon;
-use crate::docker::process::{run_stream, run_stream_with_line_callback};
+use crate::docker::overrides::{self, SHARED_NETWORK_FILE};
+use crate::docker::process::run_stream;
+use crate::log_buffer::SharedLogBuffer;
+use crate::notify::AppMessage;
 use crate::status::{Status, ToastState};
 
+/// Result of one background status refresh. `statuses` is only filled in when
+/// the daemon is up and a full comparison was requested.
+pub struct StatusSnapshot {
+    pub daemon_running: bool,
+    pub statuses: Option<HashMap<String, Status>>,
+    pub fully_stopped: HashSet<String>,
+}
+
 impl App {
+    /// Kicks off a status refresh in the background; the result comes back as
+    /// a [`StatusSnapshot`] message. At most one refresh runs at a time.
     pub fn refresh_statuses(&mut self) {
-        const DAEMON_PROBE_COOLDOWN_TICKS: u8 = 60;
+        const DAEMON_PROBE_INTERVAL: Duration = Duration::from_secs(2);
+
+        if self.status_refresh_pending {
+            self.status_refresh_requested = true;
+            return;
+        }
 
         let should_probe_daemon = self.first_status_check
-            || self.daemon_probe_cooldown_ticks == 0
+            || self
+                .last_daemon_probe
+                .is_none_or(|at| at.elapsed() >= DAEMON_PROBE_INTERVAL)
             || !self.docker_daemon_running;
-        let daemon_running = if should_probe_daemon {
-            self.daemon_probe_cooldown_ticks = DAEMON_PROBE_COOLDOWN_TICKS;
-            daemon::docker_service_active() && DockerClient::docker_info_ok()
-        } else {
-            self.docker_daemon_running
-        };
-        let daemon_changed = daemon_running != self.docker_daemon_running;
-        self.docker_daemon_running = daemon_running;
+        if should_probe_daemon {
+            self.last_daemon_probe = Some(Instant::now());
+        }
+
+        let was_running = self.docker_daemon_running;
         let has_transitioning_services = self.services.iter().any(|service| {
             matches!(
-                *service.status.lock().unwrap(),
-                Status::Pulling | Status::Starting | Status::Stopping
+                service.status,
+                Status::Pulling | Status::Building | Status::Starting | Status::Stopping
             )
         });
+        let force = self.first_status_check || has_transitioning_services;
+        let service_names: Vec<String> = self.services.iter().map(|s| s.name.clone()).collect();
+        let stopping: Vec<String> = self
+            .services
+            .iter()
+            .filter(|service| service.status == Status::Stopping)
+            .map(|service| service.name.clone())
+            .collect();
+
+        self.status_refresh_pending = true;
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            let daemon_running = if should_probe_daemon {
+                daemon::docker_service_active().await && DockerClient::docker_info_ok().await
+            } else {
+                was_running
+            };
+
+            let mut statuses = None;
+            let mut fully_stopped = HashSet::new();
+            if daemon_running && (force || daemon_running != was_running) {
+                statuses = Some(DockerClient::get_batch_statuses(&service_names).await);
+                for name in stopping {
+                    if DockerClient::all_containers_stopped(&name).await {
+                        fully_stopped.insert(name);
+                    }
+                }
+            }
+
+            notifier.send(AppMessage::StatusSnapshot(StatusSnapshot {
+                daemon_running,
+                statuses,
+                fully_stopped,
+            }));
+        });
+    }
+
+    pub fn apply_status_snapshot(&mut self, snapshot: StatusSnapshot) {
+        self.status_refresh_pending = false;
+        if self.first_status_check && !snapshot.daemon_running {
+            self.set_toast(ToastState::Warning, "Docker daemon not running.", 4);
+        }
+        self.docker_daemon_running = snapshot.daemon_running;
 
         if !self.docker_daemon_running {
             self.event_listener_running = false;
             for service in &mut self.services {
-                *service.status.lock().unwrap() = Status::DaemonNotRunning;
-                *service.pull_progress.lock().unwrap() = None;
+                service.status = Status::DaemonNotRunning;
+                service.pull_progress = None;
             }
-        } else if self.first_status_check || daemon_changed || has_transitioning_services {
-            let service_names: Vec<String> = self.services.iter().map(|s| s.name.clone()).collect();
-            let batch_statuses = DockerClient::get_batch_statuses(&service_names);
-
+        } else if let Some(batch_statuses) = snapshot.statuses {
+            // A running job reports the status it leads to itself.
+            let busy: HashSet<String> = self
+                .jobs
+                .iter()
+                .filter(|job| job.is_active() && job.kind.reports_status())
+                .map(|job| job.service.clone())
+                .collect();
             for service in &mut self.services {
+                if busy.contains(&service.name) {
+                    continue;
+                }
                 if let Some(actual_status) = batch_statuses.get(&service.name).cloned() {
-                    let mut status_lock = service.status.lock().unwrap();
-                    match *status_lock {
-                        Status::Pulling => {
+                    match service.status {
+                        Status::Pulling | Status::Building | Status::Starting => {
                             if actual_status == Status::Running {
-                                *service.pull_progress.lock().unwrap() = None;
-                                *status_lock = Status::Running;
+                                service.pull_progress = None;
+                                service.status = Status::Running;
                             }
                         }
-                        Status::Starting => {
+                        Status::CrashLoop => {
                             if actual_status == Status::Running {
-                                *service.pull_progress.lock().unwrap() = None;
-                                *status_lock = Status::Running;
+                                service.status = Status::Running;
                             }
                         }
                         Status::Stopping => {
                             if actual_status == Status::Stopped
-                                && DockerClient::all_containers_stopped(&service.name)
+                                && snapshot.fully_stopped.contains(&service.name)
                             {
-                                *service.pull_progress.lock().unwrap() = None;
-                                *status_lock = Status::Stopped;
+                                service.pull_progress = None;
+                                service.status = Status::Stopped;
                             }
                         }
                         _ => {
-                            *status_lock = actual_status;
+                            service.status = actual_status;
                         }
                     }
                 }
             }
+            if self.first_status_check {
+                self.populate_initial_logs();
+            }
             self.first_status_check = false;
         }
 
         if self.docker_daemon_running && !self.event_listener_running {
             self.start_event_listeners();
         }
+
+        if std::mem::take(&mut self.status_refresh_requested) {
+            self.refresh_statuses();
+        }
     }
 
     pub fn start_service(&mut self) {
         if let Some(i) = self.state.selected() {
-            if !daemon::docker_service_active() {
-                self.set_toast(
-                    ToastState::Error,
-                    "Cannot start service: Docker service not running",
-                    5,
-                );
-                return;
-            }
-            if !self.docker_daemon_running {
-                self.set_toast(
-                    ToastState::Error,
-                    "Cannot start service: Docker daemon not responding",
-                    5,
-                );
-                return;
-            }
-
-            let service_name = self.services[i].name.clone();
-            let current_status = DockerClient::get_status(&service_name);
-            if current_status == Status::Running {
-                self.set_toast(
-                    ToastState::Warning,
-                    format!("{} already running", service_name),
-                    4,
-                );
-                return;
-            }
-
-            let service = &mut self.services[i];
-
-            if matches!(
-                *service.status.lock().unwrap(),
-                Status::Pulling | Status::Starting | Status::Stopping
-            ) {
-                self.set_toast(
-                    ToastState::Warning,
-                    format!("{} is busy, wait for operation to finish", service_name),
-                    3,
-                );
-                return;
-            }
-
-            *service.status.lock().unwrap() = Status::Pulling;
-            *service.pull_progress.lock().unwrap() = Some("queued".to_string());
-
-            let service_name_for_toast = service_name.clone();
-            let logs = Arc::clone(&service.logs);
-            let status = Arc::clone(&service.status);
-            let pull_progress = Arc::clone(&service.pull_progress);
-            let project = ComposeProject::new(service_name.clone());
-            let service_name_for_status = service_name.clone();
-
-            thread::spawn(move || {
-                {
-                    let mut logs_lock = logs.lock().unwrap();
-                    logs_lock.clear();
-                }
-
-                let compose_path = format!("containers/{}/docker-compose.yml", service_name);
-                let mut skip_pull = false;
-                if let Ok(content) = fs::read_to_string(&compose_path)
-                    && let Ok(compose) = serde_yaml::from_str::<serde_yaml::Value>(&content)
-                        && let Some(services) = compose.get("services").and_then(|s| s.as_mapping())
-                        {
-                            let mut all_images_exist = true;
-                            for (_service_name, service_def) in services {
-                                if let Some(image) =
-                                    service_def.get("image").and_then(|i| i.as_str())
-                                    && !DockerClient::image_exists(image) {
-                                        all_images_exist = false;
-                                        break;
-                                    }
-                            }
-                            if all_images_exist {
-                                skip_pull = true;
-                                let mut logs_lock = logs.lock().unwrap();
-                                logs_lock.push_str("All images already present, skipping pull.\n");
-                                *pull_progress.lock().unwrap() = Some("cached".to_string());
-                            }
-                        }
-
-                let pull_success = if skip_pull {
-                    true
-                } else {
-                    let progress_callback = {
-                        let pull_progress = Arc::clone(&pull_progress);
-                        Arc::new(move |line: &str| {
-                            if let Some(progress) = extract_pull_progress(line) {
-                                *pull_progress.lock().unwrap() = Some(progress);
-                            }
-                        })
-                    };
-
-                    match run_stream_with_line_callback(
-                        project.pull_cmd(),
-                        Arc::clone(&logs),
-                        Some("Pull output:\n"),
-                        Some(progress_callback),
-                    ) {
-                        Ok(success) => success,
-                        Err(e) => {
-                            let mut logs_lock = logs.lock().unwrap();
-                            logs_lock.push_str(&format!("Pull failed: {}\n", e));
-                            false
-                        }
-                    }
-                };
+            self.start_service_at(i);
+        }
+    }
 
-                if !pull_success {
-                    *pull_progress.lock().unwrap() = None;
-                    *status.lock().unwrap() = Status::Error;
-                    return;
-                }
+    pub fn start_service_at(&mut self, i: usize) {
+        if !self.docker_daemon_running {
+            self.set_toast(
+                ToastState::Error,
+                "Cannot start service: Docker daemon not responding",
+                5,
+            );
+            return;
+        }
 
-                *pull_progress.lock().unwrap() = None;
-                *status.lock().unwrap() = Status::Starting;
-
-                match run_stream(
-                    project.up_detached_cmd(),
-                    Arc::clone(&logs),
-                    Some("Up output:\n"),
-                ) {
-                    Ok(true) => {
-                        let actual_status = DockerClient::get_status(&service_name_for_status);
-                        if actual_status == Status::Running {
-                            *status.lock().unwrap() = Status::Running;
-                        }
-                    }
-                    Ok(false) => {
-                        let mut logs_lock = logs.lock().unwrap();
-                        logs_lock.push_str("Up failed: command exited with non-zero status\n");
-                        *status.lock().unwrap() = Status::Error;
-                    }
-                    Err(e) => {
-                        let mut logs_lock = logs.lock().unwrap();
-                        logs_lock.push_str(&format!("Up failed: {}\n", e));
-                        *status.lock().unwrap() = Status::Error;
-                    }
-                }
-            });
+        let service_name = self.services[i].name.clone();
+        let current_status = self.services[i].status.clone();
+        if current_status == Status::Running {
+            self.set_toast(
+                ToastState::Warning,
+                format!("{} already running", service_name),
+                4,
+            );
+            return;
+        }
+        if current_status == Status::Paused {
+            self.resume_service_at(i);
+            return;
+        }
 
+        if matches!(
+            current_status,
+            Status::Pulling | Status::Building | Status::Starting | Status::Stopping
+        ) || self.active_job(&service_name).is_some()
+        {
             self.set_toast(
-                ToastState::Success,
-                format!("Starting {}", service_name_for_toast),
+                ToastState::Warning,
+                format!(
+                    "{} is busy, press {} to cancel",
+                    service_name, self.keybinds.services.cancel_job
+                ),
                 3,
             );
+            return;
         }
+
+        let logs = Arc::clone(&self.services[i].logs);
+        let name = service_name.clone();
+        let policies = self.pull_policies(&service_name);
+        let lock = self.lock_options();
+        let seeds = self.seed_options();
+        let shared_network = self.shared_network(&service_name);
+        self.spawn_job(i, JobKind::Start, JobPhase::Pull, Status::Pulling, |job| {
+            start_project(name, policies, lock, seeds, shared_network, logs, job)
+        });
+        self.services[i].pull_progress = Some("queued".to_string());
+        self.services[i].pull_layers = None;
+
+        self.reset_watchdog(&service_name);
+        self.set_toast(ToastState::Success, format!("Starting {}", service_name), 3);
     }
 
     pub fn stop_service(&mut self) {
         if let Some(i) = self.state.selected() {
-            if !daemon::docker_service_active() {
-                self.set_toast(
-                    ToastState::Error,
-                    "Cannot stop service: Docker service not running",
-                    5,
-                );
-                return;
-            }
-            if !self.docker_daemon_running {
-                self.set_toast(
-                    ToastState::Error,
-                    "Cannot stop service: Docker daemon not responding",
-                    5,
-                );
-                return;
-            }
+            self.stop_service_at(i);
+        }
+    }
 
-            let service_name = self.services[i].name.clone();
-            let current_status = DockerClient::get_status(&service_name);
-            if current_status != Status::Running {
-                self.set_toast(
-                    ToastState::Warning,
-                    format!("{} not running", service_name),
-                    4,
-                );
-                return;
-            }
+    /// Starts a stop job; returns whether one was started.
+    pub fn stop_service_at(&mut self, i: usize) -> bool {
+        if !self.docker_daemon_running {
+            self.set_toast(
+                ToastState::Error,
+                "Cannot stop service: Docker daemon not responding",
+                5,
+            );
+            return false;
+        }
 
-            let service = &mut self.services[i];
-
-            if matches!(
-                *service.status.lock().unwrap(),
-                Status::Pulling | Status::Starting | Status::Stopping
-            ) {
-                self.set_toast(
-                    ToastState::Warning,
-                    format!("{} is busy, wait for operation to finish", service_name),
-                    3,
-                );
-                return;
-            }
+        let service_name = self.services[i].name.clone();
+        let current_status = self.services[i].status.clone();
+        if matches!(
+            current_status,
+            Status::Pulling | Status::Building | Status::Starting | Status::Stopping
+        ) || self.active_job(&service_name).is_some()
+        {
+            self.set_toast(
+                ToastState::Warning,
+                format!(
+                    "{} is busy, press {} to cancel",
+                    service_name, self.keybinds.services.cancel_job
+                ),
+                3,
+            );
+            return false;
+        }
+        if !matches!(
+            current_status,
+            Status::Running | Status::Paused | Status::CrashLoop | Status::Error
+        ) {
+            self.set_toast(
+                ToastState::Warning,
+                format!("{} not running", service_name),
+                4,
+            );
+            return false;
+        }
 
-            *service.status.lock().unwrap() = Status::Stopping;
-            *service.pull_progress.lock().unwrap() = None;
+        let service = &mut self.services[i];
+        service.pull_progress = None;
 
-            *service.live_logs.lock().unwrap() = String::new();
-            if let Some(mut child) = service.logs_child.lock().unwrap().take() {
-                let _ = child.kill();
-            }
+        service.live_logs.lock().unwrap().clear();
+        if let Some(task) = service.live_logs_task.take() {
+            task.abort();
+        }
 
-            let service_name_for_toast = service_name.clone();
-            let logs = Arc::clone(&service.logs);
-            let status = Arc::clone(&service.status);
-            let project = ComposeProject::new(service_name);
-
-            thread::spawn(move || {
-                match run_stream(
-                    project.down_cmd(),
-                    Arc::clone(&logs),
-                    Some("Down output:\n"),
-                ) {
-                    Ok(true) => {
-                        *status.lock().unwrap() = Status::Stopped;
-                    }
-                    Ok(false) => {
-                        let mut logs_lock = logs.lock().unwrap();
-                        logs_lock.push_str("Down failed: command exited with non-zero status\n");
-                        *status.lock().unwrap() = Status::Error;
-                    }
-                    Err(e) => {
-                        let mut logs_lock = logs.lock().unwrap();
-                        logs_lock.push_str(&format!("Down failed: {}\n", e));
-                        *status.lock().unwrap() = Status::Error;
-                    }
-                }
-            });
+        let logs = Arc::clone(&service.logs);
+        let project = ComposeProject::new(service_name.clone());
+        self.spawn_job(
+            i,
+            JobKind::Stop,
+            JobPhase::Down,
+            Status::Stopping,
+            |job| async move {
+                let result = run_phase(project.down_cmd(), &logs, "Down output:\n", "Down").await;
+                job.status(if result.is_ok() {
+                    Status::Stopped
+                } else {
+                    Status::Error
+                });
+                result
+            },
+        );
+
+        self.reset_watchdog(&service_name);
+        self.set_toast(ToastState::Success, format!("Stopping {}", service_name), 3);
+        true
+    }
+
+    /// Kills the project's containers outright, for a start or stop that hangs.
+    pub fn kill_service_at(&mut self, i: usize) {
+        let service_name = self.services[i].name.clone();
+        let logs = Arc::clone(&self.services[i].logs);
+        let project = ComposeProject::new(service_name.clone());
+        self.spawn_job(
+            i,
+            JobKind::Kill,
+            JobPhase::Kill,
+            Status::Stopping,
+            |job| async move {
+                let result = run_phase(project.kill_cmd(), &logs, "Kill output:\n", "Kill").await;
+                job.status(if result.is_ok() {
+                    Status::Stopped
+                } else {
+                    Status::Error
+                });
+                result
+            },
+        );
+
+        self.reset_watchdog(&service_name);
+        self.set_toast(
+            ToastState::Warning,
+            format!("Force killing {}", service_name),
+            3,
+        );
+    }
 
+    /// Starts a pause job; returns whether one was started.
+    pub fn pause_service_at(&mut self, i: usize) -> bool {
+        let service = &mut self.services[i];
+        let service_name = service.name.clone();
+        if service.status != Status::Running {
             self.set_toast(
-                ToastState::Success,
-                format!("Stopping {}", service_name_for_toast),
-                3,
+                ToastState::Warning,
+                format!("{} not running", service_name),
+                4,
             );
+            return false;
+        }
+
+        service.live_logs.lock().unwrap().clear();
+        if let Some(task) = service.live_logs_task.take() {
+            task.abort();
         }
+
+        let logs = Arc::clone(&service.logs);
+        let project = ComposeProject::new(service_name.clone());
+        self.spawn_job(
+            i,
+            JobKind::Pause,
+            JobPhase::Pause,
+            Status::Running,
+            |job| async move {
+                run_phase(project.pause_cmd(), &logs, "Pause output:\n", "Pause").await?;
+                job.status(Status::Paused);
+                Ok(())
+            },
+        );
+
+        self.set_toast(ToastState::Success, format!("Pausing {}", service_name), 3);
+        true
+    }
+
+    fn resume_service_at(&mut self, i: usize) {
+        let service_name = self.services[i].name.clone();
+        let logs = Arc::clone(&self.services[i].logs);
+        let project = ComposeProject::new(service_name.clone());
+        self.spawn_job(
+            i,
+            JobKind::Resume,
+            JobPhase::Unpause,
+            Status::Starting,
+            |job| async move {
+                let result =
+                    run_phase(project.unpause_cmd(), &logs, "Unpause output:\n", "Unpause").await;
+                job.status(if result.is_ok() {
+                    Status::Running
+                } else {
+                    Status::Error
+                });
+                result
+            },
+        );
+
+        self.set_toast(ToastState::Success, format!("Resuming {}", service_name), 3);
+    }
+
+    /// The shared network the project joins, if its settings opt in.
+    pub fn shared_network(&self, project: &str) -> Option<String> {
+        self.settings
+            .project(project)
+            .filter(|settings| settings.shared_network)
+            .map(|_| self.settings.shared_network.name.clone())
     }
 
     pub fn toggle_service(&mut self) {
         if let Some(i) = self.state.selected() {
-            let service = &self.services[i];
-            if *service.status.lock().unwrap() == Status::Running {
+            if self.services[i].status == Status::Running {
                 self.stop_service();
             } else {
                 self.start_service();
@@ -331,92 +407,122 @@ impl App {
     }
 }
 
-fn extract_pull_progress(line: &str) -> Option<String> {
-    let trimmed = line.trim();
-    if trimmed.is_empty() {
-        return None;
+/// Pulls images as the project's pull policies require, checks them against
+/// the lock file, builds services whose context changed, brings the project up
+/// and runs seeds never applied, reporting each phase back through the job.
+async fn start_project(
+    service_name: String,
+    policies: PullPolicies,
+    lock: LockOptions,
+    seeds: SeedOptions,
+    shared_network: Option<String>,
+    logs: SharedLogBuffer,
+    job: JobReporter,
+) -> Result<(), String> {
+    let project = ComposeProject::new(service_name.clone());
+    logs.lock().unwrap().clear();
+
+    let services = services_to_pull(&service_name, &policies, &logs).await;
+    if services
+        .as_ref()
+        .is_some_and(|services| services.is_empty())
+    {
+        let mut logs_lock = logs.lock().unwrap();
+        logs_lock.push_str("No images to pull, skipping pull.\n");
+        job.progress(Some("cached".to_string()));
+    } else if let Err(reason) =
+        run_pull(&service_name, &services.unwrap_or_default(), &logs, &job).await
+    {
+        job.progress(None);
+        job.status(Status::Error);
+        return Err(reason);
     }
 
-    let rhs = trimmed
-        .split_once(": ")
-        .map(|(_, rhs)| rhs)
-        .unwrap_or(trimmed);
-
-    if let Some(percent) = rhs.split_whitespace().find(|token| token.ends_with('%')) {
-        return Some(percent.to_string());
-    }
-
-    if let Some((done, total)) = extract_size_ratio(rhs)
-        && total > 0.0 {
-            let phase = if rhs.contains("Extracting") {
-                "Extracting"
-            } else {
-                "Downloading"
-            };
-            let percent = ((done / total) * 100.0).round().clamp(0.0, 100.0) as u8;
-            return Some(format!("{} {}%", phase, percent));
-        }
-
-    for keyword in [
-        "Waiting",
-        "Pulling fs layer",
-        "Downloading",
-        "Extracting",
-        "Download complete",
-        "Pull complete",
-        "Already exists",
-    ] {
-        if rhs.contains(keyword) {
-            return Some(keyword.to_string());
+    check_lock(&service_name, lock, &logs, &job).await;
+
+    let built = services_to_build(&service_name, &logs).await;
+    if !built.is_empty() {
+        job.progress(None);
+        job.phase(JobPhase::Build);
+        job.status(Status::Building);
+        if let Err(reason) = run_build(&service_name, &built, false, &logs, &job).await {
+            job.progress(None);
+            job.status(Status::Error);
+            return Err(reason);
         }
     }
 
-    None
-}
-
-fn extract_size_ratio(text: &str) -> Option<(f64, f64)> {
-    for token in text.split_whitespace() {
-        let cleaned =
-            token.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '/');
-        if let Some((left, right)) = cleaned.split_once('/') {
-            let done = parse_size_to_bytes(left)?;
-            let total = parse_size_to_bytes(right)?;
-            return Some((done, total));
-        }
+    job.progress(None);
+    job.phase(JobPhase::Up);
+    job.status(Status::Starting);
+
+    if let Err(reason) = sync_shared_network(&service_name, shared_network.as_deref(), &logs).await
+    {
+        logs.lock()
+            .unwrap()
+            .push_str(&format!("Shared network failed: {}\n", reason));
+        job.status(Status::Error);
+        return Err(reason);
     }
-
-    None
-}
-
-fn parse_size_to_bytes(token: &str) -> Option<f64> {
-    let cleaned = token.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '.');
-    if cleaned.is_empty() {
-        return None;
+    // The pull phase above already applied the pull policies.
+    if let Err(reason) = run_phase(project.up_detached_cmd(true), &logs, "Up output:\n", "Up").await
+    {
+        job.status(Status::Error);
+        return Err(reason);
+    }
+    if DockerClient::get_status(&service_name).await == Status::Running {
+        job.status(Status::Running);
     }
 
-    let mut split_idx = cleaned.len();
-    for (idx, ch) in cleaned.char_indices() {
-        if !(ch.is_ascii_digit() || ch == '.') {
-            split_idx = idx;
-            break;
+    if seeds.auto {
+        let pending = pending_seeds(&service_name).await;
+        if !pending.is_empty() {
+            job.phase(JobPhase::Seed);
+            run_seeds(&service_name, pending, seeds.ready_secs, &logs, &job).await?;
         }
     }
+    Ok(())
+}
 
-    let number = cleaned[..split_idx].parse::<f64>().ok()?;
-    let unit = cleaned[split_idx..].to_ascii_lowercase();
-
-    let multiplier = match unit.as_str() {
-        "" | "b" => 1.0,
-        "kb" => 1_000.0,
-        "mb" => 1_000_000.0,
-        "gb" => 1_000_000_000.0,
-        "tb" => 1_000_000_000_000.0,
-        "kib" => 1_024.0,
-        "mib" => 1_048_576.0,
-        "gib" => 1_073_741_824.0,
-        "tib" => 1_099_511_627_776.0,
-        _ => return None,
+/// Writes the shared network override when the project opts in, creating the
+/// network first if needed, and removes it when the project does not.
+async fn sync_shared_network(
+    project: &str,
+    network: Option<&str>,
+    logs: &SharedLogBuffer,
+) -> Result<(), String> {
+    let Some(network) = network else {
+        return overrides::remove(project, SHARED_NETWORK_FILE).await;
     };
+    let compose = ComposeFile::load(project)
+        .await
+        .ok_or_else(|| format!("cannot read {}", ComposeFile::path(project)))?;
+    if overrides::ensure_network(network).await? {
+        logs.lock()
+            .unwrap()
+            .push_str(&format!("Created shared network {}\n", network));
+    }
+    overrides::write_shared_network(project, &compose, network).await?;
+    logs.lock()
+        .unwrap()
+        .push_str(&format!("Joining shared network {}\n", network));
+    Ok(())
+}
 
-    Some(number * multiplier)
+/// Runs one compose command for a job, noting a failure in the project log.
+pub async fn run_phase(
+    cmd: Command,
+    logs: &SharedLogBuffer,
+    header: &str,
+    label: &str,
+) -> Result<(), String> {
+    let reason = match run_stream(cmd, Arc::clone(logs), Some(header)).await {
+        Ok(true) => return Ok(()),
+        Ok(false) => "command exited with non-zero status".to_string(),
+        Err(e) => e.to_string(),
+    };
+    logs.lock()
+        .unwrap()
+        .push_str(&format!("{} failed: {}\n", label, reason));
+    Err(reason)
 }
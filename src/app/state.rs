@@ -1,8 +1,27 @@
-use crate::config::Keybinds;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+
+use crate::app::data::DataView;
+use crate::app::disk::DiskView;
+use crate::app::idle::IdleTracker;
+use crate::app::jobs::{Job, JobId};
+use crate::app::ports::PortsView;
+use crate::app::seeds::SeedsView;
+use crate::app::watchdog::WatchdogState;
+use crate::config::{Keybinds, Settings};
+use crate::docker::archive::ArchiveInfo;
 use crate::docker::events::EventListenerHandle;
+use crate::docker::images::ImageInfo;
+use crate::docker::networks::NetworkInfo;
+use crate::docker::stats::StatsListenerHandle;
+use crate::notify::Notifier;
 use crate::service::Service;
 use crate::status::ToastState;
 use crate::toast::Toast;
+use crate::ui::log_cache::StyledLogCache;
+use crate::web::{WebServerHandle, WebServiceState};
 
 #[derive(Clone, Copy, PartialEq, Default)]
 pub enum Focus {
@@ -16,6 +35,9 @@ pub enum LogTab {
     #[default]
     Events,
     LiveLogs,
+    Stats,
+    Images,
+    Networks,
 }
 
 #[derive(Clone, Copy, PartialEq, Default)]
@@ -30,7 +52,7 @@ pub struct App {
     pub state: ratatui::widgets::ListState,
     pub services: Vec<Service>,
     pub toast: Option<Toast>,
-    pub toast_timer: u32,
+    pub toast_expires_at: Option<Instant>,
 
     pub search_mode: bool,
     pub search_query: String,
@@ -43,16 +65,49 @@ pub struct App {
     pub password_input: String,
     pub focus: Focus,
     pub first_status_check: bool,
-    pub log_scroll: u16,
+    pub log_scroll: usize,
     pub log_auto_scroll: bool,
+    pub log_cache: StyledLogCache,
     pub log_tab: LogTab,
+    pub pull_details_expanded: bool,
     pub animation_tick: u64,
-    pub status_refresh_cooldown_ticks: u8,
-    pub daemon_probe_cooldown_ticks: u8,
+    pub started_at: Instant,
+    pub next_status_refresh: Instant,
+    pub last_daemon_probe: Option<Instant>,
+    pub status_refresh_pending: bool,
+    pub status_refresh_requested: bool,
+    pub daemon_action_pending: bool,
     pub event_listener_running: bool,
     pub event_listener_handle: Option<EventListenerHandle>,
-    pub toast_tick_accumulator: u8,
+    pub stats_listener_handle: Option<StatsListenerHandle>,
     pub keybinds: Keybinds,
+    pub notifier: Notifier,
+    pub settings: Settings,
+    pub web_server: Option<WebServerHandle>,
+    pub web_state: Option<watch::Sender<Vec<WebServiceState>>>,
+    pub idle_trackers: HashMap<String, IdleTracker>,
+    pub last_idle_check: Instant,
+    pub watchdogs: HashMap<String, WatchdogState>,
+    pub jobs: VecDeque<Job>,
+    pub next_job_id: JobId,
+    pub jobs_mode: bool,
+    pub jobs_selected: usize,
+    pub timeout_prompt: Option<JobId>,
+    pub bundles_mode: bool,
+    pub bundles: Vec<ArchiveInfo>,
+    pub bundles_selected: usize,
+    pub data_view: Option<DataView>,
+    pub seeds_view: Option<SeedsView>,
+    pub disk_view: Option<DiskView>,
+    pub ports_view: Option<PortsView>,
+    pub images: Vec<ImageInfo>,
+    pub images_selected: usize,
+    pub images_loading: bool,
+    pub images_error: Option<String>,
+    pub networks: Vec<NetworkInfo>,
+    pub networks_loading: bool,
+    pub networks_error: Option<String>,
+    pub image_action_pending: bool,
 }
 
 impl App {
@@ -86,11 +141,17 @@ impl App {
         self.log_auto_scroll = true;
     }
 
+    pub fn service_index(&self, name: &str) -> Option<usize> {
+        self.services
+            .iter()
+            .position(|service| service.name == name)
+    }
+
     pub fn set_toast(&mut self, state: ToastState, message: impl Into<String>, timer: u32) {
         self.toast = Some(Toast {
             state,
             message: message.into(),
         });
-        self.toast_timer = timer;
+        self.toast_expires_at = Some(Instant::now() + Duration::from_secs(timer.into()));
     }
 }
@@ -0,0 +1,153 @@
+use std::time::{Duration, Instant};
+
+use crate::app::jobs::{Job, JobId, JobPhase, JobTimeout};
+use crate::app::state::App;
+use crate::config::TimeoutSettings;
+use crate::status::ToastState;
+
+impl App {
+    /// Flags running jobs that ran past their phase limit or stopped printing
+    /// and reporting progress, and asks the user what to do about the first one.
+    pub fn check_job_timeouts(&mut self) {
+        let now = Instant::now();
+        let stall_limit = TimeoutSettings::limit(self.settings.timeouts.stall_secs);
+        let mut flagged = Vec::new();
+
+        for job in self.jobs.iter_mut().filter(|job| job.is_active()) {
+            let end_index = job.logs.lock().unwrap().end_index();
+            if end_index != job.last_output_index {
+                job.last_output_index = end_index;
+                job.last_output_at = now;
+            }
+            if job.timeout.is_some() {
+                continue;
+            }
+
+            let phase_limit = phase_limit(&self.settings.timeouts, job.phase);
+            let timeout = if let Some(limit) = phase_limit
+                && now.duration_since(job.phase_started_at) >= limit
+            {
+                Some(JobTimeout::Deadline(limit))
+            } else if let Some(limit) = stall_limit
+                && job.phase.reports_output()
+                && now.duration_since(job.last_output_at) >= limit
+            {
+                Some(JobTimeout::Stalled(limit))
+            } else {
+                None
+            };
+
+            if let Some(timeout) = timeout {
+                job.timeout = Some(timeout);
+                let message = format!("{} {} {}", job.service, job.phase, timeout);
+                job.logs
+                    .lock()
+                    .unwrap()
+                    .push_str(&format!("[job] timed out: {}\n", message));
+                flagged.push(message);
+            }
+        }
+
+        if let Some(message) = flagged.pop() {
+            self.set_toast(ToastState::Warning, format!("Timed out: {}", message), 5);
+        }
+        self.sync_timeout_prompt();
+    }
+
+    /// Earliest moment a running job can time out, so the UI loop wakes up for it.
+    pub fn next_job_deadline(&self) -> Option<Instant> {
+        let stall_limit = TimeoutSettings::limit(self.settings.timeouts.stall_secs);
+        self.jobs
+            .iter()
+            .filter(|job| job.is_active() && job.timeout.is_none())
+            .flat_map(|job| {
+                [
+                    phase_limit(&self.settings.timeouts, job.phase)
+                        .map(|limit| job.phase_started_at + limit),
+                    stall_limit
+                        .filter(|_| job.phase.reports_output())
+                        .map(|limit| job.last_output_at + limit),
+                ]
+            })
+            .flatten()
+            .min()
+    }
+
+    pub fn timeout_prompt_job(&self) -> Option<&Job> {
+        let id = self.timeout_prompt?;
+        self.jobs
+            .iter()
+            .find(|job| job.id == id && job.is_active() && job.timeout.is_some())
+    }
+
+    /// Keeps the prompt on a job that is still timed out, moving on to the
+    /// next one once it is resolved.
+    fn sync_timeout_prompt(&mut self) {
+        if self.timeout_prompt_job().is_some() {
+            return;
+        }
+        self.timeout_prompt = self
+            .jobs
+            .iter()
+            .find(|job| job.is_active() && job.timeout.is_some())
+            .map(|job| job.id);
+    }
+
+    /// Restarts the clock on the prompted job, giving it another full limit.
+    pub fn keep_waiting(&mut self) {
+        let Some(id) = self.timeout_prompt.take() else {
+            return;
+        };
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            let now = Instant::now();
+            job.timeout = None;
+            job.phase_started_at = now;
+            job.last_output_at = now;
+        }
+        self.sync_timeout_prompt();
+    }
+
+    pub fn cancel_timed_out_job(&mut self) {
+        if let Some(id) = self.timeout_prompt.take() {
+            self.cancel_job(id);
+        }
+        self.sync_timeout_prompt();
+    }
+
+    /// Cancels the prompted job and kills the project's containers, for
+    /// operations stuck on a container that will not start or stop.
+    pub fn force_kill_timed_out_job(&mut self) {
+        let Some(id) = self.timeout_prompt.take() else {
+            return;
+        };
+        self.force_kill_job(id);
+        self.sync_timeout_prompt();
+    }
+
+    fn force_kill_job(&mut self, id: JobId) {
+        let Some(service) = self
+            .jobs
+            .iter()
+            .find(|job| job.id == id)
+            .map(|job| job.service.clone())
+        else {
+            return;
+        };
+
+        self.cancel_job(id);
+        if let Some(index) = self.service_index(&service) {
+            self.kill_service_at(index);
+        }
+    }
+}
+
+fn phase_limit(timeouts: &TimeoutSettings, phase: JobPhase) -> Option<Duration> {
+    let secs = match phase {
+        JobPhase::Pull | JobPhase::Save | JobPhase::Load => timeouts.pull_secs,
+        JobPhase::Build => timeouts.build_secs,
+        JobPhase::Seed => timeouts.seed_secs,
+        JobPhase::Up | JobPhase::Unpause => timeouts.up_secs,
+        JobPhase::Down | JobPhase::Pause | JobPhase::Kill | JobPhase::Wipe => timeouts.down_secs,
+    };
+    TimeoutSettings::limit(secs)
+}
@@ -0,0 +1,163 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::app::jobs::{JobKind, JobPhase};
+use crate::app::state::App;
+use crate::docker::client::DockerClient;
+use crate::docker::process::run_stream;
+use crate::status::{Status, ToastState};
+
+/// Restart bookkeeping for one project. Crashes older than the policy window
+/// are forgotten, so a project that stays up long enough earns its retries back.
+#[derive(Default)]
+pub struct WatchdogState {
+    crashes: VecDeque<Instant>,
+    pending: BTreeSet<String>,
+    next_restart: Option<Instant>,
+    gave_up: bool,
+}
+
+impl App {
+    pub fn run_watchdog(&mut self) {
+        for index in 0..self.services.len() {
+            let name = self.services[index].name.clone();
+            let crashes: Vec<_> = self.services[index].crashes.drain(..).collect();
+
+            let Some(policy) = self
+                .settings
+                .project(&name)
+                .and_then(|project| project.restart.clone())
+            else {
+                continue;
+            };
+
+            if crashes.is_empty() && !self.watchdogs.contains_key(&name) {
+                continue;
+            }
+
+            let state = self.watchdogs.entry(name.clone()).or_default();
+            if state.gave_up {
+                continue;
+            }
+
+            let now = Instant::now();
+            let window = Duration::from_secs(policy.window_secs);
+            let new_crashes = !crashes.is_empty();
+            let mut last_exit_code = String::new();
+            for crash in crashes {
+                last_exit_code = crash.exit_code;
+                state.crashes.push_back(crash.at);
+                state.pending.insert(crash.container);
+            }
+            while state
+                .crashes
+                .front()
+                .is_some_and(|at| now.duration_since(*at) > window)
+            {
+                state.crashes.pop_front();
+            }
+
+            let service = &mut self.services[index];
+            if new_crashes && state.crashes.len() > policy.max_retries {
+                state.gave_up = true;
+                state.next_restart = None;
+                service.status = Status::CrashLoop;
+                service.events.lock().unwrap().push_str(&format!(
+                    "[watchdog] crash loop: {} crashes within {}s, giving up\n",
+                    state.crashes.len(),
+                    policy.window_secs
+                ));
+                self.set_toast(
+                    ToastState::Error,
+                    format!("{} is crash looping, watchdog gave up", name),
+                    8,
+                );
+                continue;
+            }
+
+            if new_crashes {
+                let exponent = state.crashes.len().saturating_sub(1).min(16) as u32;
+                let delay = policy
+                    .backoff_secs
+                    .saturating_mul(2u64.saturating_pow(exponent))
+                    .min(policy.max_backoff_secs);
+                state.next_restart = Some(now + Duration::from_secs(delay));
+                service.events.lock().unwrap().push_str(&format!(
+                    "[watchdog] {} exited with code {}, restarting in {}s (attempt {}/{})\n",
+                    state.pending.iter().cloned().collect::<Vec<_>>().join(", "),
+                    last_exit_code,
+                    delay,
+                    state.crashes.len(),
+                    policy.max_retries
+                ));
+            }
+
+            if state.next_restart.is_some_and(|at| now >= at) {
+                state.next_restart = None;
+                let containers: Vec<String> =
+                    std::mem::take(&mut state.pending).into_iter().collect();
+                if !containers.is_empty() {
+                    self.restart_containers(index, containers);
+                }
+            }
+        }
+    }
+
+    /// Earliest pending restart, so the UI loop knows when to wake up.
+    pub fn next_watchdog_restart(&self) -> Option<Instant> {
+        self.watchdogs
+            .values()
+            .filter_map(|state| state.next_restart)
+            .min()
+    }
+
+    pub fn dismiss_crash_report(&mut self) {
+        let Some(index) = self.state.selected() else {
+            return;
+        };
+
+        let dismissed = self.services[index].crash_reports.pop();
+        match dismissed {
+            Some(report) => self.set_toast(
+                ToastState::Info,
+                format!("Dismissed crash report for {}", report.container),
+                2,
+            ),
+            None => self.set_toast(ToastState::Info, "No crash reports to dismiss", 2),
+        }
+    }
+
+    pub fn reset_watchdog(&mut self, name: &str) {
+        self.watchdogs.remove(name);
+    }
+
+    fn restart_containers(&mut self, index: usize, containers: Vec<String>) {
+        let logs = Arc::clone(&self.services[index].logs);
+        self.spawn_job(
+            index,
+            JobKind::Restart,
+            JobPhase::Up,
+            Status::Starting,
+            |job| async move {
+                let reason = match run_stream(
+                    DockerClient::start_containers_cmd(&containers),
+                    Arc::clone(&logs),
+                    Some("Watchdog restart:\n"),
+                )
+                .await
+                {
+                    Ok(true) => return Ok(()),
+                    Ok(false) => "command exited with non-zero status".to_string(),
+                    Err(e) => e.to_string(),
+                };
+
+                logs.lock()
+                    .unwrap()
+                    .push_str(&format!("Watchdog restart failed: {}\n", reason));
+                job.status(Status::Error);
+                Err(reason)
+            },
+        );
+    }
+}
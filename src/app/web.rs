@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+use crate::app::state::App;
+use crate::status::ToastState;
+use crate::web::{WebCommand, WebServiceState, WebServiceTarget, spawn_dashboard_server};
+
+impl App {
+    pub fn start_web_dashboard(&mut self) {
+        if !self.settings.web.enabled || self.web_server.is_some() {
+            return;
+        }
+
+        let targets = self
+            .services
+            .iter()
+            .map(|service| WebServiceTarget {
+                name: service.name.clone(),
+                events: Arc::clone(&service.events),
+                live_logs: Arc::clone(&service.live_logs),
+                web_watchers: Arc::clone(&service.web_watchers),
+            })
+            .collect();
+
+        let (state_sender, states) = watch::channel(self.web_service_states());
+        match spawn_dashboard_server(
+            self.settings.web.port,
+            targets,
+            states,
+            self.notifier.clone(),
+        ) {
+            Ok(handle) => {
+                self.set_toast(
+                    ToastState::Info,
+                    format!("Web dashboard on http://{}", handle.addr),
+                    4,
+                );
+                self.web_server = Some(handle);
+                self.web_state = Some(state_sender);
+            }
+            Err(e) => {
+                self.set_toast(
+                    ToastState::Error,
+                    format!("Web dashboard failed to start: {}", e),
+                    5,
+                );
+            }
+        }
+    }
+
+    pub fn stop_web_dashboard(&mut self) {
+        if let Some(mut handle) = self.web_server.take() {
+            handle.signal_shutdown();
+        }
+        self.web_state = None;
+    }
+
+    /// Shares the current statuses with the dashboard; clients are only woken
+    /// when something actually changed.
+    pub fn publish_web_state(&self) {
+        let Some(sender) = &self.web_state else {
+            return;
+        };
+
+        let states = self.web_service_states();
+        sender.send_if_modified(|current| {
+            if *current == states {
+                false
+            } else {
+                *current = states;
+                true
+            }
+        });
+    }
+
+    fn web_service_states(&self) -> Vec<WebServiceState> {
+        self.services
+            .iter()
+            .map(|service| WebServiceState {
+                name: service.name.clone(),
+                status: service.status.clone(),
+                progress: service.pull_progress.clone(),
+            })
+            .collect()
+    }
+
+    pub fn handle_web_command(&mut self, command: WebCommand) {
+        let (name, start) = match command {
+            WebCommand::Start(name) => (name, true),
+            WebCommand::Stop(name) => (name, false),
+        };
+
+        if let Some(index) = self.service_index(&name) {
+            if start {
+                self.start_service_at(index);
+            } else {
+                self.stop_service_at(index);
+            }
+        }
+    }
+}
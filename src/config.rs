@@ -1,11 +1,24 @@
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
+use std::time::Duration;
+
+use crate::log_buffer::LogLimits;
 
 #[derive(Deserialize, Debug)]
 pub struct Keybinds {
     pub app: AppKeys,
     pub services: ServicesKeys,
     pub logs: LogsKeys,
+    #[serde(default)]
+    pub images: ImagesKeys,
+    #[serde(default)]
+    pub data: DataKeys,
+    #[serde(default)]
+    pub seeds: SeedsKeys,
+    #[serde(default)]
+    pub timeout_prompt: TimeoutPromptKeys,
 }
 
 #[derive(Deserialize, Debug)]
@@ -18,6 +31,14 @@ pub struct AppKeys {
     pub switch_tab_right: String,
     pub scroll_down: String,
     pub scroll_up: String,
+    #[serde(default = "default_jobs_key")]
+    pub jobs: String,
+    #[serde(default = "default_bundles_key")]
+    pub bundles: String,
+    #[serde(default = "default_disk_usage_key")]
+    pub disk_usage: String,
+    #[serde(default = "default_ports_key")]
+    pub ports: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -25,11 +46,218 @@ pub struct ServicesKeys {
     pub stop: String,
     pub start: String,
     pub toggle: String,
+    #[serde(default = "default_snooze_idle_key")]
+    pub snooze_idle: String,
+    #[serde(default = "default_dismiss_crash_key")]
+    pub dismiss_crash: String,
+    #[serde(default = "default_cancel_job_key")]
+    pub cancel_job: String,
+    #[serde(default = "default_pull_updates_key")]
+    pub pull_updates: String,
+    #[serde(default = "default_rebuild_key")]
+    pub rebuild: String,
+    #[serde(default = "default_rebuild_no_cache_key")]
+    pub rebuild_no_cache: String,
+    #[serde(default = "default_update_lock_key")]
+    pub update_lock: String,
+    #[serde(default = "default_mark_key")]
+    pub mark: String,
+    #[serde(default = "default_export_bundle_key")]
+    pub export_bundle: String,
+    #[serde(default = "default_data_key")]
+    pub data: String,
+    #[serde(default = "default_seeds_key")]
+    pub seeds: String,
+}
+
+fn default_jobs_key() -> String {
+    "J".to_string()
+}
+
+fn default_bundles_key() -> String {
+    "O".to_string()
+}
+
+fn default_disk_usage_key() -> String {
+    "U".to_string()
+}
+
+fn default_ports_key() -> String {
+    "M".to_string()
+}
+
+fn default_snooze_idle_key() -> String {
+    "z".to_string()
+}
+
+fn default_dismiss_crash_key() -> String {
+    "x".to_string()
+}
+
+fn default_cancel_job_key() -> String {
+    "c".to_string()
+}
+
+fn default_pull_updates_key() -> String {
+    "u".to_string()
+}
+
+fn default_rebuild_key() -> String {
+    "b".to_string()
+}
+
+fn default_rebuild_no_cache_key() -> String {
+    "B".to_string()
+}
+
+fn default_update_lock_key() -> String {
+    "L".to_string()
+}
+
+fn default_mark_key() -> String {
+    "m".to_string()
+}
+
+fn default_export_bundle_key() -> String {
+    "E".to_string()
+}
+
+fn default_data_key() -> String {
+    "D".to_string()
+}
+
+fn default_seeds_key() -> String {
+    "I".to_string()
 }
 
 #[derive(Deserialize, Debug)]
 pub struct LogsKeys {
     pub toggle_auto_scroll: String,
+    #[serde(default = "default_toggle_pull_details_key")]
+    pub toggle_pull_details: String,
+}
+
+fn default_toggle_pull_details_key() -> String {
+    "p".to_string()
+}
+
+/// Keys that act on the highlighted image while the Images tab is shown.
+#[derive(Deserialize, Debug)]
+pub struct ImagesKeys {
+    #[serde(default = "default_pull_image_key")]
+    pub pull: String,
+    #[serde(default = "default_remove_image_key")]
+    pub remove: String,
+    #[serde(default = "default_prune_dangling_key")]
+    pub prune_dangling: String,
+}
+
+impl Default for ImagesKeys {
+    fn default() -> Self {
+        Self {
+            pull: default_pull_image_key(),
+            remove: default_remove_image_key(),
+            prune_dangling: default_prune_dangling_key(),
+        }
+    }
+}
+
+fn default_pull_image_key() -> String {
+    "P".to_string()
+}
+
+fn default_remove_image_key() -> String {
+    "X".to_string()
+}
+
+fn default_prune_dangling_key() -> String {
+    "C".to_string()
+}
+
+/// Keys of the data overlay. `confirm` answers its restore, load and wipe
+/// prompts.
+#[derive(Deserialize, Debug)]
+pub struct DataKeys {
+    #[serde(default = "default_snapshot_key")]
+    pub snapshot: String,
+    #[serde(default = "default_dump_key")]
+    pub dump: String,
+    #[serde(default = "default_wipe_key")]
+    pub wipe: String,
+    #[serde(default = "default_confirm_key")]
+    pub confirm: String,
+}
+
+impl Default for DataKeys {
+    fn default() -> Self {
+        Self {
+            snapshot: default_snapshot_key(),
+            dump: default_dump_key(),
+            wipe: default_wipe_key(),
+            confirm: default_confirm_key(),
+        }
+    }
+}
+
+fn default_snapshot_key() -> String {
+    "s".to_string()
+}
+
+fn default_dump_key() -> String {
+    "d".to_string()
+}
+
+fn default_wipe_key() -> String {
+    "w".to_string()
+}
+
+fn default_confirm_key() -> String {
+    "y".to_string()
+}
+
+/// Keys of the seeds overlay.
+#[derive(Deserialize, Debug)]
+pub struct SeedsKeys {
+    #[serde(default = "default_run_pending_key")]
+    pub run_pending: String,
+}
+
+impl Default for SeedsKeys {
+    fn default() -> Self {
+        Self {
+            run_pending: default_run_pending_key(),
+        }
+    }
+}
+
+fn default_run_pending_key() -> String {
+    "a".to_string()
+}
+
+/// Keys of the prompt shown for a job that timed out; `cancel_job` cancels it.
+#[derive(Deserialize, Debug)]
+pub struct TimeoutPromptKeys {
+    #[serde(default = "default_keep_waiting_key")]
+    pub keep_waiting: String,
+    #[serde(default = "default_force_kill_key")]
+    pub force_kill: String,
+}
+
+impl Default for TimeoutPromptKeys {
+    fn default() -> Self {
+        Self {
+            keep_waiting: default_keep_waiting_key(),
+            force_kill: default_force_kill_key(),
+        }
+    }
+}
+
+fn default_keep_waiting_key() -> String {
+    "w".to_string()
+}
+
+fn default_force_kill_key() -> String {
+    "f".to_string()
 }
 
 impl Keybinds {
@@ -39,3 +267,348 @@ impl Keybinds {
         toml::from_str(&content).expect("Failed to parse keybinds.toml")
     }
 }
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Settings {
+    pub stats: StatsSettings,
+    pub crash_reports: CrashReportSettings,
+    pub log_buffers: LogBufferSettings,
+    pub web: WebSettings,
+    pub timeouts: TimeoutSettings,
+    pub pull: PullSettings,
+    pub bundles: BundleSettings,
+    pub data: DataSettings,
+    pub backups: BackupSettings,
+    pub seeds: SeedSettings,
+    pub shared_network: SharedNetworkSettings,
+    pub projects: HashMap<String, ProjectSettings>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct LogBufferSettings {
+    pub max_lines: usize,
+    pub max_bytes: usize,
+}
+
+impl LogBufferSettings {
+    pub fn limits(&self) -> LogLimits {
+        LogLimits {
+            max_lines: self.max_lines,
+            max_bytes: self.max_bytes,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct CrashReportSettings {
+    pub log_lines: usize,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct ProjectSettings {
+    pub idle: Option<IdlePolicy>,
+    pub restart: Option<RestartPolicy>,
+    pub pull_policy: Option<PullPolicy>,
+    /// Per compose service overrides of `pull_policy`.
+    pub pull_policies: HashMap<String, PullPolicy>,
+    /// Attach every service to the shared network on start.
+    pub shared_network: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct RestartPolicy {
+    pub max_retries: usize,
+    pub backoff_secs: u64,
+    pub max_backoff_secs: u64,
+    pub window_secs: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct IdlePolicy {
+    pub minutes: u64,
+    pub action: IdleAction,
+    pub max_cpu_percent: f64,
+    pub max_net_bytes_per_sec: f64,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum IdleAction {
+    #[default]
+    Down,
+    Pause,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct StatsSettings {
+    pub enabled: bool,
+    pub history: usize,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct BundleSettings {
+    /// Where offline image bundles are written and looked for.
+    pub dir: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct DataSettings {
+    /// Image of the throwaway container that measures, archives and wipes
+    /// volumes. It needs `sh`, `du`, `find` and `tar`.
+    pub helper_image: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct BackupSettings {
+    /// Database dumps go to `<dir>/<project>/`.
+    pub dir: String,
+    /// Dumps kept per database service; older ones are deleted after each
+    /// dump. 0 keeps them all.
+    pub keep: usize,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct SeedSettings {
+    /// Run seeds that were never applied after a project starts.
+    pub auto: bool,
+    /// How long to wait for a database to accept connections before seeding.
+    pub ready_secs: u64,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct SharedNetworkSettings {
+    /// External network that projects with `shared_network = true` join, so
+    /// their containers reach each other by name.
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct PullSettings {
+    pub policy: PullPolicy,
+    /// Write `images.lock.json` after the first pull of a project.
+    pub lock: bool,
+    /// Warn on start when local images differ from the lock file.
+    pub verify_lock: bool,
+}
+
+/// When an image is pulled before a project starts.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PullPolicy {
+    Always,
+    #[default]
+    Missing,
+    Never,
+    Daily,
+    Weekly,
+}
+
+impl PullPolicy {
+    /// Maps a compose `pull_policy` value. `build` never pulls; `every_<duration>`
+    /// is not supported and falls back to the manager's settings.
+    pub fn from_compose(value: &str) -> Option<Self> {
+        match value {
+            "always" => Some(PullPolicy::Always),
+            "missing" | "if_not_present" => Some(PullPolicy::Missing),
+            "never" | "build" => Some(PullPolicy::Never),
+            "daily" => Some(PullPolicy::Daily),
+            "weekly" => Some(PullPolicy::Weekly),
+            _ => None,
+        }
+    }
+
+    /// How old a pull may get before the image is pulled again.
+    pub fn max_age(self) -> Option<Duration> {
+        match self {
+            PullPolicy::Daily => Some(Duration::from_secs(24 * 60 * 60)),
+            PullPolicy::Weekly => Some(Duration::from_secs(7 * 24 * 60 * 60)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PullPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PullPolicy::Always => write!(f, "always"),
+            PullPolicy::Missing => write!(f, "missing"),
+            PullPolicy::Never => write!(f, "never"),
+            PullPolicy::Daily => write!(f, "daily"),
+            PullPolicy::Weekly => write!(f, "weekly"),
+        }
+    }
+}
+
+/// Limits in seconds for each kind of operation; 0 disables a limit.
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct TimeoutSettings {
+    pub pull_secs: u64,
+    pub build_secs: u64,
+    pub up_secs: u64,
+    pub down_secs: u64,
+    /// Waiting for a database and running its seeds.
+    pub seed_secs: u64,
+    pub daemon_secs: u64,
+    /// How long a pull or build may go without printing anything.
+    pub stall_secs: u64,
+}
+
+impl TimeoutSettings {
+    pub fn limit(secs: u64) -> Option<Duration> {
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct WebSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for IdlePolicy {
+    fn default() -> Self {
+        Self {
+            minutes: 30,
+            action: IdleAction::Down,
+            max_cpu_percent: 2.0,
+            max_net_bytes_per_sec: 512.0,
+        }
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            backoff_secs: 2,
+            max_backoff_secs: 120,
+            window_secs: 600,
+        }
+    }
+}
+
+impl Default for StatsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            history: 60,
+        }
+    }
+}
+
+impl Default for LogBufferSettings {
+    fn default() -> Self {
+        Self {
+            max_lines: 20_000,
+            max_bytes: 8 * 1024 * 1024,
+        }
+    }
+}
+
+impl Default for CrashReportSettings {
+    fn default() -> Self {
+        Self { log_lines: 20 }
+    }
+}
+
+impl Default for WebSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 7878,
+        }
+    }
+}
+
+impl Default for BundleSettings {
+    fn default() -> Self {
+        Self {
+            dir: "bundles".to_string(),
+        }
+    }
+}
+
+impl Default for DataSettings {
+    fn default() -> Self {
+        Self {
+            helper_image: "busybox:stable".to_string(),
+        }
+    }
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            dir: "backups".to_string(),
+            keep: 7,
+        }
+    }
+}
+
+impl Default for SeedSettings {
+    fn default() -> Self {
+        Self {
+            auto: true,
+            ready_secs: 60,
+        }
+    }
+}
+
+impl Default for SharedNetworkSettings {
+    fn default() -> Self {
+        Self {
+            name: "docker-manager-shared".to_string(),
+        }
+    }
+}
+
+impl Default for PullSettings {
+    fn default() -> Self {
+        Self {
+            policy: PullPolicy::Missing,
+            lock: true,
+            verify_lock: true,
+        }
+    }
+}
+
+impl Default for TimeoutSettings {
+    fn default() -> Self {
+        Self {
+            pull_secs: 1800,
+            build_secs: 1800,
+            up_secs: 300,
+            down_secs: 180,
+            seed_secs: 600,
+            daemon_secs: 90,
+            stall_secs: 180,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let content = fs::read_to_string("settings.toml")
+            .unwrap_or_else(|_| include_str!("../settings.toml").to_string());
+        toml::from_str(&content).expect("Failed to parse settings.toml")
+    }
+
+    pub fn project(&self, name: &str) -> Option<&ProjectSettings> {
+        self.projects.get(name)
+    }
+}
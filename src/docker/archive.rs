@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// An archive found on disk, such as a bundle, a data snapshot or a
+/// database dump.
+pub struct ArchiveInfo {
+    pub path: PathBuf,
+    pub name: String,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// `.tar` files in `dir`, newest first.
+pub fn list(dir: &Path) -> Vec<ArchiveInfo> {
+    list_with_extensions(dir, &["tar"])
+}
+
+/// Files in `dir` with one of `extensions`, newest first.
+pub fn list_with_extensions(dir: &Path, extensions: &[&str]) -> Vec<ArchiveInfo> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut archives: Vec<ArchiveInfo> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| extensions.iter().any(|wanted| ext == *wanted))
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| ArchiveInfo {
+                path: entry.path(),
+                name: entry.file_name().to_string_lossy().into_owned(),
+                size: metadata.len(),
+                modified: metadata.modified().ok(),
+            })
+        })
+        .collect();
+    archives.sort_by_key(|archive| std::cmp::Reverse(archive.modified));
+    archives
+}
+
+/// UTC `YYYYMMDD-HHMMSS` for archive names, so they sort by time.
+pub fn timestamp(at: SystemTime) -> String {
+    let secs = at
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        rest / 3600,
+        (rest % 3600) / 60,
+        rest % 60
+    )
+}
@@ -0,0 +1,330 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Git metadata changes on every commit and fetch; it is skipped even without
+/// a `.dockerignore`. Anything else counts unless `.dockerignore` excludes it.
+const IGNORED_DIRS: [&str; 1] = [".git"];
+
+/// A build step parsed from `docker compose build` output, either the classic
+/// builder's `Step 3/10 : RUN ...` or BuildKit's plain `#7 [web 3/6] RUN ...`.
+#[derive(Clone, PartialEq)]
+pub struct BuildStep {
+    pub target: Option<String>,
+    pub current: usize,
+    pub total: usize,
+}
+
+impl BuildStep {
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("Step ") {
+            let (current, total) = step_ratio(rest.split_whitespace().next()?)?;
+            return Some(Self {
+                target: None,
+                current,
+                total,
+            });
+        }
+
+        let rest = line.strip_prefix('#')?;
+        let (_, rest) = rest.split_once(" [")?;
+        let (inside, _) = rest.split_once(']')?;
+        let (target, ratio) = match inside.rsplit_once(' ') {
+            Some((target, ratio)) => (Some(target.to_string()), ratio),
+            None => (None, inside),
+        };
+        let (current, total) = step_ratio(ratio)?;
+        Some(Self {
+            target,
+            current,
+            total,
+        })
+    }
+
+    /// Progress text for the job list and the progress bar; keeps an `NN%`
+    /// token like pull progress does.
+    pub fn summary(&self) -> String {
+        let percent = (self.current * 100) / self.total.max(1);
+        match &self.target {
+            Some(target) => format!(
+                "{}% {} step {}/{}",
+                percent, target, self.current, self.total
+            ),
+            None => format!("{}% step {}/{}", percent, self.current, self.total),
+        }
+    }
+}
+
+fn step_ratio(token: &str) -> Option<(usize, usize)> {
+    let (current, total) = token.split_once('/')?;
+    let current = current.parse().ok()?;
+    let total = total.parse().ok()?;
+    (total > 0 && current <= total).then_some((current, total))
+}
+
+/// Fingerprint of a build context from the path, size and modification time
+/// of every file in it, so edits since the last build can be spotted without
+/// hashing file contents.
+///
+/// Files matched by the context's `.dockerignore` never reach the builder
+/// and are skipped, as is everything under `excluded` (the manager's own
+/// state files and data directories kept next to the compose file).
+pub fn context_fingerprint(context: &Path, excluded: &[PathBuf]) -> Option<u64> {
+    let ignore = DockerIgnore::load(context);
+    let mut files = Vec::new();
+    collect_files(context, context, &ignore, excluded, &mut files);
+    if files.is_empty() && !context.is_dir() {
+        return None;
+    }
+    files.sort();
+
+    let mut hash = Fnv::new();
+    for path in files {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|at| at.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |at| at.as_nanos());
+        let relative = path.strip_prefix(context).unwrap_or(&path);
+        hash.write(relative.to_string_lossy().as_bytes());
+        hash.write(&metadata.len().to_le_bytes());
+        hash.write(&modified.to_le_bytes());
+    }
+    Some(hash.finish())
+}
+
+fn collect_files(
+    context: &Path,
+    dir: &Path,
+    ignore: &DockerIgnore,
+    excluded: &[PathBuf],
+    files: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if excluded.iter().any(|excluded| path.starts_with(excluded)) {
+            continue;
+        }
+        let relative = path.strip_prefix(context).unwrap_or(&path);
+        let relative = relative.to_string_lossy();
+        if file_type.is_dir() {
+            let ignored = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| IGNORED_DIRS.contains(&name))
+                || ignore.prunes(&relative);
+            if !ignored {
+                collect_files(context, &path, ignore, excluded, files);
+            }
+        } else if file_type.is_file() && !ignore.ignores(&relative) {
+            files.push(path);
+        }
+    }
+}
+
+/// The rules of a `.dockerignore`, applied the way the builder does: the
+/// last rule matching a path or one of its parent directories wins, and
+/// `!` rules re-include.
+#[derive(Default)]
+struct DockerIgnore {
+    rules: Vec<(bool, Vec<String>)>,
+}
+
+impl DockerIgnore {
+    fn load(context: &Path) -> Self {
+        fs::read_to_string(context.join(".dockerignore"))
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (negated, pattern) = match line.strip_prefix('!') {
+                    Some(pattern) => (true, pattern.trim()),
+                    None => (false, line),
+                };
+                let segments: Vec<String> = pattern
+                    .split('/')
+                    .filter(|segment| !segment.is_empty() && *segment != ".")
+                    .map(str::to_string)
+                    .collect();
+                (!segments.is_empty()).then_some((negated, segments))
+            })
+            .collect();
+        Self { rules }
+    }
+
+    fn ignores(&self, relative: &str) -> bool {
+        let path: Vec<&str> = relative.split('/').filter(|s| !s.is_empty()).collect();
+        let mut ignored = false;
+        for (negated, pattern) in &self.rules {
+            let matched = (1..=path.len()).any(|len| glob_segments(pattern, &path[..len]));
+            if matched {
+                ignored = !negated;
+            }
+        }
+        ignored
+    }
+
+    /// Whether a directory can be skipped whole; with `!` rules a file
+    /// below it might be re-included, so nothing is pruned then.
+    fn prunes(&self, relative: &str) -> bool {
+        self.rules.iter().all(|(negated, _)| !negated) && self.ignores(relative)
+    }
+}
+
+/// Matches path segments against pattern segments, where `**` spans any
+/// number of segments.
+fn glob_segments(pattern: &[String], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => {
+            (0..=path.len()).any(|skip| glob_segments(rest, &path[skip..]))
+        }
+        Some((first, rest)) => match path.split_first() {
+            Some((segment, path)) => glob(first, segment) && glob_segments(rest, path),
+            None => false,
+        },
+    }
+}
+
+/// `*` and `?` wildcards within one path segment.
+fn glob(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// FNV-1a; unlike `DefaultHasher` its output is stable across Rust releases,
+/// which matters because fingerprints are written to disk.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "docker-manager-build-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn dockerignore_rules_apply_to_files_and_parents() {
+        let ignore = DockerIgnore::parse("# comment\n*.log\n/db\n**/cache\n!keep.log\n");
+        assert!(ignore.ignores("debug.log"));
+        assert!(!ignore.ignores("keep.log"));
+        assert!(ignore.ignores("db/data/base"));
+        assert!(ignore.ignores("src/deep/cache/x"));
+        assert!(!ignore.ignores("src/main.rs"));
+        assert!(!ignore.ignores("nested/debug.log"));
+        // A `!` rule disables pruning of whole directories.
+        assert!(!ignore.prunes("db"));
+        assert!(DockerIgnore::parse("db/").prunes("db"));
+    }
+
+    #[test]
+    fn glob_handles_wildcards() {
+        assert!(glob("*.json", ".build-state.json"));
+        assert!(glob("a?c", "abc"));
+        assert!(glob("*", ""));
+        assert!(!glob("*.json", "state.json.bak"));
+        assert!(!glob("a?c", "ac"));
+    }
+
+    #[test]
+    fn fingerprint_skips_excluded_and_ignored_paths() {
+        let dir = context("fingerprint");
+        fs::write(dir.join("Dockerfile"), "FROM scratch\n").unwrap();
+        fs::write(dir.join(".dockerignore"), "*.tmp\n").unwrap();
+        fs::create_dir_all(dir.join("db")).unwrap();
+        let excluded = vec![dir.join("db"), dir.join(".build-state.json")];
+        let before = context_fingerprint(&dir, &excluded).unwrap();
+
+        fs::write(dir.join("db/pgdata"), "rows").unwrap();
+        fs::write(dir.join(".build-state.json"), "{}").unwrap();
+        fs::write(dir.join("scratch.tmp"), "x").unwrap();
+        assert_eq!(context_fingerprint(&dir, &excluded), Some(before));
+
+        fs::write(dir.join("app.py"), "print()").unwrap();
+        assert_ne!(context_fingerprint(&dir, &excluded), Some(before));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn build_output_dirs_count_unless_dockerignored() {
+        let dir = context("target");
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("Dockerfile"), "COPY target/app.jar /app.jar\n").unwrap();
+        fs::write(dir.join("target/app.jar"), "v1").unwrap();
+        let before = context_fingerprint(&dir, &[]).unwrap();
+
+        fs::write(dir.join("target/app.jar"), "v2 with more bytes").unwrap();
+        let after = context_fingerprint(&dir, &[]).unwrap();
+        assert_ne!(after, before);
+
+        fs::write(dir.join(".dockerignore"), "target\n").unwrap();
+        let ignored = context_fingerprint(&dir, &[]).unwrap();
+        fs::write(dir.join("target/app.jar"), "v3").unwrap();
+        assert_eq!(context_fingerprint(&dir, &[]), Some(ignored));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
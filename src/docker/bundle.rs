@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use crate::docker::process::run_capture;
+
+/// Names of the two files inside a bundle tarball.
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const IMAGES_FILE: &str = "images.tar";
+
+/// Describes what a bundle holds, so an import can check it got everything
+/// intact before anything is started from it.
+#[derive(Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub created_at: u64,
+    pub projects: Vec<String>,
+    pub images: Vec<BundleImage>,
+    /// `sha256sum` of the `docker save` archive inside the bundle.
+    pub images_sha256: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BundleImage {
+    pub image: String,
+    /// Image id, which survives `docker save` and `docker load`.
+    pub id: String,
+    /// Registry digest at export time; `docker load` does not restore it.
+    #[serde(default)]
+    pub digest: Option<String>,
+}
+
+pub fn bundle_name(projects: &[String]) -> String {
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or(0);
+    format!("{}-{}.tar", projects.join("+"), created_at)
+}
+
+pub fn save_cmd(images: &[String], output: &Path) -> Command {
+    let mut cmd = Command::new("docker");
+    cmd.arg("save").arg("-o").arg(output).args(images);
+    cmd
+}
+
+pub fn load_cmd(input: &Path) -> Command {
+    let mut cmd = Command::new("docker");
+    cmd.arg("load").arg("-i").arg(input);
+    cmd
+}
+
+/// Packs the manifest and image archive from `dir` into one tarball.
+pub fn pack_cmd(dir: &Path, bundle: &Path) -> Command {
+    let mut cmd = Command::new("tar");
+    cmd.arg("-cf")
+        .arg(bundle)
+        .arg("-C")
+        .arg(dir)
+        .arg(MANIFEST_FILE)
+        .arg(IMAGES_FILE);
+    cmd
+}
+
+pub fn unpack_cmd(bundle: &Path, dir: &Path) -> Command {
+    let mut cmd = Command::new("tar");
+    cmd.arg("-xf").arg(bundle).arg("-C").arg(dir);
+    cmd
+}
+
+pub async fn sha256(path: &Path) -> Result<String, String> {
+    let mut cmd = Command::new("sha256sum");
+    cmd.arg(path);
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(format!("sha256sum failed for {}", path.display()));
+    }
+    String::from_utf8_lossy(&out.stdout)
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+        .ok_or_else(|| format!("sha256sum printed nothing for {}", path.display()))
+}
+
+/// Scratch directory for packing or unpacking a bundle, removed when dropped
+/// so a cancelled job does not leave a multi-gigabyte archive behind.
+///
+/// It is a hidden directory inside `parent` rather than in the system temp
+/// dir, which is often a small tmpfs, and so that a finished bundle can be
+/// renamed into place without crossing filesystems.
+pub struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    pub async fn create(parent: &Path) -> Result<Self, String> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_nanos())
+            .unwrap_or(0);
+        let path = parent.join(format!(
+            ".docker-manager-bundle-{}-{}",
+            std::process::id(),
+            nanos
+        ));
+        tokio::fs::create_dir_all(&path)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Self(path))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    /// Removes the directory off the async workers when a runtime is around,
+    /// since it can hold a multi-gigabyte image archive.
+    fn drop(&mut self) {
+        let path = std::mem::take(&mut self.0);
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn_blocking(move || fs::remove_dir_all(path).ok());
+            }
+            Err(_) => {
+                fs::remove_dir_all(path).ok();
+            }
+        }
+    }
+}
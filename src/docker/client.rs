@@ -1,54 +1,110 @@
 use std::collections::HashMap;
-use std::process::Command;
+
+use tokio::process::Command;
 
 use crate::status::Status;
 
 pub struct DockerClient;
 
 impl DockerClient {
-    pub fn docker_info_ok() -> bool {
+    pub async fn docker_info_ok() -> bool {
         Command::new("docker")
             .arg("info")
+            .kill_on_drop(true)
             .output()
+            .await
             .map(|out| out.status.success())
             .unwrap_or(false)
     }
 
-    pub fn docker_cli_ok() -> bool {
+    pub async fn docker_cli_ok() -> bool {
         Command::new("docker")
             .arg("--version")
+            .kill_on_drop(true)
             .output()
+            .await
             .map(|out| out.status.success())
             .unwrap_or(false)
     }
 
-    pub fn compose_cli_ok() -> bool {
+    pub async fn compose_cli_ok() -> bool {
         Command::new("docker")
             .arg("compose")
             .arg("version")
+            .kill_on_drop(true)
             .output()
+            .await
             .map(|out| out.status.success())
             .unwrap_or(false)
     }
 
-    pub fn image_exists(image: &str) -> bool {
+    pub async fn image_exists(image: &str) -> bool {
         Command::new("docker")
             .arg("image")
             .arg("inspect")
             .arg(image)
+            .kill_on_drop(true)
             .output()
+            .await
             .map(|out| out.status.success())
             .unwrap_or(false)
     }
 
-    pub fn get_status(project: &str) -> Status {
+    pub async fn image_id(image: &str) -> Option<String> {
+        let out = Command::new("docker")
+            .arg("image")
+            .arg("inspect")
+            .arg("--format")
+            .arg("{{.Id}}")
+            .arg(image)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .ok()?;
+        let id = String::from_utf8_lossy(&out.stdout).trim().to_string();
+        (out.status.success() && !id.is_empty()).then_some(id)
+    }
+
+    /// Registry digest of a local image, or its image id when it was never
+    /// pulled from a registry.
+    pub async fn image_digest(image: &str) -> Option<String> {
+        let out = Command::new("docker")
+            .arg("image")
+            .arg("inspect")
+            .arg("--format")
+            .arg("{{if .RepoDigests}}{{index .RepoDigests 0}}{{else}}{{.Id}}{{end}}")
+            .arg(image)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .ok()?;
+        if !out.status.success() {
+            return None;
+        }
+
+        let value = String::from_utf8_lossy(&out.stdout).trim().to_string();
+        let digest = value
+            .rsplit_once('@')
+            .map_or(value.as_str(), |(_, digest)| digest);
+        (!digest.is_empty()).then(|| digest.to_string())
+    }
+
+    pub fn start_containers_cmd(containers: &[String]) -> Command {
+        let mut cmd = Command::new("docker");
+        cmd.arg("start").args(containers);
+        cmd
+    }
+
+    pub async fn get_status(project: &str) -> Status {
         match Command::new("docker")
             .arg("ps")
             .arg("--filter")
             .arg(format!("label=com.docker.compose.project={}", project))
             .arg("--format")
             .arg("{{.Names}}\t{{.Status}}")
+            .kill_on_drop(true)
             .output()
+            .await
         {
             Ok(out) => {
                 let stdout = String::from_utf8_lossy(&out.stdout);
@@ -56,24 +112,15 @@ impl DockerClient {
                 if lines.is_empty() {
                     Status::Stopped
                 } else {
-                    let has_running = lines.iter().any(|line| {
-                        line.split('\t')
-                            .nth(1)
-                            .map(|status| status.starts_with("Up"))
-                            .unwrap_or(false)
-                    });
-                    if has_running {
-                        Status::Running
-                    } else {
-                        Status::Stopped
-                    }
+                    let statuses = lines.iter().filter_map(|line| line.split('\t').nth(1));
+                    fold_container_statuses(statuses)
                 }
             }
             Err(_) => Status::Error,
         }
     }
 
-    pub fn get_batch_statuses(service_names: &[String]) -> HashMap<String, Status> {
+    pub async fn get_batch_statuses(service_names: &[String]) -> HashMap<String, Status> {
         let mut statuses = HashMap::new();
 
         for name in service_names {
@@ -92,7 +139,9 @@ impl DockerClient {
             .arg("ps")
             .arg("--format")
             .arg("{{.Names}}\t{{.Status}}\t{{.Label \"com.docker.compose.project\"}}")
-            .output();
+            .kill_on_drop(true)
+            .output()
+            .await;
 
         match cmd {
             Ok(out) => {
@@ -104,12 +153,14 @@ impl DockerClient {
                         let project_name = parts[2];
 
                         if service_names.contains(&project_name.to_string()) {
-                            let status = if status_str.starts_with("Up") {
-                                Status::Running
-                            } else {
-                                Status::Stopped
+                            let status = container_status(status_str);
+                            // A single running container makes the whole project running.
+                            let merged = match statuses.get(project_name) {
+                                Some(Status::Running) => Status::Running,
+                                Some(Status::Paused) if status == Status::Stopped => Status::Paused,
+                                _ => status,
                             };
-                            statuses.insert(project_name.to_string(), status);
+                            statuses.insert(project_name.to_string(), merged);
                         }
                     }
                 }
@@ -124,7 +175,7 @@ impl DockerClient {
         statuses
     }
 
-    pub fn all_containers_stopped(project: &str) -> bool {
+    pub async fn all_containers_stopped(project: &str) -> bool {
         match Command::new("docker")
             .arg("ps")
             .arg("-a")
@@ -132,7 +183,9 @@ impl DockerClient {
             .arg(format!("label=com.docker.compose.project={}", project))
             .arg("--format")
             .arg("{{.Status}}")
+            .kill_on_drop(true)
             .output()
+            .await
         {
             Ok(out) if out.status.success() => {
                 let stdout = String::from_utf8_lossy(&out.stdout);
@@ -157,6 +210,28 @@ impl DockerClient {
     }
 }
 
+fn container_status(status: &str) -> Status {
+    if !status.starts_with("Up") {
+        Status::Stopped
+    } else if status.contains("(Paused)") {
+        Status::Paused
+    } else {
+        Status::Running
+    }
+}
+
+fn fold_container_statuses<'a>(statuses: impl Iterator<Item = &'a str>) -> Status {
+    let mut folded = Status::Stopped;
+    for status in statuses {
+        match container_status(status) {
+            Status::Running => return Status::Running,
+            Status::Paused => folded = Status::Paused,
+            _ => {}
+        }
+    }
+    folded
+}
+
 fn validate_service_name(name: &str) -> bool {
     name.chars()
         .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
@@ -1,7 +1,62 @@
-use std::process::{Child, Command, Output, Stdio};
+use std::path::Path;
+use std::process::{Output, Stdio};
 
+use tokio::process::{Child, Command};
+use tokio::sync::OnceCell;
+
+use crate::docker::overrides;
 use crate::docker::process::run_capture;
 
+static JSON_PROGRESS: OnceCell<bool> = OnceCell::const_new();
+
+/// `--progress json` only exists in newer Compose releases; probed once.
+pub async fn supports_json_progress() -> bool {
+    *JSON_PROGRESS
+        .get_or_init(|| async {
+            let mut cmd = Command::new("docker");
+            cmd.args(["compose", "--progress", "json", "version"]);
+            run_capture(cmd).await.is_ok_and(|out| out.status.success())
+        })
+        .await
+}
+
+static VERSION: OnceCell<Option<ComposeVersion>> = OnceCell::const_new();
+
+/// `major.minor.patch` of the installed Compose plugin.
+pub type ComposeVersion = (u32, u32, u32);
+
+/// First Compose release that understands the `!override` YAML tag.
+pub const OVERRIDE_TAG_VERSION: ComposeVersion = (2, 24, 4);
+
+/// Version of the installed Compose plugin, or `None` if it cannot be
+/// determined; probed once.
+pub async fn compose_version() -> Option<ComposeVersion> {
+    *VERSION
+        .get_or_init(|| async {
+            let mut cmd = Command::new("docker");
+            cmd.args(["compose", "version", "--short"]);
+            let out = run_capture(cmd).await.ok()?;
+            if !out.status.success() {
+                return None;
+            }
+            parse_version(&String::from_utf8_lossy(&out.stdout))
+        })
+        .await
+}
+
+/// Parses `2.24.4`, `v2.24.4` or `2.24.4-desktop.1`.
+pub fn parse_version(text: &str) -> Option<ComposeVersion> {
+    let text = text.trim().trim_start_matches('v');
+    let mut parts = text.split('.').map(|part| {
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse::<u32>().ok()
+    });
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
 #[derive(Clone)]
 pub struct ComposeProject {
     pub dir: String,
@@ -14,21 +69,64 @@ impl ComposeProject {
         Self { dir }
     }
 
+    /// `docker compose` in the project directory, with the manager's
+    /// generated overrides applied when there are any.
     pub fn command(&self) -> Command {
         let mut cmd = Command::new("docker");
         cmd.arg("compose").current_dir(&self.dir);
+        if let Some(files) = overrides::compose_files(Path::new(&self.dir)) {
+            cmd.env("COMPOSE_PATH_SEPARATOR", ":")
+                .env("COMPOSE_FILE", files);
+        }
         cmd
     }
 
-    pub fn pull_cmd(&self) -> Command {
+    /// `json_progress` switches to machine-readable per-layer progress, see
+    /// [`supports_json_progress`].
+    /// An empty `services` list pulls every service in the project.
+    pub fn pull_cmd(&self, json_progress: bool, services: &[String]) -> Command {
         let mut cmd = self.command();
-        cmd.arg("pull");
+        if json_progress {
+            cmd.arg("--progress").arg("json");
+        }
+        cmd.arg("pull").args(services);
         cmd
     }
 
-    pub fn up_detached_cmd(&self) -> Command {
+    /// Plain BuildKit output, so steps show up as `#N [service i/n]` lines.
+    /// An empty `services` list builds every service with a `build:` section.
+    pub fn build_cmd(&self, no_cache: bool, services: &[String]) -> Command {
+        let mut cmd = self.command();
+        cmd.env("BUILDKIT_PROGRESS", "plain").arg("build");
+        if no_cache {
+            cmd.arg("--no-cache");
+        }
+        cmd.args(services);
+        cmd
+    }
+
+    /// The fully interpolated project model as JSON.
+    pub fn config_cmd(&self) -> Command {
+        let mut cmd = self.command();
+        cmd.arg("config").arg("--format").arg("json");
+        cmd
+    }
+
+    /// Runs a shell script read from stdin inside `service`.
+    pub fn exec_shell_cmd(&self, service: &str) -> Command {
+        let mut cmd = self.command();
+        cmd.arg("exec").arg("-T").arg(service).args(["sh", "-s"]);
+        cmd
+    }
+
+    /// `pull_never` leaves pulling to the caller, so `up` does not pull again
+    /// images the pull policies skipped.
+    pub fn up_detached_cmd(&self, pull_never: bool) -> Command {
         let mut cmd = self.command();
         cmd.arg("up").arg("-d");
+        if pull_never {
+            cmd.args(["--pull", "never"]);
+        }
         cmd
     }
 
@@ -38,10 +136,28 @@ impl ComposeProject {
         cmd
     }
 
-    pub fn ps_output(&self) -> std::io::Result<Output> {
+    pub fn kill_cmd(&self) -> Command {
+        let mut cmd = self.command();
+        cmd.arg("kill");
+        cmd
+    }
+
+    pub fn pause_cmd(&self) -> Command {
+        let mut cmd = self.command();
+        cmd.arg("pause");
+        cmd
+    }
+
+    pub fn unpause_cmd(&self) -> Command {
+        let mut cmd = self.command();
+        cmd.arg("unpause");
+        cmd
+    }
+
+    pub async fn ps_output(&self) -> std::io::Result<Output> {
         let mut cmd = self.command();
         cmd.arg("ps");
-        run_capture(cmd)
+        run_capture(cmd).await
     }
 
     pub fn logs_follow(&self) -> std::io::Result<Child> {
@@ -50,7 +166,44 @@ impl ComposeProject {
             .arg("-f")
             .arg("--tail=100")
             .stdout(Stdio::piped())
-            .stderr(Stdio::null());
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
         cmd.spawn()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_accepts_release_and_vendor_forms() {
+        assert_eq!(parse_version("2.24.4\n"), Some((2, 24, 4)));
+        assert_eq!(parse_version("v2.29.1"), Some((2, 29, 1)));
+        assert_eq!(parse_version("2.24.4-desktop.1"), Some((2, 24, 4)));
+        assert_eq!(parse_version("2.10"), Some((2, 10, 0)));
+        assert_eq!(parse_version(""), None);
+        assert_eq!(parse_version("unknown"), None);
+        assert!(parse_version("2.24.3").unwrap() < OVERRIDE_TAG_VERSION);
+        assert!(parse_version("2.24.4").unwrap() >= OVERRIDE_TAG_VERSION);
+    }
+
+    #[test]
+    fn up_skips_pulling_when_asked() {
+        let project = ComposeProject::new("no-such-project");
+        let args = |cmd: Command| {
+            cmd.as_std()
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            args(project.up_detached_cmd(true)),
+            ["compose", "up", "-d", "--pull", "never"]
+        );
+        assert_eq!(
+            args(project.up_detached_cmd(false)),
+            ["compose", "up", "-d"]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::docker::compose::ComposeProject;
+use crate::docker::process::run_capture;
+
+/// The parts of a project's `docker-compose.yml` the manager acts on.
+#[derive(Deserialize, Default)]
+pub struct ComposeFile {
+    #[serde(default)]
+    pub services: BTreeMap<String, ComposeService>,
+    /// Top-level named volumes; an entry with no options parses as `None`.
+    #[serde(default)]
+    pub volumes: BTreeMap<String, Option<ComposeVolume>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct ComposeService {
+    pub image: Option<String>,
+    pub build: Option<serde_yaml::Value>,
+    pub pull_policy: Option<String>,
+    /// Short (`src:dst[:mode]`) or long syntax entries, kept raw.
+    pub volumes: Vec<serde_yaml::Value>,
+    /// Map or `KEY=value` list form, kept raw; see [`ComposeService::env`].
+    pub environment: serde_yaml::Value,
+    /// List or map form, kept raw; null when the service sets none.
+    pub networks: serde_yaml::Value,
+    pub network_mode: Option<String>,
+    /// Short (`[ip:]host:container[/proto]`) or long syntax entries, kept raw.
+    pub ports: Vec<serde_yaml::Value>,
+    pub container_name: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct ComposeVolume {
+    pub name: Option<String>,
+    /// `true`, or the legacy `{ name: ... }` form.
+    pub external: Option<serde_yaml::Value>,
+}
+
+impl ComposeService {
+    /// Environment variables; entries without a value are left out.
+    pub fn env(&self) -> BTreeMap<String, String> {
+        match &self.environment {
+            serde_yaml::Value::Mapping(map) => map
+                .iter()
+                .filter_map(|(key, value)| {
+                    let value = match value {
+                        serde_yaml::Value::String(value) => value.clone(),
+                        serde_yaml::Value::Number(value) => value.to_string(),
+                        serde_yaml::Value::Bool(value) => value.to_string(),
+                        _ => return None,
+                    };
+                    Some((key.as_str()?.to_string(), value))
+                })
+                .collect(),
+            serde_yaml::Value::Sequence(entries) => entries
+                .iter()
+                .filter_map(|entry| entry.as_str()?.split_once('='))
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            _ => BTreeMap::new(),
+        }
+    }
+}
+
+impl ComposeFile {
+    pub fn path(project: &str) -> String {
+        format!("containers/{}/docker-compose.yml", project)
+    }
+
+    pub async fn load(project: &str) -> Option<Self> {
+        let content = tokio::fs::read_to_string(Self::path(project)).await.ok()?;
+        serde_yaml::from_str(&content).ok()
+    }
+
+    /// The project as Compose resolves it, with `.env` interpolation and
+    /// `env_file` entries merged into each service's environment.
+    pub async fn resolved(project: &str) -> Option<Self> {
+        let out = run_capture(ComposeProject::new(project).config_cmd())
+            .await
+            .ok()?;
+        if !out.status.success() {
+            return None;
+        }
+        serde_yaml::from_slice(&out.stdout).ok()
+    }
+
+    /// Services that run a registry image rather than building one.
+    pub fn pulled_images(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.services
+            .iter()
+            .filter(|(_, service)| service.build.is_none())
+            .filter_map(|(name, service)| service.image.as_ref().map(|image| (name, image)))
+    }
+
+    /// Every image the project runs, including the ones compose builds; those
+    /// are named `<project>-<service>` unless the service sets `image:`.
+    pub fn images(&self, project: &str) -> Vec<String> {
+        self.services
+            .iter()
+            .map(|(name, service)| {
+                service
+                    .image
+                    .clone()
+                    .unwrap_or_else(|| format!("{}-{}", project, name))
+            })
+            .collect()
+    }
+
+    /// Services built from a local context, with the context directory
+    /// resolved against the project directory.
+    pub fn built_services(&self, project: &str) -> Vec<(String, PathBuf)> {
+        let project_dir = Path::new("containers").join(project);
+        self.services
+            .iter()
+            .filter_map(|(name, service)| {
+                let context = match service.build.as_ref()? {
+                    serde_yaml::Value::String(context) => context.as_str(),
+                    build => build
+                        .get("context")
+                        .and_then(|context| context.as_str())
+                        .unwrap_or("."),
+                };
+                Some((name.clone(), project_dir.join(context)))
+            })
+            .collect()
+    }
+}
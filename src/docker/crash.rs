@@ -0,0 +1,65 @@
+use chrono::Local;
+use tokio::process::Command;
+
+/// Everything worth knowing about a container that died unexpectedly,
+/// captured right after the `die` event while its logs are still around.
+#[derive(Clone)]
+pub struct CrashReport {
+    pub container: String,
+    pub exit_code: String,
+    pub oom_killed: bool,
+    pub captured_at: String,
+    pub log_tail: Vec<String>,
+}
+
+pub async fn capture_crash_report(
+    container: &str,
+    exit_code: &str,
+    log_lines: usize,
+) -> CrashReport {
+    let oom_killed = Command::new("docker")
+        .arg("inspect")
+        .arg("--format")
+        .arg("{{.State.OOMKilled}}")
+        .arg(container)
+        .output()
+        .await
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim() == "true")
+        .unwrap_or(false);
+
+    // `docker logs` works on exited containers. Both streams are captured with
+    // timestamps so they can be merged back into their original order.
+    let log_tail = Command::new("docker")
+        .arg("logs")
+        .arg("--timestamps")
+        .arg("--tail")
+        .arg(log_lines.to_string())
+        .arg(container)
+        .output()
+        .await
+        .map(|out| {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            let mut lines: Vec<&str> = stdout.lines().chain(stderr.lines()).collect();
+            lines.sort_by_key(|line| line.split_once(' ').map(|(ts, _)| ts).unwrap_or(""));
+            lines
+                .iter()
+                .skip(lines.len().saturating_sub(log_lines))
+                .map(|line| {
+                    line.split_once(' ')
+                        .map(|(_, message)| message)
+                        .unwrap_or(line)
+                        .to_string()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    CrashReport {
+        container: container.to_string(),
+        exit_code: exit_code.to_string(),
+        oom_killed,
+        captured_at: Local::now().format("%H:%M:%S").to_string(),
+        log_tail,
+    }
+}
@@ -1,33 +1,35 @@
-use std::io::{Read, Write};
-use std::process::{Command, Stdio};
-use std::thread;
+use std::process::Stdio;
 use std::time::Duration;
 
-pub fn docker_service_active() -> bool {
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::process::Command;
+
+pub async fn docker_service_active() -> bool {
     Command::new("systemctl")
         .arg("is-active")
         .arg("docker.service")
         .output()
+        .await
         .map(|out| out.status.success())
         .unwrap_or(false)
 }
 
-pub fn start(password: &str) -> Result<(), String> {
-    run_systemctl(password, "start", &["docker.service", "docker.socket"])?;
-    ensure_daemon_state(true, "start")
+pub async fn start(password: &str) -> Result<(), String> {
+    run_systemctl(password, "start", &["docker.service", "docker.socket"]).await?;
+    ensure_daemon_state(true, "start").await
 }
 
-pub fn stop(password: &str) -> Result<(), String> {
-    run_systemctl(password, "stop", &["docker.service", "docker.socket"])?;
-    ensure_daemon_state(false, "stop")
+pub async fn stop(password: &str) -> Result<(), String> {
+    run_systemctl(password, "stop", &["docker.service", "docker.socket"]).await?;
+    ensure_daemon_state(false, "stop").await
 }
 
-pub fn restart(password: &str) -> Result<(), String> {
-    run_systemctl(password, "restart", &["docker.service", "docker.socket"])?;
-    ensure_daemon_state(true, "restart")
+pub async fn restart(password: &str) -> Result<(), String> {
+    run_systemctl(password, "restart", &["docker.service", "docker.socket"]).await?;
+    ensure_daemon_state(true, "restart").await
 }
 
-fn run_systemctl(password: &str, action: &str, units: &[&str]) -> Result<(), String> {
+async fn run_systemctl(password: &str, action: &str, units: &[&str]) -> Result<(), String> {
     let mut cmd = Command::new("sudo");
     cmd.arg("-S")
         .arg("-p")
@@ -37,21 +39,25 @@ fn run_systemctl(password: &str, action: &str, units: &[&str]) -> Result<(), Str
         .args(units)
         .stdin(Stdio::piped())
         .stdout(Stdio::null())
-        .stderr(Stdio::piped());
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
 
     let mut child = cmd.spawn().map_err(|e| e.to_string())?;
-    if let Some(stdin) = child.stdin.as_mut() {
-        writeln!(stdin, "{}", password).ok();
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(format!("{}\n", password).as_bytes())
+            .await
+            .ok();
     }
 
     let mut stderr = child.stderr.take();
 
-    match child.wait() {
+    match child.wait().await {
         Ok(status) if status.success() => Ok(()),
         _ => {
             let error_msg = if let Some(stderr) = stderr.as_mut() {
                 let mut buf = String::new();
-                stderr.read_to_string(&mut buf).ok();
+                stderr.read_to_string(&mut buf).await.ok();
                 if buf.trim().is_empty() {
                     format!("Failed to {} Docker daemon", action)
                 } else {
@@ -65,15 +71,15 @@ fn run_systemctl(password: &str, action: &str, units: &[&str]) -> Result<(), Str
     }
 }
 
-fn ensure_daemon_state(expected_active: bool, action: &str) -> Result<(), String> {
+async fn ensure_daemon_state(expected_active: bool, action: &str) -> Result<(), String> {
     const MAX_RETRIES: usize = 20;
     const RETRY_DELAY_MS: u64 = 100;
 
     for _ in 0..MAX_RETRIES {
-        if docker_service_active() == expected_active {
+        if docker_service_active().await == expected_active {
             return Ok(());
         }
-        thread::sleep(Duration::from_millis(RETRY_DELAY_MS));
+        tokio::time::sleep(Duration::from_millis(RETRY_DELAY_MS)).await;
     }
 
     let expectation = if expected_active {
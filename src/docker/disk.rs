@@ -0,0 +1,556 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+use tokio::process::Command;
+
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::images;
+use crate::docker::process::run_capture;
+use crate::docker::units::parse_size_to_bytes;
+use crate::docker::volumes::{self, MountSource};
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum UsageKind {
+    Image,
+    Container,
+    Volume,
+    BuildCache,
+}
+
+impl UsageKind {
+    pub const ALL: [UsageKind; 4] = [
+        UsageKind::Image,
+        UsageKind::Container,
+        UsageKind::Volume,
+        UsageKind::BuildCache,
+    ];
+
+    /// Containers go first so the images and volumes only they used can go too.
+    const REMOVAL_ORDER: [UsageKind; 4] = [
+        UsageKind::Container,
+        UsageKind::Image,
+        UsageKind::Volume,
+        UsageKind::BuildCache,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            UsageKind::Image => "Images",
+            UsageKind::Container => "Containers",
+            UsageKind::Volume => "Volumes",
+            UsageKind::BuildCache => "Build cache",
+        }
+    }
+
+    /// The `Type` column of `docker system df`.
+    fn df_type(self) -> &'static str {
+        match self {
+            UsageKind::Image => "Images",
+            UsageKind::Container => "Containers",
+            UsageKind::Volume => "Local Volumes",
+            UsageKind::BuildCache => "Build Cache",
+        }
+    }
+}
+
+/// One row of `docker system df`.
+#[derive(Clone, Copy, Default)]
+pub struct UsageTotal {
+    pub count: usize,
+    pub active: usize,
+    pub size: u64,
+    pub reclaimable: u64,
+}
+
+/// One image, container, volume or build cache record of `docker system df -v`.
+#[derive(Clone)]
+pub struct UsageItem {
+    pub kind: UsageKind,
+    /// What removal commands take: image or container id, volume name, cache id.
+    pub id: String,
+    pub name: String,
+    /// Bytes removing it frees; for images, the layers no other image shares.
+    pub size: u64,
+    /// Managed project it belongs to or, for images, the first one using it.
+    pub project: Option<String>,
+    /// Used by a container (or, for build cache, by a running build).
+    pub in_use: bool,
+    /// Stopped container.
+    pub stopped: bool,
+    /// Volume of a managed project, kept by prune unless asked otherwise.
+    pub protected: bool,
+    /// For containers, the image they run as Docker lists it: a reference
+    /// or an image id.
+    pub image: String,
+}
+
+impl UsageItem {
+    pub fn is_dangling(&self) -> bool {
+        self.kind == UsageKind::Image && self.name.starts_with("<none>")
+    }
+
+    /// Whether this container runs `image`.
+    fn runs(&self, image: &UsageItem) -> bool {
+        let id = |value: &str| value.trim_start_matches("sha256:").to_string();
+        self.kind == UsageKind::Container
+            && !self.image.is_empty()
+            && ((!image.is_dangling() && images::same_reference(&self.image, &image.name))
+                || (!image.id.is_empty()
+                    && (id(&image.id).starts_with(&id(&self.image))
+                        || id(&self.image).starts_with(&id(&image.id)))))
+    }
+
+    /// What `docker image rm` takes: the reference of a tagged image, so
+    /// removing one tag leaves the others, and the id of a dangling one.
+    fn removal_ref(&self) -> &str {
+        if self.kind == UsageKind::Image && !self.is_dangling() {
+            &self.name
+        } else {
+            &self.id
+        }
+    }
+}
+
+pub struct DiskUsage {
+    pub totals: Vec<(UsageKind, UsageTotal)>,
+    pub items: Vec<UsageItem>,
+}
+
+impl DiskUsage {
+    pub fn total(&self, kind: UsageKind) -> UsageTotal {
+        self.totals
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, total)| *total)
+            .unwrap_or_default()
+    }
+
+    pub fn size(&self) -> u64 {
+        self.totals.iter().map(|(_, total)| total.size).sum()
+    }
+}
+
+/// What a guided prune removes besides stopped containers, dangling images
+/// and unused build cache.
+#[derive(Clone, Copy, Default)]
+pub struct PruneOptions {
+    /// Tagged images no container and no managed project uses.
+    pub unused_images: bool,
+    /// Unused volumes of managed projects.
+    pub managed_volumes: bool,
+}
+
+/// Every item a prune with `options` would remove, in removal order. Images
+/// used only by stopped containers count as unused, since those containers go
+/// first.
+pub fn prune_plan(usage: &DiskUsage, options: PruneOptions) -> Vec<UsageItem> {
+    let containers: Vec<&UsageItem> = usage
+        .items
+        .iter()
+        .filter(|item| item.kind == UsageKind::Container)
+        .collect();
+    let image_in_use = |image: &UsageItem| {
+        image.in_use
+            && (containers
+                .iter()
+                .any(|container| !container.stopped && container.runs(image))
+                || !containers.iter().any(|container| container.runs(image)))
+    };
+
+    let mut plan: Vec<UsageItem> = Vec::new();
+    for kind in UsageKind::REMOVAL_ORDER {
+        plan.extend(
+            usage
+                .items
+                .iter()
+                .filter(|item| item.kind == kind)
+                .filter(|item| match kind {
+                    UsageKind::Container => item.stopped,
+                    UsageKind::Image => {
+                        !image_in_use(item)
+                            && (item.is_dangling()
+                                || (options.unused_images && item.project.is_none()))
+                    }
+                    UsageKind::Volume => {
+                        !item.in_use && (!item.protected || options.managed_volumes)
+                    }
+                    UsageKind::BuildCache => !item.in_use,
+                })
+                .cloned(),
+        );
+    }
+    plan
+}
+
+/// Disk usage attributed to the managed `projects`.
+pub async fn usage(projects: &[String]) -> Result<DiskUsage, String> {
+    let totals = totals().await?;
+    let verbose = df(&["--verbose"]).await?;
+    let verbose: Map<String, Value> = serde_json::from_str(verbose.trim())
+        .map_err(|e| format!("cannot read docker system df: {}", e))?;
+
+    let mut references = Vec::new();
+    let mut managed_volumes = BTreeMap::new();
+    for project in projects {
+        if let Some(compose) = ComposeFile::load(project).await {
+            for image in compose.images(project) {
+                references.push((project.clone(), image));
+            }
+            for mount in volumes::data_mounts(project, &compose) {
+                if let MountSource::Volume(name) = mount.source {
+                    managed_volumes.insert(name, project.clone());
+                }
+            }
+        }
+    }
+    let managed = |labels: &str| {
+        label(labels, PROJECT_LABEL).filter(|project| projects.iter().any(|p| p == project))
+    };
+
+    let mut items = Vec::new();
+    for row in rows(&verbose, "Images") {
+        let name = format!("{}:{}", text(row, "Repository"), text(row, "Tag"));
+        let project = references
+            .iter()
+            .find(|(_, reference)| images::same_reference(&name, reference))
+            .map(|(project, _)| project.clone());
+        items.push(UsageItem {
+            kind: UsageKind::Image,
+            id: text(row, "ID"),
+            size: if row.contains_key("UniqueSize") {
+                size(row, "UniqueSize")
+            } else {
+                size(row, "Size")
+            },
+            name,
+            project,
+            in_use: count(row, "Containers") > 0,
+            stopped: false,
+            protected: false,
+            image: String::new(),
+        });
+    }
+    for row in rows(&verbose, "Containers") {
+        let state = text(row, "State");
+        items.push(UsageItem {
+            kind: UsageKind::Container,
+            id: text(row, "ID"),
+            name: text(row, "Names"),
+            size: size(row, "Size"),
+            project: managed(&text(row, "Labels")),
+            in_use: state == "running" || state == "paused" || state == "restarting",
+            stopped: matches!(state.as_str(), "exited" | "created" | "dead"),
+            protected: false,
+            image: text(row, "Image"),
+        });
+    }
+    for row in rows(&verbose, "Volumes") {
+        let name = text(row, "Name");
+        let project = managed(&text(row, "Labels")).or_else(|| managed_volumes.get(&name).cloned());
+        items.push(UsageItem {
+            kind: UsageKind::Volume,
+            id: name.clone(),
+            size: size(row, "Size"),
+            protected: project.is_some(),
+            project,
+            in_use: count(row, "Links") > 0,
+            stopped: false,
+            image: String::new(),
+            name,
+        });
+    }
+    for row in rows(&verbose, "BuildCache") {
+        let description = text(row, "Description");
+        items.push(UsageItem {
+            kind: UsageKind::BuildCache,
+            id: text(row, "ID"),
+            name: if description.is_empty() {
+                text(row, "CacheType")
+            } else {
+                description
+            },
+            size: size(row, "Size"),
+            project: None,
+            in_use: flag(row, "InUse"),
+            stopped: false,
+            protected: false,
+            image: String::new(),
+        });
+    }
+    items.sort_by(|a, b| {
+        (a.kind as u8)
+            .cmp(&(b.kind as u8))
+            .then_with(|| b.size.cmp(&a.size))
+    });
+
+    Ok(DiskUsage { totals, items })
+}
+
+/// The summary rows of `docker system df`.
+pub async fn totals() -> Result<Vec<(UsageKind, UsageTotal)>, String> {
+    let summary = df(&[]).await?;
+    let rows: Vec<Map<String, Value>> = summary
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    Ok(UsageKind::ALL
+        .into_iter()
+        .map(|kind| {
+            let total = rows
+                .iter()
+                .find(|row| text(row, "Type") == kind.df_type())
+                .map(|row| UsageTotal {
+                    count: count(row, "TotalCount"),
+                    active: count(row, "Active"),
+                    size: size(row, "Size"),
+                    reclaimable: size(row, "Reclaimable"),
+                })
+                .unwrap_or_default();
+            (kind, total)
+        })
+        .collect())
+}
+
+async fn df(args: &[&str]) -> Result<String, String> {
+    let mut cmd = Command::new("docker");
+    cmd.args(["system", "df", "--format", "json"]).args(args);
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// Commands removing `plan`, one per kind present in it, containers first.
+pub fn remove_cmds(plan: &[UsageItem]) -> Vec<(UsageKind, Command)> {
+    let mut cmds = Vec::new();
+    for kind in UsageKind::REMOVAL_ORDER {
+        let mut ids: Vec<&str> = Vec::new();
+        for item in plan.iter().filter(|item| item.kind == kind) {
+            if !ids.contains(&item.removal_ref()) {
+                ids.push(item.removal_ref());
+            }
+        }
+        if ids.is_empty() {
+            continue;
+        }
+        let mut cmd = Command::new("docker");
+        match kind {
+            UsageKind::Container => cmd.args(["container", "rm"]).args(&ids),
+            UsageKind::Image => cmd.args(["image", "rm"]).args(&ids),
+            UsageKind::Volume => cmd.args(["volume", "rm"]).args(&ids),
+            // Build cache records cannot be removed one by one; the plan holds
+            // exactly the records `--all` removes.
+            UsageKind::BuildCache => cmd.args(["builder", "prune", "--all", "--force"]),
+        };
+        cmds.push((kind, cmd));
+    }
+    cmds
+}
+
+fn rows<'a>(
+    verbose: &'a Map<String, Value>,
+    key: &str,
+) -> impl Iterator<Item = &'a Map<String, Value>> {
+    verbose
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+}
+
+/// A field as text; Docker formats most of them as strings, some as numbers.
+fn text(row: &Map<String, Value>, key: &str) -> String {
+    match row.get(key) {
+        Some(Value::String(value)) => value.clone(),
+        Some(Value::Null) | None => String::new(),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn size(row: &Map<String, Value>, key: &str) -> u64 {
+    let value = text(row, key);
+    // Container sizes read "12kB (virtual 80MB)"; reclaimable ones "1GB (40%)".
+    let value = value.split_whitespace().next().unwrap_or_default();
+    parse_size_to_bytes(value).map_or(0, |bytes| bytes as u64)
+}
+
+fn count(row: &Map<String, Value>, key: &str) -> usize {
+    text(row, key).trim().parse().unwrap_or(0)
+}
+
+fn flag(row: &Map<String, Value>, key: &str) -> bool {
+    matches!(text(row, key).as_str(), "true" | "*")
+}
+
+/// Value of `key` in Docker's comma-separated `key=value` label list.
+fn label(labels: &str, key: &str) -> Option<String> {
+    labels
+        .split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, value)| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: UsageKind, name: &str) -> UsageItem {
+        UsageItem {
+            kind,
+            id: name.to_string(),
+            name: name.to_string(),
+            size: 1,
+            project: None,
+            in_use: false,
+            stopped: false,
+            protected: false,
+            image: String::new(),
+        }
+    }
+
+    fn usage() -> DiskUsage {
+        let items = vec![
+            item(UsageKind::Image, "<none>:<none>"),
+            item(UsageKind::Image, "stray:latest"),
+            UsageItem {
+                project: Some("postgres".to_string()),
+                ..item(UsageKind::Image, "postgres:16")
+            },
+            UsageItem {
+                in_use: true,
+                ..item(UsageKind::Image, "redis:7")
+            },
+            UsageItem {
+                stopped: true,
+                ..item(UsageKind::Container, "old")
+            },
+            item(UsageKind::Container, "running"),
+            item(UsageKind::Volume, "scratch"),
+            UsageItem {
+                protected: true,
+                project: Some("postgres".to_string()),
+                ..item(UsageKind::Volume, "postgres_data")
+            },
+            UsageItem {
+                in_use: true,
+                protected: true,
+                ..item(UsageKind::Volume, "redis_data")
+            },
+            item(UsageKind::BuildCache, "cache"),
+            UsageItem {
+                in_use: true,
+                ..item(UsageKind::BuildCache, "busy-cache")
+            },
+        ];
+        DiskUsage {
+            totals: Vec::new(),
+            items,
+        }
+    }
+
+    fn names(plan: &[UsageItem]) -> Vec<&str> {
+        plan.iter().map(|item| item.name.as_str()).collect()
+    }
+
+    #[test]
+    fn default_prune_protects_managed_volumes_and_tagged_images() {
+        let plan = prune_plan(&usage(), PruneOptions::default());
+        assert_eq!(names(&plan), ["old", "<none>:<none>", "scratch", "cache"]);
+    }
+
+    #[test]
+    fn options_widen_the_plan_but_never_touch_used_items() {
+        let options = PruneOptions {
+            unused_images: true,
+            managed_volumes: true,
+        };
+        let plan = prune_plan(&usage(), options);
+        assert_eq!(
+            names(&plan),
+            [
+                "old",
+                "<none>:<none>",
+                "stray:latest",
+                "scratch",
+                "postgres_data",
+                "cache"
+            ]
+        );
+    }
+
+    #[test]
+    fn images_of_stopped_containers_go_with_them() {
+        let image = |name: &str| UsageItem {
+            in_use: true,
+            ..item(UsageKind::Image, name)
+        };
+        let container = |name: &str, image: &str, stopped: bool| UsageItem {
+            stopped,
+            image: image.to_string(),
+            ..item(UsageKind::Container, name)
+        };
+        let usage = DiskUsage {
+            totals: Vec::new(),
+            items: vec![
+                image("stray:latest"),
+                image("redis:7"),
+                image("orphan:1"),
+                container("old", "stray", true),
+                container("cache", "redis:7", false),
+                container("also-old", "redis:7", true),
+            ],
+        };
+        let options = PruneOptions {
+            unused_images: true,
+            managed_volumes: false,
+        };
+        let plan = prune_plan(&usage, options);
+        assert_eq!(names(&plan), ["old", "also-old", "stray:latest"]);
+    }
+
+    #[test]
+    fn tagged_images_are_removed_by_reference_and_dangling_ones_by_id() {
+        let plan = vec![
+            UsageItem {
+                id: "abc123".to_string(),
+                ..item(UsageKind::Image, "app:1")
+            },
+            UsageItem {
+                id: "abc123".to_string(),
+                ..item(UsageKind::Image, "app:latest")
+            },
+            UsageItem {
+                id: "def456".to_string(),
+                ..item(UsageKind::Image, "<none>:<none>")
+            },
+            UsageItem {
+                id: "def456".to_string(),
+                ..item(UsageKind::Image, "<none>:<none>")
+            },
+            UsageItem {
+                stopped: true,
+                ..item(UsageKind::Container, "old")
+            },
+        ];
+        let cmds: Vec<(UsageKind, Vec<String>)> = remove_cmds(&plan)
+            .into_iter()
+            .map(|(kind, cmd)| {
+                let args = cmd
+                    .as_std()
+                    .get_args()
+                    .map(|arg| arg.to_string_lossy().into_owned())
+                    .collect();
+                (kind, args)
+            })
+            .collect();
+        assert!(cmds[0].0 == UsageKind::Container);
+        assert_eq!(cmds[0].1, ["container", "rm", "old"]);
+        assert!(cmds[1].0 == UsageKind::Image);
+        assert_eq!(cmds[1].1, ["image", "rm", "app:1", "app:latest", "def456"]);
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use tokio::process::Command;
+
+use crate::docker::compose::ComposeProject;
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::process::run_capture;
+
+/// Database engines the manager knows how to dump and restore logically.
+#[derive(Clone, Copy, PartialEq)]
+pub enum DbEngine {
+    Postgres,
+    Mysql,
+    Mariadb,
+    Redis,
+}
+
+impl fmt::Display for DbEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbEngine::Postgres => write!(f, "postgres"),
+            DbEngine::Mysql => write!(f, "mysql"),
+            DbEngine::Mariadb => write!(f, "mariadb"),
+            DbEngine::Redis => write!(f, "redis"),
+        }
+    }
+}
+
+impl DbEngine {
+    /// Engine of an image such as `postgres:16` or `docker.io/bitnami/redis`,
+    /// judged by the repository name.
+    pub fn detect(image: &str) -> Option<Self> {
+        let reference = image.split('@').next().unwrap_or(image);
+        let last = reference.rsplit('/').next().unwrap_or(reference);
+        let name = last.split(':').next().unwrap_or(last);
+        if name.contains("postgres") || name.contains("postgis") || name.contains("timescale") {
+            Some(DbEngine::Postgres)
+        } else if name.contains("mariadb") {
+            Some(DbEngine::Mariadb)
+        } else if name.contains("mysql") || name.contains("percona") {
+            Some(DbEngine::Mysql)
+        } else if name.contains("redis") || name.contains("valkey") {
+            Some(DbEngine::Redis)
+        } else {
+            None
+        }
+    }
+
+    /// File extension of this engine's dumps.
+    pub fn extension(self) -> &'static str {
+        match self {
+            DbEngine::Redis => "rdb",
+            _ => "sql",
+        }
+    }
+}
+
+/// A compose service running a known database, with the credentials its
+/// environment declares.
+#[derive(Clone)]
+pub struct DbService {
+    pub service: String,
+    pub engine: DbEngine,
+    env: BTreeMap<String, String>,
+}
+
+impl DbService {
+    /// User the dump and restore clients connect as.
+    fn user(&self) -> String {
+        match self.engine {
+            DbEngine::Postgres => self
+                .var(&["POSTGRES_USER"])
+                .unwrap_or_else(|| "postgres".to_string()),
+            DbEngine::Mysql | DbEngine::Mariadb => {
+                if self.root_password().is_some() {
+                    "root".to_string()
+                } else {
+                    self.var(&["MYSQL_USER", "MARIADB_USER"])
+                        .unwrap_or_else(|| "root".to_string())
+                }
+            }
+            DbEngine::Redis => String::new(),
+        }
+    }
+
+    fn root_password(&self) -> Option<String> {
+        self.var(&["MYSQL_ROOT_PASSWORD", "MARIADB_ROOT_PASSWORD"])
+    }
+
+    /// Variable the client reads its password from, and the password.
+    fn password_env(&self) -> Option<(&'static str, String)> {
+        let (name, password) = match self.engine {
+            DbEngine::Postgres => ("PGPASSWORD", self.var(&["POSTGRES_PASSWORD"])?),
+            DbEngine::Mysql | DbEngine::Mariadb => (
+                "MYSQL_PWD",
+                self.root_password()
+                    .or_else(|| self.var(&["MYSQL_PASSWORD", "MARIADB_PASSWORD"]))?,
+            ),
+            DbEngine::Redis => ("REDISCLI_AUTH", self.var(&["REDIS_PASSWORD"])?),
+        };
+        Some((name, password))
+    }
+
+    fn var(&self, names: &[&str]) -> Option<String> {
+        names
+            .iter()
+            .find_map(|name| self.env.get(*name))
+            .filter(|value| !value.is_empty())
+            .cloned()
+    }
+
+    /// `docker compose exec -T` into this service with the password set.
+    /// The password is passed through the environment, so it never shows up
+    /// in a process listing.
+    fn exec(&self, project: &ComposeProject) -> Command {
+        let mut cmd = project.command();
+        cmd.arg("exec").arg("-T");
+        if let Some((name, password)) = self.password_env() {
+            cmd.env(name, password).arg("-e").arg(name);
+        }
+        cmd.arg(&self.service);
+        cmd
+    }
+
+    /// Writes a dump to stdout. Redis has none; see [`DbService::save_cmd`].
+    pub fn dump_cmd(&self, project: &ComposeProject) -> Option<Command> {
+        let mut cmd = self.exec(project);
+        match self.engine {
+            DbEngine::Postgres => {
+                cmd.args(["pg_dumpall", "--clean", "--if-exists", "-U"])
+                    .arg(self.user());
+            }
+            DbEngine::Mysql | DbEngine::Mariadb => {
+                let tool = if self.engine == DbEngine::Mariadb {
+                    "mariadb-dump"
+                } else {
+                    "mysqldump"
+                };
+                cmd.args([
+                    tool,
+                    "--all-databases",
+                    "--single-transaction",
+                    "--routines",
+                    "--events",
+                    "-u",
+                ])
+                .arg(self.user());
+            }
+            DbEngine::Redis => return None,
+        }
+        Some(cmd)
+    }
+
+    /// Replays a dump read from stdin.
+    pub fn load_cmd(&self, project: &ComposeProject) -> Option<Command> {
+        let mut cmd = self.exec(project);
+        match self.engine {
+            DbEngine::Postgres => {
+                cmd.args(["psql", "-q", "-d", "postgres", "-U"])
+                    .arg(self.user());
+            }
+            DbEngine::Mysql => {
+                cmd.args(["mysql", "-u"]).arg(self.user());
+            }
+            DbEngine::Mariadb => {
+                cmd.args(["mariadb", "-u"]).arg(self.user());
+            }
+            DbEngine::Redis => return None,
+        }
+        Some(cmd)
+    }
+
+    /// Runs SQL read from stdin against the service's default database,
+    /// stopping at the first error. `None` for Redis.
+    pub fn sql_cmd(&self, project: &ComposeProject) -> Option<Command> {
+        let mut cmd = self.exec(project);
+        match self.engine {
+            DbEngine::Postgres => {
+                let database = self.var(&["POSTGRES_DB"]).unwrap_or_else(|| self.user());
+                cmd.args(["psql", "-q", "-v", "ON_ERROR_STOP=1", "-d"])
+                    .arg(database)
+                    .arg("-U")
+                    .arg(self.user());
+            }
+            DbEngine::Mysql | DbEngine::Mariadb => {
+                let client = if self.engine == DbEngine::Mariadb {
+                    "mariadb"
+                } else {
+                    "mysql"
+                };
+                cmd.args([client, "-u"]).arg(self.user());
+                if let Some(database) = self.var(&["MYSQL_DATABASE", "MARIADB_DATABASE"]) {
+                    cmd.arg(database);
+                }
+            }
+            DbEngine::Redis => return None,
+        }
+        Some(cmd)
+    }
+
+    /// Runs a shell script read from stdin, with the password variable set
+    /// so the script's own clients can connect.
+    pub fn shell_cmd(&self, project: &ComposeProject) -> Command {
+        let mut cmd = self.exec(project);
+        cmd.args(["sh", "-s"]);
+        cmd
+    }
+
+    /// Exits successfully once the server accepts connections.
+    pub fn ready_cmd(&self, project: &ComposeProject) -> Command {
+        let mut cmd = self.exec(project);
+        match self.engine {
+            DbEngine::Postgres => {
+                cmd.args(["pg_isready", "-q", "-U"]).arg(self.user());
+            }
+            DbEngine::Mysql => {
+                cmd.args(["mysqladmin", "ping", "--silent", "-u"])
+                    .arg(self.user());
+            }
+            DbEngine::Mariadb => {
+                cmd.args(["mariadb-admin", "ping", "--silent", "-u"])
+                    .arg(self.user());
+            }
+            DbEngine::Redis => {
+                cmd.args(["redis-cli", "ping"]);
+            }
+        }
+        cmd
+    }
+
+    /// Makes Redis write its RDB file now.
+    pub fn save_cmd(&self, project: &ComposeProject) -> Command {
+        let mut cmd = self.exec(project);
+        cmd.args(["redis-cli", "SAVE"]);
+        cmd
+    }
+
+    /// Whether Redis persists with an append-only file, which it loads
+    /// instead of the RDB file on startup.
+    pub async fn appendonly(&self, project: &ComposeProject) -> bool {
+        self.redis_config(project, "appendonly").await.as_deref() == Some("yes")
+    }
+
+    /// Path of the RDB file inside the container, from the running config.
+    pub async fn rdb_path(&self, project: &ComposeProject) -> Option<String> {
+        let dir = self.redis_config(project, "dir").await?;
+        let file = self.redis_config(project, "dbfilename").await?;
+        Some(format!("{}/{}", dir.trim_end_matches('/'), file))
+    }
+
+    async fn redis_config(&self, project: &ComposeProject, key: &str) -> Option<String> {
+        let mut cmd = self.exec(project);
+        cmd.args(["redis-cli", "CONFIG", "GET", key]);
+        let out = run_capture(cmd).await.ok()?;
+        if !out.status.success() {
+            return None;
+        }
+        // Prints the key, then its value, one per line.
+        String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .nth(1)
+            .map(str::to_string)
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Services of the project running a known database image, from the
+/// resolved compose model.
+pub fn db_services(compose: &ComposeFile) -> Vec<DbService> {
+    compose
+        .services
+        .iter()
+        .filter_map(|(name, service)| {
+            let engine = DbEngine::detect(service.image.as_deref()?)?;
+            Some(DbService {
+                service: name.clone(),
+                engine,
+                env: service.env(),
+            })
+        })
+        .collect()
+}
+
+/// `docker compose cp` between a service container and the host.
+pub fn copy_cmd(project: &ComposeProject, from: &str, to: &str) -> Command {
+    let mut cmd = project.command();
+    cmd.arg("cp").arg(from).arg(to);
+    cmd
+}
+
+pub fn stop_service_cmd(project: &ComposeProject, service: &str) -> Command {
+    let mut cmd = project.command();
+    cmd.arg("stop").arg(service);
+    cmd
+}
+
+pub fn start_service_cmd(project: &ComposeProject, service: &str) -> Command {
+    let mut cmd = project.command();
+    cmd.arg("start").arg(service);
+    cmd
+}
+
+/// `<service>-<timestamp>.<ext>`; [`dump_service`] reads the service back.
+pub fn dump_name(service: &str, timestamp: &str, engine: DbEngine) -> String {
+    format!("{}-{}.{}", service, timestamp, engine.extension())
+}
+
+/// Service a dump file was taken from, given its file name.
+pub fn dump_service(name: &str) -> Option<&str> {
+    let (stem, _) = name.rsplit_once('.')?;
+    // Timestamps are `-YYYYMMDD-HHMMSS`, 16 bytes.
+    let cut = stem.len().checked_sub(16)?;
+    stem.is_char_boundary(cut).then(|| &stem[..cut])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_judges_by_repository_name() {
+        let detect = DbEngine::detect;
+        assert!(detect("postgres") == Some(DbEngine::Postgres));
+        assert!(detect("postgis/postgis:16-3.4") == Some(DbEngine::Postgres));
+        assert!(detect("timescale/timescaledb:latest-pg16") == Some(DbEngine::Postgres));
+        assert!(detect("mysql:8") == Some(DbEngine::Mysql));
+        assert!(detect("percona/percona-server") == Some(DbEngine::Mysql));
+        assert!(detect("mariadb:11") == Some(DbEngine::Mariadb));
+        assert!(detect("docker.io/bitnami/redis:7.2") == Some(DbEngine::Redis));
+        assert!(detect("valkey/valkey@sha256:abc") == Some(DbEngine::Redis));
+        assert!(detect("phpmyadmin/phpmyadmin").is_none());
+        assert!(detect("adminer").is_none());
+        // Registry host and port must not be mistaken for the name.
+        assert!(detect("registry.local:5000/team/api:redis").is_none());
+    }
+
+    #[test]
+    fn dump_service_recovers_the_service_from_a_dump_name() {
+        let name = dump_name("db-main", "20261018-141056", DbEngine::Postgres);
+        assert_eq!(name, "db-main-20261018-141056.sql");
+        assert_eq!(dump_service(&name), Some("db-main"));
+        assert_eq!(dump_service("short.sql"), None);
+        assert_eq!(dump_service("noextension"), None);
+    }
+}
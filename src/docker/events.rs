@@ -1,47 +1,73 @@
 use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
-use std::process::Command;
 use std::process::Stdio;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::Duration;
-
-use crate::status::Status;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+
+use crate::docker::ports;
+use crate::log_buffer::SharedLogBuffer;
+use crate::notify::{AppMessage, Notifier};
+
+const EVENT_WINDOW_SECS: u64 = 2;
+/// How long a `kill` event explains a following signal exit. Covers the
+/// default stop timeout and the SIGKILL that follows it.
+const KILL_GRACE: Duration = Duration::from_secs(60);
+
+/// A container lifecycle event for one of the managed projects. The listener
+/// only records it in the events log; deciding what it means for the project
+/// status is left to the app.
+pub struct ContainerEvent {
+    pub project: String,
+    pub container: String,
+    pub action: String,
+    pub exit_code: String,
+}
 
-const MAX_EVENT_LOG_SIZE: usize = 100 * 1024;
+/// A container that died with a non-zero exit code outside of a stop request.
+pub struct ContainerCrash {
+    pub container: String,
+    pub exit_code: String,
+    pub at: Instant,
+}
 
-pub struct ProjectEventTargets {
-    pub status: Arc<Mutex<Status>>,
-    pub events: Arc<Mutex<String>>,
-    pub pull_progress: Arc<Mutex<Option<String>>>,
+/// Whether a `die` with this exit code is the result of a kill seen at
+/// `killed_at`, as with `docker stop` or `docker kill`, rather than a crash.
+pub fn died_from_kill(killed_at: Option<Instant>, exit_code: &str, now: Instant) -> bool {
+    let Some(killed_at) = killed_at else {
+        return false;
+    };
+    let signalled = exit_code.parse::<i32>().is_ok_and(|code| code > 128);
+    signalled && now.saturating_duration_since(killed_at) <= KILL_GRACE
 }
 
 pub struct EventListenerHandle {
-    shutdown: Arc<AtomicBool>,
+    task: JoinHandle<()>,
 }
 
 impl EventListenerHandle {
     pub fn signal_shutdown(&mut self) {
-        self.shutdown.store(true, std::sync::atomic::Ordering::Relaxed);
+        // Aborting drops the running `docker events` child, which kills it.
+        self.task.abort();
     }
 }
 
 pub fn spawn_projects_listener(
-    project_targets: HashMap<String, ProjectEventTargets>,
+    project_events: HashMap<String, SharedLogBuffer>,
+    notifier: Notifier,
 ) -> EventListenerHandle {
-    let shutdown = Arc::new(AtomicBool::new(false));
-    let shutdown_clone = Arc::clone(&shutdown);
+    let task = tokio::spawn(async move {
+        seed_initial_events(&project_events).await;
 
-    thread::spawn(move || {
-        seed_initial_events(&project_targets);
+        // Each `docker events` call covers one short window so a daemon restart
+        // is noticed; windows are contiguous so no event is missed.
+        let mut window_start = unix_now();
 
         loop {
-            if shutdown_clone.load(Ordering::Relaxed) {
-                break;
-            }
+            let window_end = unix_now().max(window_start) + EVENT_WINDOW_SECS;
 
-            let mut cmd = std::process::Command::new("docker");
+            let mut cmd = Command::new("docker");
             cmd.arg("events")
                 .arg("--filter")
                 .arg("type=container")
@@ -50,61 +76,52 @@ pub fn spawn_projects_listener(
                 .arg("--format")
                 .arg("{{.Action}}\t{{index .Actor.Attributes \"com.docker.compose.project\"}}\t{{index .Actor.Attributes \"name\"}}\t{{index .Actor.Attributes \"exitCode\"}}")
                 .arg("--since")
-                .arg("0")
+                .arg(window_start.to_string())
                 .arg("--until")
-                .arg("2");
+                .arg(window_end.to_string())
+                .kill_on_drop(true);
 
             match cmd.stdout(Stdio::piped()).spawn() {
                 Ok(mut child) => {
                     if let Some(stdout) = child.stdout.take() {
-                        let reader = BufReader::new(stdout);
-                        for line in reader.lines().map_while(Result::ok) {
-                            if shutdown_clone.load(Ordering::Relaxed) {
-                                let _ = child.kill();
-                                let _ = child.wait();
-                                break;
-                            }
-                            handle_event_line(&line, &project_targets);
+                        let mut lines = BufReader::new(stdout).lines();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            handle_event_line(&line, &project_events, &notifier).await;
                         }
                     }
-                    let _ = child.wait();
+                    let _ = child.wait().await;
                 }
                 Err(_) => {
                     // Docker events stream unavailable, retry shortly.
                 }
             }
 
-            if shutdown_clone.load(Ordering::Relaxed) {
-                break;
+            if unix_now() < window_end {
+                // The command failed early; avoid spinning while the daemon is away.
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            } else {
+                window_start = window_end;
             }
-
-            thread::sleep(Duration::from_millis(500));
         }
     });
 
-    EventListenerHandle { shutdown }
+    EventListenerHandle { task }
 }
 
-fn seed_initial_events(project_targets: &HashMap<String, ProjectEventTargets>) {
-    for (project, target) in project_targets {
-        let containers = list_project_containers(project);
-        if containers.is_empty() {
-            continue;
-        }
-
-        for container_name in containers {
-            append_event_log(
-                &target.events,
-                project,
-                &container_name,
-                "running (snapshot)",
-            );
-            append_runtime_details(&target.events, &container_name);
+async fn seed_initial_events(project_events: &HashMap<String, SharedLogBuffer>) {
+    for (project, events) in project_events {
+        for container_name in list_project_containers(project).await {
+            append_event_log(events, project, &container_name, "running (snapshot)");
+            append_runtime_details(events, &container_name).await;
         }
     }
 }
 
-fn handle_event_line(line: &str, project_targets: &HashMap<String, ProjectEventTargets>) {
+async fn handle_event_line(
+    line: &str,
+    project_events: &HashMap<String, SharedLogBuffer>,
+    notifier: &Notifier,
+) {
     let mut parts = line.splitn(4, '\t');
     let action = parts.next().unwrap_or("").trim();
     let project = normalize_template_value(parts.next().unwrap_or("").trim());
@@ -116,57 +133,35 @@ fn handle_event_line(line: &str, project_targets: &HashMap<String, ProjectEventT
     }
 
     let project = if project.is_empty() {
-        resolve_project_from_container(container_name).unwrap_or_default()
+        resolve_project_from_container(container_name)
+            .await
+            .unwrap_or_default()
     } else {
         project
     };
 
-    if project.is_empty() {
+    let Some(events) = project_events.get(&project) else {
         return;
-    }
-
-    if let Some(target) = project_targets.get(&project) {
-        append_event_log(&target.events, &project, container_name, action);
-        if matches!(action, "start" | "restart" | "unpause") {
-            append_runtime_details(&target.events, container_name);
-        }
+    };
 
-        let mut status = target.status.lock().unwrap();
-        let currently_stopping = matches!(*status, Status::Stopping);
-        let next_status = match action {
-            "create" | "restart" | "unpause" => Some(Status::Starting),
-            "start" => Some(Status::Running),
-            "stop" | "destroy" | "pause" => Some(Status::Stopped),
-            "die" | "kill" => {
-                if currently_stopping || matches!(*status, Status::Stopped) || exit_code == "0" {
-                    Some(Status::Stopped)
-                } else {
-                    Some(Status::Error)
-                }
-            }
-            _ if action.starts_with("health_status: ") => {
-                if action.ends_with("healthy") {
-                    Some(Status::Running)
-                } else if action.ends_with("unhealthy") {
-                    Some(Status::Error)
-                } else {
-                    None
-                }
-            }
-            _ => None,
-        };
+    append_event_log(events, &project, container_name, action);
+    if matches!(action, "start" | "restart" | "unpause") {
+        append_runtime_details(events, container_name).await;
+    }
 
-        if let Some(next_status) = next_status {
-            if next_status != Status::Pulling {
-                *target.pull_progress.lock().unwrap() = None;
-            }
+    notifier.send(AppMessage::ContainerEvent(ContainerEvent {
+        project,
+        container: container_name.to_string(),
+        action: action.to_string(),
+        exit_code: exit_code.to_string(),
+    }));
+}
 
-            if *status != Status::Pulling || matches!(next_status, Status::Running | Status::Error)
-            {
-                *status = next_status;
-            }
-        }
-    }
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
 }
 
 fn normalize_template_value(value: &str) -> String {
@@ -177,7 +172,7 @@ fn normalize_template_value(value: &str) -> String {
     }
 }
 
-fn resolve_project_from_container(container_name: &str) -> Option<String> {
+pub async fn resolve_project_from_container(container_name: &str) -> Option<String> {
     if container_name.is_empty() {
         return None;
     }
@@ -186,9 +181,10 @@ fn resolve_project_from_container(container_name: &str) -> Option<String> {
         container_name,
         "{{index .Config.Labels \"com.docker.compose.project\"}}",
     )
+    .await
 }
 
-fn append_runtime_details(logs: &Arc<Mutex<String>>, container_name: &str) {
+async fn append_runtime_details(logs: &SharedLogBuffer, container_name: &str) {
     if container_name.is_empty() {
         return;
     }
@@ -197,32 +193,50 @@ fn append_runtime_details(logs: &Arc<Mutex<String>>, container_name: &str) {
         container_name,
         "{{range $k, $v := .NetworkSettings.Networks}}{{$k}}={{$v.IPAddress}} {{end}}",
     )
+    .await
     .unwrap_or_else(|| "unknown".to_string());
 
-    let ports = docker_inspect_field(
-        container_name,
-        "{{range $p, $v := .NetworkSettings.Ports}}{{$p}}={{if $v}}{{(index $v 0).HostIp}}:{{(index $v 0).HostPort}}{{else}}internal{{end}} {{end}}",
-    )
-    .unwrap_or_else(|| "none".to_string());
+    // First binding of each port, as the port map reads them.
+    let mut ports: Vec<String> = Vec::new();
+    let mut described: Vec<String> = Vec::new();
+    for (port, binding) in ports::container_ports(container_name)
+        .await
+        .unwrap_or_default()
+    {
+        if described.contains(&port) {
+            continue;
+        }
+        ports.push(match binding {
+            Some(binding) => format!(
+                "{}={}:{}",
+                port,
+                binding.host_ip,
+                binding.host_port.map(|p| p.to_string()).unwrap_or_default()
+            ),
+            None => format!("{}=internal", port),
+        });
+        described.push(port);
+    }
+    let ports = ports.join(" ");
 
     let ips = normalize_runtime_value(&ips, "pending");
     let ports = normalize_runtime_value(&ports, "none");
 
-    let mut logs_lock = logs.lock().unwrap();
-    logs_lock.push_str(&format!(
-        "[event] {} runtime ips=[{}] ports=[{}]\n",
+    logs.lock().unwrap().push_line(format!(
+        "[event] {} runtime ips=[{}] ports=[{}]",
         container_name, ips, ports
     ));
 }
 
-fn list_project_containers(project: &str) -> Vec<String> {
+async fn list_project_containers(project: &str) -> Vec<String> {
     let output = Command::new("docker")
         .arg("ps")
         .arg("--filter")
         .arg(format!("label=com.docker.compose.project={}", project))
         .arg("--format")
         .arg("{{.Names}}")
-        .output();
+        .output()
+        .await;
 
     match output {
         Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
@@ -235,13 +249,14 @@ fn list_project_containers(project: &str) -> Vec<String> {
     }
 }
 
-fn docker_inspect_field(container_name: &str, template: &str) -> Option<String> {
+async fn docker_inspect_field(container_name: &str, template: &str) -> Option<String> {
     let output = Command::new("docker")
         .arg("inspect")
         .arg("--format")
         .arg(template)
         .arg(container_name)
         .output()
+        .await
         .ok()?;
 
     if !output.status.success() {
@@ -276,23 +291,35 @@ fn normalize_runtime_value(value: &str, fallback: &str) -> String {
     }
 }
 
-fn append_event_log(logs: &Arc<Mutex<String>>, project: &str, container_name: &str, action: &str) {
-    let mut logs_lock = logs.lock().unwrap();
+pub fn append_event_log(logs: &SharedLogBuffer, project: &str, container_name: &str, action: &str) {
     let scope = if container_name.is_empty() {
         project
     } else {
         container_name
     };
-    let new_entry = format!("[event] {} {}\n", scope, action);
-
-    if logs_lock.len() + new_entry.len() > MAX_EVENT_LOG_SIZE {
-        let truncate_point = logs_lock.len().saturating_sub(MAX_EVENT_LOG_SIZE / 2);
-        if truncate_point > 0
-            && let Some(pos) = logs_lock[truncate_point..].find('\n')
-        {
-            logs_lock.drain(0..truncate_point + pos + 1);
-        }
+    logs.lock()
+        .unwrap()
+        .push_line(format!("[event] {} {}", scope, action));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signal_exits_after_a_recent_kill_are_stops() {
+        let now = Instant::now();
+        assert!(died_from_kill(Some(now), "143", now));
+        let later = now + Duration::from_secs(10);
+        assert!(died_from_kill(Some(now), "137", later));
     }
 
-    logs_lock.push_str(&new_entry);
+    #[test]
+    fn other_exits_are_still_crashes() {
+        let now = Instant::now();
+        assert!(!died_from_kill(None, "137", now));
+        assert!(!died_from_kill(Some(now), "1", now));
+        assert!(!died_from_kill(Some(now), "", now));
+        assert!(!died_from_kill(Some(now), "137", now + KILL_GRACE * 2));
+    }
 }
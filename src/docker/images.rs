@@ -0,0 +1,190 @@
+use serde::Deserialize;
+use tokio::process::Command;
+
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::process::run_capture;
+
+/// A local image, with the managed projects whose compose files use it.
+#[derive(Clone)]
+pub struct ImageInfo {
+    pub id: String,
+    /// `repository:tag` references; empty for dangling images.
+    pub tags: Vec<String>,
+    /// `repository@sha256:...` references from the registry it came from.
+    pub digests: Vec<String>,
+    pub size: u64,
+    /// RFC 3339 creation time, as Docker reports it.
+    pub created: String,
+    pub projects: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedImage {
+    id: String,
+    #[serde(default)]
+    repo_tags: Option<Vec<String>>,
+    #[serde(default)]
+    repo_digests: Option<Vec<String>>,
+    #[serde(default)]
+    created: String,
+    #[serde(default)]
+    size: u64,
+}
+
+impl ImageInfo {
+    pub fn is_dangling(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// First tag, or `<none>` for dangling images.
+    pub fn name(&self) -> &str {
+        self.tags.first().map_or("<none>", String::as_str)
+    }
+
+    /// Short registry digest, or the short image id for images that were
+    /// never pulled.
+    pub fn short_digest(&self) -> String {
+        let full = self
+            .digests
+            .first()
+            .and_then(|digest| digest.split_once('@'))
+            .map_or(self.id.as_str(), |(_, digest)| digest);
+        let hex = full.strip_prefix("sha256:").unwrap_or(full);
+        hex.chars().take(12).collect()
+    }
+
+    /// `YYYY-MM-DD HH:MM` from the creation time.
+    pub fn created_display(&self) -> String {
+        self.created
+            .get(..16)
+            .unwrap_or(&self.created)
+            .replace('T', " ")
+    }
+
+    /// Whether a compose `image:` reference names this image.
+    pub fn matches(&self, reference: &str) -> bool {
+        let reference = normalize(reference);
+        if reference.contains('@') {
+            return self
+                .digests
+                .iter()
+                .any(|digest| normalize(digest) == reference);
+        }
+        self.tags.iter().any(|tag| normalize(tag) == reference)
+    }
+}
+
+/// Canonical form of an image reference: without the Docker Hub prefixes
+/// Docker leaves out of tags, and with `:latest` when no tag is given.
+fn normalize(reference: &str) -> String {
+    let reference = reference
+        .strip_prefix("docker.io/library/")
+        .or_else(|| reference.strip_prefix("docker.io/"))
+        .unwrap_or(reference);
+    if reference.contains('@') {
+        return reference.to_string();
+    }
+    let last = reference.rsplit('/').next().unwrap_or(reference);
+    if last.contains(':') {
+        reference.to_string()
+    } else {
+        format!("{}:latest", reference)
+    }
+}
+
+/// Whether two image references name the same image.
+pub fn same_reference(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+/// Every local image, attributed to the `projects` whose compose files use
+/// it. Managed images come first.
+pub async fn list(projects: &[String]) -> Result<Vec<ImageInfo>, String> {
+    let mut cmd = Command::new("docker");
+    cmd.args(["image", "ls", "--quiet", "--no-trunc"]);
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    let mut ids: Vec<String> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+    ids.sort();
+    ids.dedup();
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut cmd = Command::new("docker");
+    cmd.args(["image", "inspect"]).args(&ids);
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    let inspected: Vec<InspectedImage> =
+        serde_json::from_slice(&out.stdout).map_err(|e| e.to_string())?;
+
+    let mut references = Vec::new();
+    for project in projects {
+        if let Some(compose) = ComposeFile::load(project).await {
+            for image in compose.images(project) {
+                references.push((project.clone(), image));
+            }
+        }
+    }
+
+    let mut images: Vec<ImageInfo> = inspected
+        .into_iter()
+        .map(|image| {
+            let mut info = ImageInfo {
+                id: image.id,
+                tags: image.repo_tags.unwrap_or_default(),
+                digests: image.repo_digests.unwrap_or_default(),
+                size: image.size,
+                created: image.created,
+                projects: Vec::new(),
+            };
+            for (project, reference) in &references {
+                if info.matches(reference) && !info.projects.contains(project) {
+                    info.projects.push(project.clone());
+                }
+            }
+            info
+        })
+        .collect();
+    images.sort_by(|a, b| {
+        a.projects
+            .is_empty()
+            .cmp(&b.projects.is_empty())
+            .then_with(|| a.is_dangling().cmp(&b.is_dangling()))
+            .then_with(|| a.name().cmp(b.name()))
+    });
+    Ok(images)
+}
+
+pub fn pull_cmd(reference: &str) -> Command {
+    let mut cmd = Command::new("docker");
+    cmd.arg("pull").arg(reference);
+    cmd
+}
+
+/// Removes every tag of the image, or the image itself when it has none.
+/// Without `--force`, Docker still refuses images containers use.
+pub fn remove_cmd(image: &ImageInfo) -> Command {
+    let mut cmd = Command::new("docker");
+    cmd.args(["image", "rm"]);
+    if image.tags.is_empty() {
+        cmd.arg(&image.id);
+    } else {
+        cmd.args(&image.tags);
+    }
+    cmd
+}
+
+pub fn prune_dangling_cmd() -> Command {
+    let mut cmd = Command::new("docker");
+    cmd.args(["image", "prune", "--force"]);
+    cmd
+}
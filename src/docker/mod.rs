@@ -1,5 +1,20 @@
+pub mod archive;
+pub mod build;
+pub mod bundle;
 pub mod client;
 pub mod compose;
+pub mod compose_file;
+pub mod crash;
 pub mod daemon;
+pub mod disk;
+pub mod dumps;
 pub mod events;
+pub mod images;
+pub mod networks;
+pub mod overrides;
+pub mod ports;
 pub mod process;
+pub mod pull;
+pub mod stats;
+pub mod units;
+pub mod volumes;
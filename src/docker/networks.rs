@@ -0,0 +1,269 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Deserialize;
+use tokio::process::Command;
+
+use crate::docker::process::run_capture;
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const NETWORK_LABEL: &str = "com.docker.compose.network";
+
+/// A container attached to a network.
+#[derive(Clone)]
+pub struct Attachment {
+    pub container: String,
+    /// Compose project of the container, managed or not.
+    pub project: Option<String>,
+    /// IPv4 address with prefix length, as Docker reports it.
+    pub address: String,
+    pub aliases: Vec<String>,
+}
+
+/// A network that a managed project created or that its containers use.
+#[derive(Clone)]
+pub struct NetworkInfo {
+    pub name: String,
+    pub driver: String,
+    pub internal: bool,
+    pub subnets: Vec<String>,
+    /// Compose project that created it; `None` for external networks.
+    pub project: Option<String>,
+    /// Its key in the project's compose file.
+    pub key: Option<String>,
+    pub containers: Vec<Attachment>,
+}
+
+impl NetworkInfo {
+    /// Containers not belonging to the project that owns the network, or for
+    /// external networks, to no managed project.
+    pub fn foreign<'a>(&'a self, managed: &'a [String]) -> impl Iterator<Item = &'a Attachment> {
+        self.containers
+            .iter()
+            .filter(move |attachment| self.is_foreign(attachment, managed))
+    }
+
+    pub fn is_foreign(&self, attachment: &Attachment, managed: &[String]) -> bool {
+        match (&self.project, &attachment.project) {
+            (Some(owner), Some(project)) => owner != project,
+            (Some(_), None) => true,
+            (None, Some(project)) => !managed.contains(project),
+            (None, None) => true,
+        }
+    }
+
+    /// A project network `docker compose down` cannot remove because other
+    /// containers are still attached to it, so it outlives the project.
+    pub fn orphaned_on_down(&self, managed: &[String]) -> bool {
+        self.project.is_some() && self.foreign(managed).next().is_some()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedNetwork {
+    name: String,
+    #[serde(default)]
+    driver: String,
+    #[serde(default)]
+    internal: bool,
+    #[serde(rename = "IPAM", default)]
+    ipam: Option<Ipam>,
+    #[serde(default)]
+    containers: Option<HashMap<String, Endpoint>>,
+    #[serde(default)]
+    labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Ipam {
+    #[serde(default)]
+    config: Option<Vec<IpamConfig>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct IpamConfig {
+    #[serde(default)]
+    subnet: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Endpoint {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "IPv4Address", default)]
+    ipv4_address: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedContainer {
+    id: String,
+    #[serde(default)]
+    config: Option<ContainerConfig>,
+    #[serde(default)]
+    network_settings: Option<NetworkSettings>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerConfig {
+    #[serde(default)]
+    labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct NetworkSettings {
+    #[serde(default)]
+    networks: Option<HashMap<String, ContainerNetwork>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerNetwork {
+    #[serde(default)]
+    aliases: Option<Vec<String>>,
+    /// Newer engines report the names a container answers to here.
+    #[serde(rename = "DNSNames", default)]
+    dns_names: Option<Vec<String>>,
+}
+
+/// Networks created by the managed `projects` or used by their containers,
+/// with every attached container. Project networks come first.
+pub async fn list(projects: &[String]) -> Result<Vec<NetworkInfo>, String> {
+    let mut cmd = Command::new("docker");
+    cmd.args(["network", "ls", "--quiet", "--no-trunc"]);
+    let ids = network_ids(cmd).await?;
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut cmd = Command::new("docker");
+    cmd.args(["network", "inspect"]).args(&ids);
+    let networks = inspect_networks(cmd).await?;
+
+    let container_ids: BTreeSet<&String> = networks
+        .iter()
+        .flat_map(|network| network.containers.iter().flatten())
+        .map(|(id, _)| id)
+        .collect();
+    let containers: HashMap<String, InspectedContainer> = if container_ids.is_empty() {
+        HashMap::new()
+    } else {
+        // Containers that went away since the network was inspected make the
+        // command fail, but the others are still printed.
+        let mut cmd = Command::new("docker");
+        cmd.args(["container", "inspect"]).args(container_ids);
+        let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+        serde_json::from_slice::<Vec<InspectedContainer>>(&out.stdout)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|container| (container.id.clone(), container))
+            .collect()
+    };
+
+    let mut list: Vec<NetworkInfo> = networks
+        .into_iter()
+        .map(|network| {
+            let labels = network.labels.unwrap_or_default();
+            let mut attached: Vec<Attachment> = network
+                .containers
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(id, endpoint)| {
+                    let container = containers.get(&id);
+                    let project = container
+                        .and_then(|c| c.config.as_ref())
+                        .and_then(|config| config.labels.as_ref())
+                        .and_then(|labels| labels.get(PROJECT_LABEL))
+                        .cloned();
+                    let settings = container
+                        .and_then(|c| c.network_settings.as_ref())
+                        .and_then(|settings| settings.networks.as_ref())
+                        .and_then(|networks| networks.get(&network.name));
+                    let mut aliases: Vec<String> = settings
+                        .into_iter()
+                        .flat_map(|settings| {
+                            settings
+                                .aliases
+                                .iter()
+                                .flatten()
+                                .chain(settings.dns_names.iter().flatten())
+                        })
+                        // Docker adds the short container id as an alias.
+                        .filter(|alias| !id.starts_with(alias.as_str()) && **alias != endpoint.name)
+                        .cloned()
+                        .collect();
+                    aliases.sort();
+                    aliases.dedup();
+                    Attachment {
+                        container: endpoint.name,
+                        project,
+                        address: endpoint.ipv4_address,
+                        aliases,
+                    }
+                })
+                .collect();
+            attached.sort_by(|a, b| a.container.cmp(&b.container));
+            NetworkInfo {
+                name: network.name,
+                driver: network.driver,
+                internal: network.internal,
+                subnets: network
+                    .ipam
+                    .and_then(|ipam| ipam.config)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|config| config.subnet)
+                    .filter(|subnet| !subnet.is_empty())
+                    .collect(),
+                project: labels.get(PROJECT_LABEL).cloned(),
+                key: labels.get(NETWORK_LABEL).cloned(),
+                containers: attached,
+            }
+        })
+        .filter(|network| {
+            network
+                .project
+                .as_ref()
+                .is_some_and(|project| projects.contains(project))
+                || network.containers.iter().any(|attachment| {
+                    attachment
+                        .project
+                        .as_ref()
+                        .is_some_and(|project| projects.contains(project))
+                })
+        })
+        .collect();
+    list.sort_by(|a, b| {
+        a.project
+            .is_none()
+            .cmp(&b.project.is_none())
+            .then_with(|| a.project.cmp(&b.project))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(list)
+}
+
+async fn network_ids(cmd: Command) -> Result<Vec<String>, String> {
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+async fn inspect_networks(cmd: Command) -> Result<Vec<InspectedNetwork>, String> {
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    serde_json::from_slice(&out.stdout).map_err(|e| e.to_string())
+}
@@ -0,0 +1,365 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::value::{Tag, TaggedValue};
+use serde_yaml::{Mapping, Value};
+use tokio::process::Command;
+
+use crate::docker::compose::{OVERRIDE_TAG_VERSION, compose_version};
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::ports;
+use crate::docker::process::run_capture;
+
+/// Where generated override files live, inside the project directory.
+pub const DIR: &str = ".docker-manager";
+pub const SHARED_NETWORK_FILE: &str = "shared-network.yml";
+pub const PORTS_FILE: &str = "ports.yml";
+
+/// Generated files, in the order they are applied after the project's own.
+const FILES: [&str; 2] = [SHARED_NETWORK_FILE, PORTS_FILE];
+
+/// Top-level extension key the ports override records its remaps under;
+/// Compose ignores `x-` keys.
+const REMAPS_KEY: &str = "x-docker-manager-port-remaps";
+
+/// A declared port published on a different host port than the compose
+/// file says.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PortRemap {
+    pub service: String,
+    pub container_port: String,
+    pub protocol: String,
+    /// Host port in the compose file; `None` when Docker picked one.
+    pub from: Option<u16>,
+    pub to: u16,
+}
+
+impl PortRemap {
+    pub fn matches(&self, service: &str, container_port: &str, protocol: &str) -> bool {
+        self.service == service
+            && self.container_port == container_port
+            && self.protocol == protocol
+    }
+}
+
+const HEADER: &str = "# Generated by docker-manager; changes are overwritten.\n";
+
+/// `COMPOSE_FILE` for a project with generated overrides: its own compose
+/// file (and `docker-compose.override.yml`, which an explicit list would
+/// otherwise skip) followed by each override. `None` when there are none.
+pub fn compose_files(project_dir: &Path) -> Option<String> {
+    let overrides: Vec<String> = FILES
+        .iter()
+        .map(|file| format!("{}/{}", DIR, file))
+        .filter(|file| project_dir.join(file).is_file())
+        .collect();
+    if overrides.is_empty() {
+        return None;
+    }
+    let mut files = vec!["docker-compose.yml".to_string()];
+    if project_dir.join("docker-compose.override.yml").is_file() {
+        files.push("docker-compose.override.yml".to_string());
+    }
+    files.extend(overrides);
+    Some(files.join(":"))
+}
+
+fn path(project: &str, file: &str) -> String {
+    format!("containers/{}/{}/{}", project, DIR, file)
+}
+
+async fn write(project: &str, file: &str, content: Mapping) -> Result<(), String> {
+    let yaml = serde_yaml::to_string(&content).map_err(|e| e.to_string())?;
+    let dir = format!("containers/{}/{}", project, DIR);
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| format!("{}: {}", dir, e))?;
+    let path = path(project, file);
+    tokio::fs::write(&path, format!("{}{}", HEADER, yaml))
+        .await
+        .map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Deletes a generated override; a missing file is fine.
+pub async fn remove(project: &str, file: &str) -> Result<(), String> {
+    let path = path(project, file);
+    match tokio::fs::remove_file(&path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("{}: {}", path, e)),
+    }
+}
+
+/// Remaps recorded in the project's ports override; empty without one.
+pub async fn port_remaps(project: &str) -> Vec<PortRemap> {
+    tokio::fs::read_to_string(path(project, PORTS_FILE))
+        .await
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<Value>(&content).ok())
+        .and_then(|content| content.get(REMAPS_KEY).cloned())
+        .and_then(|remaps| serde_yaml::from_value(remaps).ok())
+        .unwrap_or_default()
+}
+
+/// Rewrites the ports override so each remapped service publishes its
+/// compose file ports with the remapped host ports swapped in. No remaps
+/// removes the file.
+///
+/// The list is tagged `!override` because Compose otherwise appends override
+/// ports to the original ones; older Compose releases that do not know the
+/// tag are refused rather than left publishing both host ports.
+pub async fn write_port_remaps(
+    project: &str,
+    compose: &ComposeFile,
+    remaps: &[PortRemap],
+) -> Result<(), String> {
+    if remaps.is_empty() {
+        return remove(project, PORTS_FILE).await;
+    }
+    let (major, minor, patch) = OVERRIDE_TAG_VERSION;
+    match compose_version().await {
+        Some(version) if version >= OVERRIDE_TAG_VERSION => {}
+        Some((found_major, found_minor, found_patch)) => {
+            return Err(format!(
+                "Port remapping needs Docker Compose {}.{}.{} or later, found {}.{}.{}",
+                major, minor, patch, found_major, found_minor, found_patch
+            ));
+        }
+        None => {
+            return Err(format!(
+                "Port remapping needs Docker Compose {}.{}.{} or later; cannot read its version",
+                major, minor, patch
+            ));
+        }
+    }
+    write(project, PORTS_FILE, port_remaps_override(compose, remaps)?).await
+}
+
+/// Content of the ports override for `remaps`, which must not be empty.
+fn port_remaps_override(compose: &ComposeFile, remaps: &[PortRemap]) -> Result<Mapping, String> {
+    let mut services = Mapping::new();
+    for remap in remaps {
+        let key = Value::from(remap.service.as_str());
+        if services.contains_key(&key) {
+            continue;
+        }
+        let service = compose
+            .services
+            .get(&remap.service)
+            .ok_or_else(|| format!("{} is not in docker-compose.yml", remap.service))?;
+        let service_remaps: Vec<&PortRemap> = remaps
+            .iter()
+            .filter(|other| other.service == remap.service)
+            .collect();
+        let mut applied = 0;
+        let entries: Vec<Value> = service
+            .ports
+            .iter()
+            .map(|port| {
+                let Some((host_ip, host_port, container_port, protocol)) =
+                    ports::declared_port(port)
+                else {
+                    return port.clone();
+                };
+                let Some(remap) = service_remaps.iter().find(|remap| {
+                    remap.container_port == container_port
+                        && remap.protocol == protocol
+                        && remap.from == host_port
+                }) else {
+                    return port.clone();
+                };
+                applied += 1;
+                let mut entry = Mapping::new();
+                let target = container_port
+                    .parse::<u16>()
+                    .map(Value::from)
+                    .unwrap_or_else(|_| container_port.clone().into());
+                entry.insert("target".into(), target);
+                entry.insert("published".into(), remap.to.to_string().into());
+                entry.insert("protocol".into(), protocol.into());
+                if !host_ip.is_empty() {
+                    entry.insert("host_ip".into(), host_ip.into());
+                }
+                Value::Mapping(entry)
+            })
+            .collect();
+        if applied < service_remaps.len() {
+            return Err(format!(
+                "{}: remapped ports not found as written in docker-compose.yml",
+                remap.service
+            ));
+        }
+        let mut definition = Mapping::new();
+        definition.insert(
+            "ports".into(),
+            Value::Tagged(Box::new(TaggedValue {
+                tag: Tag::new("override"),
+                value: Value::Sequence(entries),
+            })),
+        );
+        services.insert(key, Value::Mapping(definition));
+    }
+
+    let mut content = Mapping::new();
+    content.insert("services".into(), Value::Mapping(services));
+    content.insert(
+        REMAPS_KEY.into(),
+        serde_yaml::to_value(remaps).map_err(|e| e.to_string())?,
+    );
+    Ok(content)
+}
+
+/// Attaches every service of the project to the external `network`. Services
+/// that list no networks keep the project's default one; services with a
+/// `network_mode` cannot join networks and are left alone.
+pub async fn write_shared_network(
+    project: &str,
+    compose: &ComposeFile,
+    network: &str,
+) -> Result<(), String> {
+    let mut services = Mapping::new();
+    for (name, service) in &compose.services {
+        if service.network_mode.is_some() {
+            continue;
+        }
+        let mut networks = Mapping::new();
+        if service.networks.is_null() {
+            networks.insert("default".into(), Value::Mapping(Mapping::new()));
+        }
+        networks.insert(network.into(), Value::Mapping(Mapping::new()));
+        let mut definition = Mapping::new();
+        definition.insert("networks".into(), Value::Mapping(networks));
+        services.insert(name.as_str().into(), Value::Mapping(definition));
+    }
+
+    let mut external = Mapping::new();
+    external.insert("name".into(), network.into());
+    external.insert("external".into(), true.into());
+    let mut networks = Mapping::new();
+    networks.insert(network.into(), Value::Mapping(external));
+
+    let mut content = Mapping::new();
+    content.insert("services".into(), Value::Mapping(services));
+    content.insert("networks".into(), Value::Mapping(networks));
+    write(project, SHARED_NETWORK_FILE, content).await
+}
+
+/// Creates the shared bridge network unless it exists. Returns whether it
+/// had to be created.
+pub async fn ensure_network(network: &str) -> Result<bool, String> {
+    let mut cmd = Command::new("docker");
+    cmd.args(["network", "inspect", network]);
+    if run_capture(cmd).await.is_ok_and(|out| out.status.success()) {
+        return Ok(false);
+    }
+    let mut cmd = Command::new("docker");
+    cmd.args(["network", "create", "--driver", "bridge", network]);
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "docker-manager-overrides-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join(DIR)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn compose_files_lists_present_overrides_in_order() {
+        let dir = project_dir("files");
+        assert_eq!(compose_files(&dir), None);
+
+        std::fs::write(dir.join(DIR).join(PORTS_FILE), "").unwrap();
+        assert_eq!(
+            compose_files(&dir).as_deref(),
+            Some("docker-compose.yml:.docker-manager/ports.yml")
+        );
+
+        std::fs::write(dir.join(DIR).join(SHARED_NETWORK_FILE), "").unwrap();
+        std::fs::write(dir.join("docker-compose.override.yml"), "").unwrap();
+        assert_eq!(
+            compose_files(&dir).as_deref(),
+            Some(
+                "docker-compose.yml:docker-compose.override.yml:\
+                 .docker-manager/shared-network.yml:.docker-manager/ports.yml"
+            )
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn compose(yaml: &str) -> ComposeFile {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn remap(service: &str, container_port: &str, from: Option<u16>, to: u16) -> PortRemap {
+        PortRemap {
+            service: service.to_string(),
+            container_port: container_port.to_string(),
+            protocol: "tcp".to_string(),
+            from,
+            to,
+        }
+    }
+
+    #[test]
+    fn port_remaps_replace_only_the_remapped_entries() {
+        let compose = compose(
+            "services:
+  db:
+    image: postgres:16
+    ports:
+      - \"127.0.0.1:5432:5432\"
+      - \"9187:9187\"
+  cache:
+    image: redis:7
+    ports: [\"6379:6379\"]
+",
+        );
+        let remaps = [remap("db", "5432", Some(5432), 15432)];
+        let yaml =
+            serde_yaml::to_string(&port_remaps_override(&compose, &remaps).unwrap()).unwrap();
+        assert_eq!(
+            yaml,
+            "services:
+  db:
+    ports: !override
+    - target: 5432
+      published: '15432'
+      protocol: tcp
+      host_ip: 127.0.0.1
+    - 9187:9187
+x-docker-manager-port-remaps:
+- service: db
+  container_port: '5432'
+  protocol: tcp
+  from: 5432
+  to: 15432
+"
+        );
+
+        let content: Value = serde_yaml::from_str(&yaml).unwrap();
+        let recorded: Vec<PortRemap> =
+            serde_yaml::from_value(content.get(REMAPS_KEY).unwrap().clone()).unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert!(recorded[0].matches("db", "5432", "tcp"));
+    }
+
+    #[test]
+    fn port_remaps_refuse_ports_not_in_the_compose_file() {
+        let compose = compose("services:\n  db:\n    ports: [\"5432:5432\"]\n");
+        assert!(port_remaps_override(&compose, &[remap("db", "5432", Some(5433), 1)]).is_err());
+        assert!(port_remaps_override(&compose, &[remap("web", "80", None, 8080)]).is_err());
+    }
+}
@@ -0,0 +1,483 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::ErrorKind;
+use std::net::{TcpListener, UdpSocket};
+
+use serde::Deserialize;
+use tokio::process::Command;
+
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::process::run_capture;
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// Whether a host port could be bound when the ports were scanned.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Availability {
+    Free,
+    /// Held by something other than the project's own container.
+    InUse,
+    /// Privileged port or unknown address; nothing can be said.
+    Unknown,
+}
+
+/// One declared or published port of a managed project.
+#[derive(Clone)]
+pub struct PortEntry {
+    pub project: String,
+    pub service: String,
+    pub container: String,
+    pub container_port: String,
+    pub protocol: String,
+    /// Empty when the port listens on every address.
+    pub host_ip: String,
+    /// `None` when Docker picks the host port.
+    pub host_port: Option<u16>,
+    /// In the compose model; `false` for ports only seen on a container.
+    pub declared: bool,
+    /// Published by the project's running container right now.
+    pub bound: bool,
+    /// Checked only for declared ports that are not bound.
+    pub availability: Availability,
+    /// `project/service` of other entries that want the same host port.
+    pub conflicts: Vec<String>,
+}
+
+impl PortEntry {
+    pub fn host_display(&self) -> String {
+        let ip = if self.host_ip.is_empty() {
+            "*"
+        } else {
+            self.host_ip.as_str()
+        };
+        match self.host_port {
+            Some(port) => format!("{}:{}", ip, port),
+            None => format!("{}:random", ip),
+        }
+    }
+
+    fn owner(&self) -> String {
+        format!("{}/{}", self.project, self.service)
+    }
+}
+
+/// One host binding of a container port, as `docker inspect` reports it.
+#[derive(Clone)]
+pub struct Published {
+    pub host_ip: String,
+    pub host_port: Option<u16>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedContainer {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    config: Option<ContainerConfig>,
+    #[serde(default)]
+    network_settings: Option<NetworkSettings>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerConfig {
+    #[serde(default)]
+    labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct NetworkSettings {
+    /// `null` for ports that are exposed but not published.
+    #[serde(default)]
+    ports: Option<BTreeMap<String, Option<Vec<Binding>>>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Binding {
+    #[serde(default)]
+    host_ip: String,
+    #[serde(default)]
+    host_port: String,
+}
+
+impl InspectedContainer {
+    fn label(&self, key: &str) -> Option<&String> {
+        self.config.as_ref()?.labels.as_ref()?.get(key)
+    }
+
+    /// Every binding, plus a `None` host port for exposed ports that are not
+    /// published, so callers can tell them apart from missing ports.
+    fn published(&self) -> Vec<(String, Option<Published>)> {
+        let Some(ports) = self
+            .network_settings
+            .as_ref()
+            .and_then(|settings| settings.ports.as_ref())
+        else {
+            return Vec::new();
+        };
+        let mut published = Vec::new();
+        for (port, bindings) in ports {
+            match bindings {
+                Some(bindings) if !bindings.is_empty() => {
+                    for binding in bindings {
+                        published.push((
+                            port.clone(),
+                            Some(Published {
+                                host_ip: binding.host_ip.clone(),
+                                host_port: binding.host_port.parse().ok(),
+                            }),
+                        ));
+                    }
+                }
+                _ => published.push((port.clone(), None)),
+            }
+        }
+        published
+    }
+}
+
+async fn inspect(ids: &[String]) -> Vec<InspectedContainer> {
+    if ids.is_empty() {
+        return Vec::new();
+    }
+    let mut cmd = Command::new("docker");
+    cmd.args(["container", "inspect"]).args(ids);
+    match run_capture(cmd).await {
+        // Containers that stopped in between make the command fail, but the
+        // others are still printed.
+        Ok(out) => serde_json::from_slice(&out.stdout).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Port bindings of one container: `(port, Some(binding))` for each host
+/// binding and `(port, None)` for ports it only exposes.
+pub async fn container_ports(container: &str) -> Option<Vec<(String, Option<Published>)>> {
+    let container = inspect(&[container.to_string()]).await.into_iter().next()?;
+    Some(container.published())
+}
+
+/// Every port the managed `projects` declare or publish, with conflicts
+/// between them and host ports something else holds.
+pub async fn scan(projects: &[String]) -> Result<Vec<PortEntry>, String> {
+    let mut cmd = Command::new("docker");
+    cmd.args([
+        "ps",
+        "--quiet",
+        "--no-trunc",
+        "--filter",
+        &format!("label={}", PROJECT_LABEL),
+    ]);
+    let out = run_capture(cmd).await.map_err(|e| e.to_string())?;
+    if !out.status.success() {
+        return Err(String::from_utf8_lossy(&out.stderr).trim().to_string());
+    }
+    let ids: Vec<String> = String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    let running: Vec<InspectedContainer> = inspect(&ids)
+        .await
+        .into_iter()
+        .filter(|container| {
+            container
+                .label(PROJECT_LABEL)
+                .is_some_and(|project| projects.contains(project))
+        })
+        .collect();
+
+    let mut entries = Vec::new();
+    for project in projects {
+        let compose = match ComposeFile::resolved(project).await {
+            Some(compose) => Some(compose),
+            None => ComposeFile::load(project).await,
+        };
+        let Some(compose) = compose else {
+            continue;
+        };
+        for (service, definition) in &compose.services {
+            let container = definition
+                .container_name
+                .clone()
+                .unwrap_or_else(|| format!("{}-{}-1", project, service));
+            for port in &definition.ports {
+                let Some((host_ip, host_port, container_port, protocol)) = declared_port(port)
+                else {
+                    continue;
+                };
+                entries.push(PortEntry {
+                    project: project.clone(),
+                    service: service.clone(),
+                    container: container.clone(),
+                    container_port,
+                    protocol,
+                    host_ip,
+                    host_port,
+                    declared: true,
+                    bound: false,
+                    availability: Availability::Unknown,
+                    conflicts: Vec::new(),
+                });
+            }
+        }
+    }
+
+    for container in &running {
+        let name = container.name.trim_start_matches('/').to_string();
+        let project = container.label(PROJECT_LABEL).cloned().unwrap_or_default();
+        let service = container.label(SERVICE_LABEL).cloned().unwrap_or_default();
+        for (port, binding) in container.published() {
+            let Some(binding) = binding else {
+                continue;
+            };
+            let (container_port, protocol) = split_port(&port);
+            let host_ip = normalize_ip(&binding.host_ip);
+            // Docker publishes on IPv4 and IPv6 separately; both are one port.
+            if entries.iter().any(|entry| {
+                entry.container == name
+                    && entry.bound
+                    && entry.container_port == container_port
+                    && entry.protocol == protocol
+                    && entry.host_port == binding.host_port
+            }) {
+                continue;
+            }
+            let declared = entries.iter_mut().find(|entry| {
+                entry.container == name
+                    && !entry.bound
+                    && entry.container_port == container_port
+                    && entry.protocol == protocol
+                    && (entry.host_port.is_none() || entry.host_port == binding.host_port)
+            });
+            match declared {
+                Some(entry) => {
+                    entry.bound = true;
+                    entry.host_port = binding.host_port;
+                }
+                None => entries.push(PortEntry {
+                    project: project.clone(),
+                    service: service.clone(),
+                    container: name.clone(),
+                    container_port,
+                    protocol,
+                    host_ip,
+                    host_port: binding.host_port,
+                    declared: false,
+                    bound: true,
+                    availability: Availability::Unknown,
+                    conflicts: Vec::new(),
+                }),
+            }
+        }
+    }
+
+    for i in 0..entries.len() {
+        let mut conflicts: Vec<String> = entries
+            .iter()
+            .enumerate()
+            .filter(|(j, other)| {
+                *j != i && other.owner() != entries[i].owner() && overlaps(&entries[i], other)
+            })
+            .map(|(_, other)| other.owner())
+            .collect();
+        conflicts.sort();
+        conflicts.dedup();
+        entries[i].conflicts = conflicts;
+    }
+    for entry in entries.iter_mut().filter(|entry| !entry.bound) {
+        if let Some(port) = entry.host_port {
+            entry.availability = probe(&entry.host_ip, port, &entry.protocol);
+        }
+    }
+
+    entries.sort_by(|a, b| {
+        a.host_port
+            .cmp(&b.host_port)
+            .then_with(|| a.project.cmp(&b.project))
+            .then_with(|| a.service.cmp(&b.service))
+    });
+    Ok(entries)
+}
+
+/// The first host port above `entry`'s that no managed project wants and
+/// that can be bound now.
+pub fn suggest_port(entries: &[PortEntry], entry: &PortEntry) -> Option<u16> {
+    let start = entry.host_port.unwrap_or(8000).saturating_add(1);
+    (start..=u16::MAX).find(|&port| {
+        !entries.iter().any(|other| other.host_port == Some(port))
+            && probe(&entry.host_ip, port, &entry.protocol) == Availability::Free
+    })
+}
+
+/// `(host_ip, host_port, container_port, protocol)` of a compose `ports:`
+/// entry in short (`[ip:]host:container[/proto]`) or long syntax.
+pub fn declared_port(port: &serde_yaml::Value) -> Option<(String, Option<u16>, String, String)> {
+    match port {
+        serde_yaml::Value::Mapping(_) => {
+            let target = scalar(port.get("target")?)?;
+            let published = port.get("published").and_then(scalar);
+            let host_ip = port.get("host_ip").and_then(scalar).unwrap_or_default();
+            let protocol = port
+                .get("protocol")
+                .and_then(scalar)
+                .unwrap_or_else(|| "tcp".to_string());
+            Some((
+                normalize_ip(&host_ip),
+                published.and_then(|published| published.parse().ok()),
+                target,
+                protocol,
+            ))
+        }
+        short => {
+            let short = scalar(short)?;
+            let (mapping, protocol) = short.split_once('/').unwrap_or((&short, "tcp"));
+            // IPv6 host addresses are bracketed, so split from the right.
+            let mut parts = mapping.rsplitn(3, ':');
+            let container_port = parts.next()?.to_string();
+            let host_port = parts.next().and_then(|port| port.parse().ok());
+            let host_ip = parts.next().unwrap_or_default();
+            Some((
+                normalize_ip(host_ip.trim_matches(['[', ']'])),
+                host_port,
+                container_port,
+                protocol.to_string(),
+            ))
+        }
+    }
+}
+
+fn scalar(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(value) => Some(value.clone()),
+        serde_yaml::Value::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// `5432/tcp` into `("5432", "tcp")`.
+fn split_port(port: &str) -> (String, String) {
+    let (port, protocol) = port.split_once('/').unwrap_or((port, "tcp"));
+    (port.to_string(), protocol.to_string())
+}
+
+/// Every-address forms read as empty, so they compare equal.
+fn normalize_ip(ip: &str) -> String {
+    match ip {
+        "0.0.0.0" | "::" => String::new(),
+        ip => ip.to_string(),
+    }
+}
+
+fn overlaps(a: &PortEntry, b: &PortEntry) -> bool {
+    a.host_port.is_some()
+        && a.host_port == b.host_port
+        && a.protocol == b.protocol
+        && (a.host_ip.is_empty() || b.host_ip.is_empty() || a.host_ip == b.host_ip)
+}
+
+/// Tries to bind the host port the way Docker would.
+pub fn probe(host_ip: &str, port: u16, protocol: &str) -> Availability {
+    let ip = if host_ip.is_empty() {
+        "0.0.0.0"
+    } else {
+        host_ip
+    };
+    let result = match protocol {
+        "udp" => UdpSocket::bind((ip, port)).map(drop),
+        _ => TcpListener::bind((ip, port)).map(drop),
+    };
+    match result {
+        Ok(()) => Availability::Free,
+        Err(e) if e.kind() == ErrorKind::AddrInUse => Availability::InUse,
+        Err(_) => Availability::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declared(yaml: &str) -> Option<(String, Option<u16>, String, String)> {
+        declared_port(&serde_yaml::from_str(yaml).unwrap())
+    }
+
+    fn entry(service: &str, host_ip: &str, host_port: u16) -> PortEntry {
+        PortEntry {
+            project: "demo".to_string(),
+            service: service.to_string(),
+            container: format!("demo-{}-1", service),
+            container_port: "80".to_string(),
+            protocol: "tcp".to_string(),
+            host_ip: host_ip.to_string(),
+            host_port: Some(host_port),
+            declared: true,
+            bound: false,
+            availability: Availability::Unknown,
+            conflicts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn declared_port_reads_short_syntax() {
+        let port = |ip: &str, host: Option<u16>, container: &str, protocol: &str| {
+            Some((
+                ip.to_string(),
+                host,
+                container.to_string(),
+                protocol.to_string(),
+            ))
+        };
+        assert_eq!(declared("\"8080:80\""), port("", Some(8080), "80", "tcp"));
+        assert_eq!(declared("\"80\""), port("", None, "80", "tcp"));
+        assert_eq!(declared("5432"), port("", None, "5432", "tcp"));
+        assert_eq!(
+            declared("\"127.0.0.1:5353:53/udp\""),
+            port("127.0.0.1", Some(5353), "53", "udp")
+        );
+        assert_eq!(
+            declared("\"0.0.0.0:8080:80\""),
+            port("", Some(8080), "80", "tcp")
+        );
+        assert_eq!(
+            declared("\"[::1]:8080:80\""),
+            port("::1", Some(8080), "80", "tcp")
+        );
+    }
+
+    #[test]
+    fn declared_port_reads_long_syntax() {
+        assert_eq!(
+            declared("{target: 80, published: \"8080\", host_ip: 127.0.0.1, protocol: udp}"),
+            Some((
+                "127.0.0.1".to_string(),
+                Some(8080),
+                "80".to_string(),
+                "udp".to_string()
+            ))
+        );
+        assert_eq!(
+            declared("{target: 80}"),
+            Some((String::new(), None, "80".to_string(), "tcp".to_string()))
+        );
+        assert_eq!(declared("{published: 8080}"), None);
+    }
+
+    #[test]
+    fn suggest_port_skips_wanted_and_bound_ports() {
+        let held = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let taken = held.local_addr().unwrap().port();
+        let entries = vec![
+            entry("web", "127.0.0.1", taken - 1),
+            entry("api", "127.0.0.1", taken + 1),
+        ];
+
+        let suggested = suggest_port(&entries, &entries[0]).unwrap();
+        assert!(suggested > taken + 1);
+        assert!(probe("127.0.0.1", suggested, "tcp") == Availability::Free);
+    }
+}
@@ -1,29 +1,37 @@
-use std::io::{BufRead, BufReader};
-use std::process::{Command, Output, Stdio};
-use std::sync::{Arc, Mutex};
-use std::thread;
+use std::process::{Output, Stdio};
+use std::sync::Arc;
 
-type LineCallback = Arc<dyn Fn(&str) + Send + Sync + 'static>;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
 
-pub fn run_capture(mut cmd: Command) -> std::io::Result<Output> {
-    cmd.output()
+use crate::log_buffer::SharedLogBuffer;
+
+/// Maps each output line to what gets logged for it; `None` drops the line.
+pub type LineFilter = Arc<dyn Fn(&str) -> Option<String> + Send + Sync + 'static>;
+
+pub async fn run_capture(mut cmd: Command) -> std::io::Result<Output> {
+    // A cancelled job drops this future; take the child down with it.
+    cmd.kill_on_drop(true);
+    cmd.output().await
 }
 
-pub fn run_stream(
+pub async fn run_stream(
     cmd: Command,
-    logs: Arc<Mutex<String>>,
+    logs: SharedLogBuffer,
     header: Option<&str>,
 ) -> std::io::Result<bool> {
-    run_stream_with_line_callback(cmd, logs, header, None)
+    run_stream_with_line_filter(cmd, logs, header, None).await
 }
 
-pub fn run_stream_with_line_callback(
+pub async fn run_stream_with_line_filter(
     mut cmd: Command,
-    logs: Arc<Mutex<String>>,
+    logs: SharedLogBuffer,
     header: Option<&str>,
-    on_line: Option<LineCallback>,
+    filter: Option<LineFilter>,
 ) -> std::io::Result<bool> {
-    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
 
     let mut child = cmd.spawn()?;
     if let Some(header) = header {
@@ -31,38 +39,64 @@ pub fn run_stream_with_line_callback(
         logs_lock.push_str(header);
     }
 
-    if let Some(stdout) = child.stdout.take() {
-        let logs_stdout = Arc::clone(&logs);
-        let on_line_stdout = on_line.clone();
-        thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            for line in reader.lines().map_while(Result::ok) {
-                let mut logs_lock = logs_stdout.lock().unwrap();
-                logs_lock.push_str(&line);
-                logs_lock.push('\n');
-                if let Some(callback) = &on_line_stdout {
-                    callback(&line);
-                }
-            }
-        });
-    }
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    tokio::join!(
+        forward_lines(stdout, &logs, filter.as_ref()),
+        forward_lines(stderr, &logs, filter.as_ref()),
+    );
 
-    if let Some(stderr) = child.stderr.take() {
-        let logs_stderr = Arc::clone(&logs);
-        let on_line_stderr = on_line;
-        thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines().map_while(Result::ok) {
-                let mut logs_lock = logs_stderr.lock().unwrap();
-                logs_lock.push_str(&line);
-                logs_lock.push('\n');
-                if let Some(callback) = &on_line_stderr {
-                    callback(&line);
-                }
-            }
-        });
+    let status = child.wait().await?;
+    Ok(status.success())
+}
+
+/// Like [`run_stream`], but with stdin and/or stdout connected to files, for
+/// commands that produce or consume a dump. Stdin is closed when not given.
+pub async fn run_with_files(
+    mut cmd: Command,
+    stdin: Option<std::fs::File>,
+    stdout: Option<std::fs::File>,
+    logs: SharedLogBuffer,
+    header: Option<&str>,
+) -> std::io::Result<bool> {
+    cmd.stdin(stdin.map_or_else(Stdio::null, Stdio::from))
+        .stdout(stdout.map_or_else(Stdio::piped, Stdio::from))
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let mut child = cmd.spawn()?;
+    if let Some(header) = header {
+        logs.lock().unwrap().push_str(header);
     }
 
-    let status = child.wait()?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    tokio::join!(
+        forward_lines(stdout, &logs, None),
+        forward_lines(stderr, &logs, None),
+    );
+
+    let status = child.wait().await?;
     Ok(status.success())
 }
+
+async fn forward_lines(
+    stream: Option<impl AsyncRead + Unpin>,
+    logs: &SharedLogBuffer,
+    filter: Option<&LineFilter>,
+) {
+    let Some(stream) = stream else {
+        return;
+    };
+
+    let mut lines = BufReader::new(stream).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let logged = match filter {
+            Some(filter) => filter(&line),
+            None => Some(line),
+        };
+        if let Some(logged) = logged {
+            logs.lock().unwrap().push_line(logged.as_str());
+        }
+    }
+}
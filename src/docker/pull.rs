@@ -0,0 +1,389 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::docker::units::{format_bytes, parse_size_to_bytes};
+
+/// One line of `docker compose --progress json` output.
+#[derive(Deserialize)]
+struct JsonEvent {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    parent_id: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    current: u64,
+    #[serde(default)]
+    total: u64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LayerState {
+    Waiting,
+    Downloading,
+    Verifying,
+    Extracting,
+    Done,
+}
+
+impl LayerState {
+    fn from_text(text: &str) -> Option<Self> {
+        let state = if text.contains("Pulling fs layer") || text.contains("Waiting") {
+            LayerState::Waiting
+        } else if text.contains("Downloading") {
+            LayerState::Downloading
+        } else if text.contains("Verifying") || text.contains("Download complete") {
+            LayerState::Verifying
+        } else if text.contains("Extracting") {
+            LayerState::Extracting
+        } else if text.contains("Pull complete") || text.contains("Already exists") {
+            LayerState::Done
+        } else {
+            return None;
+        };
+        Some(state)
+    }
+}
+
+impl fmt::Display for LayerState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayerState::Waiting => write!(f, "waiting"),
+            LayerState::Downloading => write!(f, "downloading"),
+            LayerState::Verifying => write!(f, "verifying"),
+            LayerState::Extracting => write!(f, "extracting"),
+            LayerState::Done => write!(f, "done"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LayerProgress {
+    pub state: LayerState,
+    /// Downloaded bytes; stays at `total` once the download is complete.
+    pub current: u64,
+    pub total: u64,
+}
+
+#[derive(Clone, Default)]
+pub struct ImageProgress {
+    pub layers: BTreeMap<String, LayerProgress>,
+    pub done: bool,
+}
+
+impl ImageProgress {
+    pub fn bytes(&self) -> (u64, u64) {
+        self.layers
+            .values()
+            .fold((0, 0), |(current, total), layer| {
+                (current + layer.current, total + layer.total)
+            })
+    }
+
+    pub fn percent(&self) -> Option<u8> {
+        if self.done {
+            return Some(100);
+        }
+        let (current, total) = self.bytes();
+        percent_of(current, total)
+    }
+}
+
+/// What a line of pull output meant for the progress view.
+pub enum PullLine {
+    /// Not pull progress; log it as is.
+    Other,
+    /// Only a byte count moved, not worth a log line.
+    Tick,
+    /// A layer or image changed state; carries the line to log.
+    Changed(String),
+}
+
+/// Pull state for a whole project, fed line by line from compose's output.
+#[derive(Clone)]
+pub struct PullProgress {
+    pub images: BTreeMap<String, ImageProgress>,
+    first_byte_at: Option<Instant>,
+    /// Image that most recently started pulling, for plain output that does
+    /// not say which image a layer belongs to.
+    current_image: String,
+}
+
+impl Default for PullProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PullProgress {
+    pub fn new() -> Self {
+        Self {
+            images: BTreeMap::new(),
+            first_byte_at: None,
+            current_image: String::new(),
+        }
+    }
+
+    pub fn apply_line(&mut self, line: &str) -> PullLine {
+        let trimmed = line.trim();
+        if trimmed.starts_with('{')
+            && let Ok(event) = serde_json::from_str::<JsonEvent>(trimmed)
+        {
+            return self.apply_json(event);
+        }
+        self.apply_text(trimmed)
+    }
+
+    fn apply_json(&mut self, event: JsonEvent) -> PullLine {
+        if event.parent_id.is_empty() {
+            return self.apply_image(&event.id, &event.text);
+        }
+
+        let Some(state) = LayerState::from_text(&event.text) else {
+            return PullLine::Other;
+        };
+        let changed = self.apply_layer(
+            &event.parent_id,
+            &event.id,
+            state,
+            (state == LayerState::Downloading && event.total > 0)
+                .then_some((event.current, event.total)),
+        );
+        if changed {
+            PullLine::Changed(format!("{} {} {}", event.parent_id, event.id, event.text))
+        } else {
+            PullLine::Tick
+        }
+    }
+
+    fn apply_text(&mut self, line: &str) -> PullLine {
+        let (id, text) = match line.split_once(": ") {
+            Some((id, text)) if !id.contains(' ') => (id, text),
+            _ => match line.split_once(' ') {
+                Some((id, text)) => (id, text.trim_start()),
+                None => return PullLine::Other,
+            },
+        };
+
+        if !is_layer_id(id) {
+            return self.apply_image(id, text);
+        }
+
+        let Some(state) = LayerState::from_text(text) else {
+            return PullLine::Other;
+        };
+        let image = self.current_image.clone();
+        let bytes = if state == LayerState::Downloading {
+            size_ratio(text)
+        } else {
+            None
+        };
+        if self.apply_layer(&image, id, state, bytes) {
+            PullLine::Changed(line.to_string())
+        } else {
+            PullLine::Tick
+        }
+    }
+
+    fn apply_image(&mut self, id: &str, text: &str) -> PullLine {
+        let text = text.trim();
+        if id.is_empty() || !matches!(text, "Pulling" | "Pulled" | "Skipped") {
+            return PullLine::Other;
+        }
+
+        let image = self.images.entry(id.to_string()).or_default();
+        if text == "Pulling" {
+            self.current_image = id.to_string();
+        } else {
+            image.done = true;
+            for layer in image.layers.values_mut() {
+                layer.state = LayerState::Done;
+                layer.current = layer.total;
+            }
+        }
+        PullLine::Changed(format!("{} {}", id, text))
+    }
+
+    /// Returns whether the layer is new or changed state, as opposed to just
+    /// counting bytes.
+    fn apply_layer(
+        &mut self,
+        image: &str,
+        id: &str,
+        state: LayerState,
+        bytes: Option<(u64, u64)>,
+    ) -> bool {
+        let layers = &mut self.images.entry(image.to_string()).or_default().layers;
+        let is_new = !layers.contains_key(id);
+        let layer = layers.entry(id.to_string()).or_insert(LayerProgress {
+            state: LayerState::Waiting,
+            current: 0,
+            total: 0,
+        });
+
+        let changed = is_new || layer.state != state;
+        layer.state = state;
+        if let Some((current, total)) = bytes {
+            layer.current = current.min(total);
+            layer.total = total;
+            if current > 0 && self.first_byte_at.is_none() {
+                self.first_byte_at = Some(Instant::now());
+            }
+        } else if !matches!(state, LayerState::Waiting | LayerState::Downloading) {
+            layer.current = layer.total;
+        }
+        changed
+    }
+
+    /// Downloaded and total bytes across every layer seen so far.
+    pub fn bytes(&self) -> (u64, u64) {
+        self.images
+            .values()
+            .fold((0, 0), |(current, total), image| {
+                let (image_current, image_total) = image.bytes();
+                (current + image_current, total + image_total)
+            })
+    }
+
+    pub fn layer_counts(&self) -> (usize, usize) {
+        self.images
+            .values()
+            .flat_map(|image| image.layers.values())
+            .fold((0, 0), |(done, all), layer| {
+                (done + usize::from(layer.state == LayerState::Done), all + 1)
+            })
+    }
+
+    /// Remaining download time at the average rate since the first byte.
+    pub fn eta(&self) -> Option<Duration> {
+        let (current, total) = self.bytes();
+        let elapsed = self.first_byte_at?.elapsed().as_secs_f64();
+        if current == 0 || current >= total || elapsed <= 0.0 {
+            return None;
+        }
+        let rate = current as f64 / elapsed;
+        Some(Duration::from_secs_f64((total - current) as f64 / rate))
+    }
+
+    /// One-line summary used for the job list, the progress bar and the web
+    /// dashboard. Keeps an `NN%` token so the bar can be drawn from it.
+    pub fn summary(&self) -> String {
+        let (current, total) = self.bytes();
+        let (done_layers, layers) = self.layer_counts();
+        let mut summary = match percent_of(current, total) {
+            Some(percent) => format!(
+                "{}% {}/{}",
+                percent,
+                format_bytes(current as f64),
+                format_bytes(total as f64)
+            ),
+            None => "waiting".to_string(),
+        };
+        if let Some(eta) = self.eta() {
+            let secs = eta.as_secs();
+            summary.push_str(&format!(" ETA {}:{:02}", secs / 60, secs % 60));
+        }
+        if layers > 0 {
+            summary.push_str(&format!(" ({}/{} layers)", done_layers, layers));
+        }
+        summary
+    }
+}
+
+fn percent_of(current: u64, total: u64) -> Option<u8> {
+    (total > 0).then(|| ((current as f64 / total as f64) * 100.0).round().min(100.0) as u8)
+}
+
+fn is_layer_id(id: &str) -> bool {
+    id.len() == 12 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn size_ratio(text: &str) -> Option<(u64, u64)> {
+    text.split_whitespace().find_map(|token| {
+        let (left, right) = token.split_once('/')?;
+        let current = parse_size_to_bytes(left)?;
+        let total = parse_size_to_bytes(right)?;
+        Some((current as u64, total as u64))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer<'a>(progress: &'a PullProgress, image: &str, id: &str) -> &'a LayerProgress {
+        &progress.images[image].layers[id]
+    }
+
+    #[test]
+    fn plain_output_tracks_layers_of_the_current_image() {
+        let mut progress = PullProgress::new();
+        assert!(matches!(
+            progress.apply_line(" db Pulling "),
+            PullLine::Changed(_)
+        ));
+        assert!(matches!(
+            progress.apply_line("a1b2c3d4e5f6 Pulling fs layer"),
+            PullLine::Changed(_)
+        ));
+        assert!(matches!(
+            progress.apply_line("a1b2c3d4e5f6 Downloading [=>   ]  10MB/40MB"),
+            PullLine::Changed(_)
+        ));
+        assert!(matches!(
+            progress.apply_line("a1b2c3d4e5f6 Downloading [==>  ]  20MB/40MB"),
+            PullLine::Tick
+        ));
+
+        let downloading = layer(&progress, "db", "a1b2c3d4e5f6");
+        assert!(downloading.state == LayerState::Downloading);
+        assert_eq!(
+            (downloading.current, downloading.total),
+            (20_000_000, 40_000_000)
+        );
+        assert_eq!(progress.images["db"].percent(), Some(50));
+
+        progress.apply_line("a1b2c3d4e5f6 Pull complete");
+        progress.apply_line("db Pulled");
+        assert_eq!(progress.layer_counts(), (1, 1));
+        assert_eq!(progress.images["db"].percent(), Some(100));
+        assert_eq!(progress.bytes(), (40_000_000, 40_000_000));
+    }
+
+    #[test]
+    fn json_output_uses_parent_ids_and_byte_counts() {
+        let mut progress = PullProgress::new();
+        progress.apply_line(r#"{"id":"web","text":"Pulling"}"#);
+        progress.apply_line(
+            r#"{"id":"0123456789ab","parent_id":"web","text":"Downloading","current":5,"total":10}"#,
+        );
+        assert!(matches!(
+            progress.apply_line(
+                r#"{"id":"0123456789ab","parent_id":"web","text":"Downloading","current":8,"total":10}"#
+            ),
+            PullLine::Tick
+        ));
+        assert_eq!(layer(&progress, "web", "0123456789ab").current, 8);
+        assert!(progress.summary().starts_with("80% "));
+    }
+
+    #[test]
+    fn unrelated_lines_are_logged_as_is() {
+        let mut progress = PullProgress::new();
+        assert!(matches!(
+            progress.apply_line("Network demo_default Created"),
+            PullLine::Other
+        ));
+        assert!(matches!(progress.apply_line(""), PullLine::Other));
+        assert!(matches!(
+            progress.apply_line("a1b2c3d4e5f6 something odd"),
+            PullLine::Other
+        ));
+        assert!(progress.images.is_empty());
+        assert_eq!(progress.summary(), "waiting");
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+
+use crate::docker::events::resolve_project_from_container;
+use crate::docker::units::parse_size_to_bytes;
+use crate::notify::{AppMessage, Notifier};
+
+/// Containers that have not reported for this long are considered gone.
+const STALE_AFTER: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Default)]
+pub struct StatsSample {
+    pub cpu_percent: f64,
+    pub mem_bytes: f64,
+    pub mem_limit: f64,
+    pub net_rx: f64,
+    pub net_tx: f64,
+    pub block_read: f64,
+    pub block_write: f64,
+}
+
+pub struct ContainerStats {
+    pub history: VecDeque<StatsSample>,
+    pub last_seen: Instant,
+}
+
+impl ContainerStats {
+    pub fn latest(&self) -> Option<&StatsSample> {
+        self.history.back()
+    }
+
+    pub fn is_fresh(&self) -> bool {
+        self.last_seen.elapsed() < STALE_AFTER
+    }
+
+    pub fn cpu_series(&self) -> Vec<u64> {
+        // Hundredths of a percent keep low-usage containers visible.
+        self.history
+            .iter()
+            .map(|sample| (sample.cpu_percent * 100.0) as u64)
+            .collect()
+    }
+
+    pub fn mem_series(&self) -> Vec<u64> {
+        self.history
+            .iter()
+            .map(|sample| sample.mem_bytes as u64)
+            .collect()
+    }
+
+    /// Net and block I/O are cumulative counters, so the sparkline shows the
+    /// delta between consecutive samples.
+    pub fn net_series(&self) -> Vec<u64> {
+        self.deltas(|sample| sample.net_rx + sample.net_tx)
+    }
+
+    pub fn block_series(&self) -> Vec<u64> {
+        self.deltas(|sample| sample.block_read + sample.block_write)
+    }
+
+    fn deltas(&self, value: impl Fn(&StatsSample) -> f64) -> Vec<u64> {
+        self.history
+            .iter()
+            .zip(self.history.iter().skip(1))
+            .map(|(previous, current)| (value(current) - value(previous)).max(0.0) as u64)
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct ProjectStats {
+    pub containers: BTreeMap<String, ContainerStats>,
+}
+
+impl ProjectStats {
+    pub fn totals(&self) -> Option<StatsSample> {
+        let mut fresh = self
+            .containers
+            .values()
+            .filter(|container| container.is_fresh())
+            .filter_map(ContainerStats::latest)
+            .peekable();
+        fresh.peek()?;
+
+        Some(fresh.fold(StatsSample::default(), |mut total, sample| {
+            total.cpu_percent += sample.cpu_percent;
+            total.mem_bytes += sample.mem_bytes;
+            total.mem_limit = total.mem_limit.max(sample.mem_limit);
+            total.net_rx += sample.net_rx;
+            total.net_tx += sample.net_tx;
+            total.block_read += sample.block_read;
+            total.block_write += sample.block_write;
+            total
+        }))
+    }
+
+    pub fn record(&mut self, container_name: &str, sample: StatsSample, history_len: usize) {
+        let entry = self
+            .containers
+            .entry(container_name.to_string())
+            .or_insert_with(|| ContainerStats {
+                history: VecDeque::with_capacity(history_len),
+                last_seen: Instant::now(),
+            });
+
+        let history_len = history_len.max(2);
+        if entry.history.len() >= history_len {
+            entry.history.pop_front();
+        }
+        entry.history.push_back(sample);
+        entry.last_seen = Instant::now();
+
+        self.containers.retain(|_, container| container.is_fresh());
+    }
+}
+
+pub struct StatsListenerHandle {
+    task: JoinHandle<()>,
+}
+
+impl StatsListenerHandle {
+    pub fn signal_shutdown(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Deserialize)]
+struct StatsLine {
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "CPUPerc")]
+    cpu_percent: String,
+    #[serde(rename = "MemUsage")]
+    mem_usage: String,
+    #[serde(rename = "NetIO")]
+    net_io: String,
+    #[serde(rename = "BlockIO")]
+    block_io: String,
+}
+
+pub fn spawn_stats_listener(projects: HashSet<String>, notifier: Notifier) -> StatsListenerHandle {
+    let task = tokio::spawn(async move {
+        let mut container_projects: HashMap<String, Option<String>> = HashMap::new();
+
+        loop {
+            let mut cmd = Command::new("docker");
+            cmd.arg("stats")
+                .arg("--format")
+                .arg("{{json .}}")
+                .kill_on_drop(true);
+
+            match cmd.stdout(Stdio::piped()).stderr(Stdio::null()).spawn() {
+                Ok(mut child) => {
+                    if let Some(stdout) = child.stdout.take() {
+                        let mut lines = BufReader::new(stdout).lines();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            handle_stats_line(&line, &projects, &mut container_projects, &notifier)
+                                .await;
+                        }
+                    }
+                    let _ = child.wait().await;
+                }
+                Err(_) => {
+                    // Docker stats stream unavailable, retry shortly.
+                }
+            }
+
+            tokio::time::sleep(Duration::from_millis(1000)).await;
+        }
+    });
+
+    StatsListenerHandle { task }
+}
+
+async fn handle_stats_line(
+    line: &str,
+    projects: &HashSet<String>,
+    container_projects: &mut HashMap<String, Option<String>>,
+    notifier: &Notifier,
+) {
+    // The streaming output redraws the table with ANSI clear-screen sequences.
+    let Some(json_start) = line.find('{') else {
+        return;
+    };
+    let Ok(stats) = serde_json::from_str::<StatsLine>(&line[json_start..]) else {
+        return;
+    };
+
+    if !container_projects.contains_key(&stats.name) {
+        let project = resolve_project_from_container(&stats.name).await;
+        container_projects.insert(stats.name.clone(), project);
+    }
+    let Some(project) = container_projects
+        .get(&stats.name)
+        .and_then(Option::as_ref)
+        .filter(|project| projects.contains(*project))
+    else {
+        return;
+    };
+
+    let (mem_bytes, mem_limit) = parse_pair(&stats.mem_usage);
+    let (net_rx, net_tx) = parse_pair(&stats.net_io);
+    let (block_read, block_write) = parse_pair(&stats.block_io);
+    let sample = StatsSample {
+        cpu_percent: stats
+            .cpu_percent
+            .trim_end_matches('%')
+            .parse()
+            .unwrap_or(0.0),
+        mem_bytes,
+        mem_limit,
+        net_rx,
+        net_tx,
+        block_read,
+        block_write,
+    };
+
+    notifier.send(AppMessage::Stats {
+        service: project.clone(),
+        container: stats.name,
+        sample,
+    });
+}
+
+fn parse_pair(value: &str) -> (f64, f64) {
+    let (left, right) = value.split_once('/').unwrap_or((value, ""));
+    (
+        parse_size_to_bytes(left.trim()).unwrap_or(0.0),
+        parse_size_to_bytes(right.trim()).unwrap_or(0.0),
+    )
+}
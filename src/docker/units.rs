@@ -0,0 +1,49 @@
+pub fn parse_size_to_bytes(token: &str) -> Option<f64> {
+    let cleaned = token.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '.');
+    if cleaned.is_empty() {
+        return None;
+    }
+
+    let mut split_idx = cleaned.len();
+    for (idx, ch) in cleaned.char_indices() {
+        if !(ch.is_ascii_digit() || ch == '.') {
+            split_idx = idx;
+            break;
+        }
+    }
+
+    let number = cleaned[..split_idx].parse::<f64>().ok()?;
+    let unit = cleaned[split_idx..].to_ascii_lowercase();
+
+    let multiplier = match unit.as_str() {
+        "" | "b" => 1.0,
+        "kb" => 1_000.0,
+        "mb" => 1_000_000.0,
+        "gb" => 1_000_000_000.0,
+        "tb" => 1_000_000_000_000.0,
+        "kib" => 1_024.0,
+        "mib" => 1_048_576.0,
+        "gib" => 1_073_741_824.0,
+        "tib" => 1_099_511_627_776.0,
+        _ => return None,
+    };
+
+    Some(number * multiplier)
+}
+
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes.max(0.0);
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{:.0}{}", value, UNITS[unit])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::docker::compose_file::ComposeFile;
+use crate::docker::process::run_capture;
+
+/// Where a helper container sees each mount and the snapshot directory.
+const DATA_ROOT: &str = "/data";
+const BACKUP_ROOT: &str = "/backup";
+
+#[derive(Clone)]
+pub enum MountSource {
+    /// Named volume, by its Docker name (`<project>_<key>` unless renamed).
+    Volume(String),
+    /// Host directory, absolute.
+    Bind(PathBuf),
+}
+
+/// A named volume or bind-mounted directory holding a project's data.
+#[derive(Clone)]
+pub struct DataMount {
+    /// Directory name for this mount inside snapshot archives.
+    pub key: String,
+    /// The source as written in the compose file.
+    pub label: String,
+    pub source: MountSource,
+    /// Compose services that mount it.
+    pub services: Vec<String>,
+    /// Whether wipes and restores may empty it: named volumes, and bind
+    /// mounts that resolve inside the project directory. A bind of `~/code`
+    /// or `/srv` is the user's own data and is only ever read.
+    pub erasable: bool,
+}
+
+impl DataMount {
+    fn mount_arg(&self) -> String {
+        format!("{}:{}/{}", self.target(), DATA_ROOT, self.key)
+    }
+
+    /// Docker volume name or absolute host path.
+    pub fn target(&self) -> String {
+        match &self.source {
+            MountSource::Volume(name) => name.clone(),
+            MountSource::Bind(path) => path.display().to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self.source {
+            MountSource::Volume(_) => "volume",
+            MountSource::Bind(_) => "bind",
+        }
+    }
+}
+
+/// Volumes and directory bind mounts of a project. Anonymous volumes and
+/// single-file binds (config files) are not data and are left out.
+pub fn data_mounts(project: &str, compose: &ComposeFile) -> Vec<DataMount> {
+    let project_dir = std::env::current_dir()
+        .unwrap_or_default()
+        .join("containers")
+        .join(project);
+    let mut mounts: BTreeMap<String, DataMount> = BTreeMap::new();
+
+    for (service, definition) in &compose.services {
+        for entry in &definition.volumes {
+            let Some((kind, source)) = mount_source(entry) else {
+                continue;
+            };
+            let (key, label, source, erasable) = if kind == "bind" {
+                let path = resolve_bind(&project_dir, &source);
+                if path.is_file() {
+                    continue;
+                }
+                let erasable = inside(&project_dir, &path);
+                (
+                    format!("bind-{}", sanitize(&source)),
+                    source,
+                    MountSource::Bind(path),
+                    erasable,
+                )
+            } else {
+                let name = volume_name(project, compose, &source);
+                (
+                    format!("volume-{}", sanitize(&source)),
+                    source,
+                    MountSource::Volume(name),
+                    true,
+                )
+            };
+            mounts
+                .entry(key.clone())
+                .or_insert_with(|| DataMount {
+                    key,
+                    label,
+                    source,
+                    services: Vec::new(),
+                    erasable,
+                })
+                .services
+                .push(service.clone());
+        }
+    }
+    mounts.into_values().collect()
+}
+
+/// `(type, source)` of one service volume entry, or `None` for anonymous
+/// volumes and types that hold no data (tmpfs, npipe).
+fn mount_source(entry: &serde_yaml::Value) -> Option<(&'static str, String)> {
+    let (kind, source) = match entry {
+        serde_yaml::Value::String(short) => {
+            let (source, _) = short.split_once(':')?;
+            let kind = if source.starts_with(['.', '/', '~']) {
+                "bind"
+            } else {
+                "volume"
+            };
+            (kind, source.to_string())
+        }
+        long => {
+            let kind = match long.get("type")?.as_str()? {
+                "bind" => "bind",
+                "volume" => "volume",
+                _ => return None,
+            };
+            (kind, long.get("source")?.as_str()?.to_string())
+        }
+    };
+    (!source.is_empty()).then_some((kind, source))
+}
+
+fn resolve_bind(project_dir: &Path, source: &str) -> PathBuf {
+    if let Some(rest) = source.strip_prefix("~/")
+        && let Some(home) = std::env::var_os("HOME")
+    {
+        return PathBuf::from(home).join(rest);
+    }
+    let path = project_dir.join(source);
+    // Drop `.` components so the path reads well and works as a mount source.
+    path.components().filter(|c| c.as_os_str() != ".").collect()
+}
+
+/// Whether `path` lies inside `dir` once `..` and symlinks are resolved. A
+/// path that does not exist yet is judged by its nearest existing parent.
+fn inside(dir: &Path, path: &Path) -> bool {
+    let Ok(dir) = dir.canonicalize() else {
+        return false;
+    };
+    let mut existing = path;
+    let mut rest = Vec::new();
+    let resolved = loop {
+        if let Ok(resolved) = existing.canonicalize() {
+            break resolved;
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name);
+                existing = parent;
+            }
+            _ => return false,
+        }
+    };
+    let full = rest
+        .iter()
+        .rev()
+        .fold(resolved, |full, name| full.join(name));
+    full.starts_with(&dir) && full != dir
+}
+
+/// Splits mounts into those wipes and restores may empty and those they
+/// must leave alone.
+pub fn partition_erasable(mounts: Vec<DataMount>) -> (Vec<DataMount>, Vec<DataMount>) {
+    mounts.into_iter().partition(|mount| mount.erasable)
+}
+
+fn volume_name(project: &str, compose: &ComposeFile, key: &str) -> String {
+    let Some(Some(volume)) = compose.volumes.get(key) else {
+        return format!("{}_{}", project, key);
+    };
+    if let Some(name) = &volume.name {
+        return name.clone();
+    }
+    match &volume.external {
+        Some(serde_yaml::Value::Bool(true)) => key.to_string(),
+        Some(external) => external
+            .get("name")
+            .and_then(|name| name.as_str())
+            .map_or_else(|| format!("{}_{}", project, key), str::to_string),
+        None => format!("{}_{}", project, key),
+    }
+}
+
+fn sanitize(source: &str) -> String {
+    let cleaned: String = source
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    cleaned.trim_matches('_').to_string()
+}
+
+/// Mounts that exist, so measuring or archiving them does not create empty
+/// volumes or root-owned directories as a side effect.
+pub async fn present_mounts(mounts: &[DataMount]) -> Vec<DataMount> {
+    let mut present = Vec::new();
+    for mount in mounts {
+        let exists = match &mount.source {
+            MountSource::Bind(path) => path.is_dir(),
+            MountSource::Volume(name) => {
+                let mut cmd = Command::new("docker");
+                cmd.arg("volume").arg("inspect").arg(name);
+                run_capture(cmd).await.is_ok_and(|out| out.status.success())
+            }
+        };
+        if exists {
+            present.push(mount.clone());
+        }
+    }
+    present
+}
+
+/// `docker run --rm` with every mount under `/data` and, if given, the
+/// snapshot directory under `/backup`.
+fn helper_cmd(image: &str, mounts: &[DataMount], backup_dir: Option<&Path>) -> Command {
+    let mut cmd = Command::new("docker");
+    cmd.arg("run").arg("--rm");
+    for mount in mounts {
+        cmd.arg("-v").arg(mount.mount_arg());
+    }
+    if let Some(dir) = backup_dir {
+        cmd.arg("-v")
+            .arg(format!("{}:{}", dir.display(), BACKUP_ROOT));
+    }
+    cmd.arg(image);
+    cmd
+}
+
+/// Bytes used by each mount, keyed by [`DataMount::key`]; missing entries
+/// could not be measured.
+pub async fn mount_sizes(image: &str, mounts: &[DataMount]) -> BTreeMap<String, u64> {
+    if mounts.is_empty() {
+        return BTreeMap::new();
+    }
+    let mut cmd = helper_cmd(image, mounts, None);
+    cmd.arg("du").arg("-sk");
+    for mount in mounts {
+        cmd.arg(format!("{}/{}", DATA_ROOT, mount.key));
+    }
+    let Ok(out) = run_capture(cmd).await else {
+        return BTreeMap::new();
+    };
+
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (kib, path) = line.split_once(char::is_whitespace)?;
+            let key = path.trim().strip_prefix(DATA_ROOT)?.trim_start_matches('/');
+            Some((key.to_string(), kib.parse::<u64>().ok()? * 1024))
+        })
+        .collect()
+}
+
+/// Tars every mount into `<backup_dir>/<archive>`, one top-level directory
+/// per mount key.
+pub fn snapshot_cmd(
+    image: &str,
+    mounts: &[DataMount],
+    backup_dir: &Path,
+    archive: &str,
+) -> Command {
+    let mut cmd = helper_cmd(image, mounts, Some(backup_dir));
+    cmd.arg("tar")
+        .arg("-cf")
+        .arg(format!("{}/{}", BACKUP_ROOT, archive))
+        .arg("-C")
+        .arg(DATA_ROOT);
+    for mount in mounts {
+        cmd.arg(&mount.key);
+    }
+    cmd
+}
+
+/// Mount keys a snapshot holds, from its top-level directories.
+pub async fn archive_keys(archive: &Path) -> Option<BTreeSet<String>> {
+    let mut cmd = Command::new("tar");
+    cmd.arg("-tf").arg(archive);
+    let out = run_capture(cmd).await.ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter_map(|entry| entry.trim_start_matches("./").split('/').next())
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Empties every erasable mount, then unpacks `<backup_dir>/<archive>` over
+/// them; other mounts are not even attached to the helper. The archive name
+/// reaches the script as `$1`, never as script text.
+pub fn restore_cmd(
+    image: &str,
+    mounts: &[DataMount],
+    backup_dir: &Path,
+    archive: &str,
+) -> Result<Command, String> {
+    if archive.is_empty() || archive.contains('/') || archive == ".." {
+        return Err(format!("invalid snapshot name: {}", archive));
+    }
+    let mounts = erasable(mounts);
+    let mut cmd = helper_shell(image, &mounts, Some(backup_dir));
+    let script = wipe_script(&mounts);
+    cmd.arg(format!(
+        "{} && tar -xf \"{}/$1\" -C {}",
+        script, BACKUP_ROOT, DATA_ROOT
+    ))
+    .arg("sh")
+    .arg(archive);
+    Ok(cmd)
+}
+
+/// Deletes the contents of every erasable mount, keeping the mount points.
+pub fn wipe_cmd(image: &str, mounts: &[DataMount]) -> Command {
+    let mounts = erasable(mounts);
+    let mut cmd = helper_shell(image, &mounts, None);
+    cmd.arg(wipe_script(&mounts));
+    cmd
+}
+
+fn erasable(mounts: &[DataMount]) -> Vec<DataMount> {
+    mounts
+        .iter()
+        .filter(|mount| mount.erasable)
+        .cloned()
+        .collect()
+}
+
+fn helper_shell(image: &str, mounts: &[DataMount], backup_dir: Option<&Path>) -> Command {
+    let mut cmd = helper_cmd(image, mounts, backup_dir);
+    cmd.arg("sh").arg("-c");
+    cmd
+}
+
+fn wipe_script(mounts: &[DataMount]) -> String {
+    mounts
+        .iter()
+        .map(|mount| format!("find {}/{} -mindepth 1 -delete", DATA_ROOT, mount.key))
+        .collect::<Vec<_>>()
+        .join(" && ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mount(key: &str, source: MountSource, erasable: bool) -> DataMount {
+        DataMount {
+            key: key.to_string(),
+            label: key.to_string(),
+            source,
+            services: vec!["app".to_string()],
+            erasable,
+        }
+    }
+
+    #[test]
+    fn only_paths_below_the_project_are_inside_it() {
+        let dir =
+            std::env::temp_dir().join(format!("docker-manager-volumes-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("project/db")).unwrap();
+        let project = dir.join("project");
+
+        assert!(inside(&project, &project.join("db")));
+        assert!(inside(&project, &project.join("not-created-yet/data")));
+        assert!(!inside(&project, &project));
+        assert!(!inside(&project, &project.join("../elsewhere")));
+        assert!(!inside(&project, &dir));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn mount_source_classifies_short_and_long_entries() {
+        let source = |yaml: &str| mount_source(&serde_yaml::from_str(yaml).unwrap());
+        assert_eq!(
+            source("./db:/var/lib/db"),
+            Some(("bind", "./db".to_string()))
+        );
+        assert_eq!(
+            source("~/code:/code:ro"),
+            Some(("bind", "~/code".to_string()))
+        );
+        assert_eq!(
+            source("pgdata:/var/lib/postgresql/data"),
+            Some(("volume", "pgdata".to_string()))
+        );
+        assert_eq!(source("/var/lib/anonymous"), None);
+        assert_eq!(
+            source("{type: bind, source: /srv, target: /srv}"),
+            Some(("bind", "/srv".to_string()))
+        );
+        assert_eq!(source("{type: tmpfs, target: /tmp}"), None);
+    }
+
+    #[test]
+    fn wipes_never_attach_mounts_outside_the_project() {
+        let mounts = [
+            mount(
+                "volume-pgdata",
+                MountSource::Volume("demo_pgdata".to_string()),
+                true,
+            ),
+            mount(
+                "bind-code",
+                MountSource::Bind(PathBuf::from("/home/me/code")),
+                false,
+            ),
+        ];
+        let cmd = wipe_cmd("busybox", &mounts);
+        let args: Vec<String> = cmd
+            .as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert!(args.contains(&"demo_pgdata:/data/volume-pgdata".to_string()));
+        assert!(
+            !args
+                .iter()
+                .any(|arg| arg.contains("/home/me/code") || arg.contains("bind-code"))
+        );
+        assert_eq!(
+            args.last().unwrap(),
+            "find /data/volume-pgdata -mindepth 1 -delete"
+        );
+    }
+
+    #[test]
+    fn restore_passes_the_archive_name_as_an_argument() {
+        let mounts = [mount(
+            "volume-pgdata",
+            MountSource::Volume("demo_pgdata".to_string()),
+            true,
+        )];
+        let name = "snap shot; $(reboot) 'x'.tar";
+        let cmd = restore_cmd("busybox", &mounts, Path::new("/tmp/snaps"), name).unwrap();
+        let args: Vec<String> = cmd
+            .as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        let script = &args[args.len() - 3];
+        assert!(!script.contains("snap shot"));
+        assert!(script.ends_with("tar -xf \"/backup/$1\" -C /data"));
+        assert_eq!(args[args.len() - 2..], ["sh".to_string(), name.to_string()]);
+
+        assert!(restore_cmd("busybox", &mounts, Path::new("/tmp"), "../etc/x.tar").is_err());
+        assert!(restore_cmd("busybox", &mounts, Path::new("/tmp"), "").is_err());
+    }
+}
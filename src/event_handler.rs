@@ -1,36 +1,99 @@
 use std::io;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use ratatui::crossterm::event::{self, KeyCode, KeyEventKind};
+use futures::StreamExt;
+use ratatui::crossterm::event::{Event, EventStream, KeyCode, KeyEventKind};
+use tokio::sync::mpsc::UnboundedReceiver;
 
 use crate::app::{App, DaemonAction, Focus, LogTab};
+use crate::notify::AppMessage;
 use crate::status::{Status, ToastState};
 
-pub async fn handle_events(app: &mut App, poll_timeout: Duration) -> io::Result<bool> {
-    app.animation_tick = app.animation_tick.wrapping_add(1);
-    if app.daemon_probe_cooldown_ticks > 0 {
-        app.daemon_probe_cooldown_ticks = app.daemon_probe_cooldown_ticks.saturating_sub(1);
-    }
-
-    let keys = Keys::from_app(app);
+/// Animation cadence while something on screen is moving.
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
 
-    if event::poll(poll_timeout)? {
-        let event = event::read()?;
-        if let event::Event::Key(key) = event
-            && key.kind == KeyEventKind::Press
-            && !handle_key(app, key.code, &keys)
-        {
-            return Ok(false);
+/// Waits for input, a background update or the next deadline, then applies
+/// everything that arrived in the meantime. Returns `false` when the app should quit.
+pub async fn handle_events(
+    app: &mut App,
+    input: &mut EventStream,
+    messages: &mut UnboundedReceiver<AppMessage>,
+) -> io::Result<bool> {
+    let wakeup = next_wakeup(app);
+    let deadline = async {
+        match wakeup {
+            Some(timeout) => tokio::time::sleep(timeout).await,
+            None => std::future::pending().await,
         }
-    } else {
-        refresh_if_transitioning(app);
+    };
+
+    let mut first = None;
+    tokio::select! {
+        event = input.next() => match event {
+            Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                let keys = Keys::from_app(app);
+                if !handle_key(app, key.code, &keys) {
+                    return Ok(false);
+                }
+            }
+            Some(Ok(_)) => {}
+            Some(Err(err)) => return Err(err),
+            None => return Ok(false),
+        },
+        message = messages.recv() => match message {
+            Some(message) => first = Some(message),
+            None => return Ok(false),
+        },
+        _ = deadline => {}
     }
+    app.notifier.acknowledge();
 
+    if let Some(message) = first {
+        app.handle_message(message);
+    }
+    while let Ok(message) = messages.try_recv() {
+        app.handle_message(message);
+    }
+
+    refresh_if_transitioning(app);
+    app.check_idle_projects();
+    app.run_watchdog();
+    app.check_job_timeouts();
     update_toast_timer(app);
     app.sync_live_log_listener();
+    app.publish_web_state();
+    app.animation_tick = (app.started_at.elapsed().as_millis() / FRAME_INTERVAL.as_millis()) as u64;
     Ok(true)
 }
 
+/// How long the loop may sleep before something on screen needs updating.
+/// `None` means nothing is scheduled and only input or a message can wake it.
+fn next_wakeup(app: &App) -> Option<Duration> {
+    if any_service_transitioning(app) {
+        return Some(FRAME_INTERVAL);
+    }
+
+    [
+        app.toast_expires_at,
+        app.next_watchdog_restart(),
+        app.next_idle_check(),
+        app.next_job_deadline(),
+    ]
+    .into_iter()
+    .flatten()
+    .min()
+    .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+fn any_service_transitioning(app: &App) -> bool {
+    app.services.iter().any(|service| {
+        matches!(
+            service.status,
+            Status::Pulling | Status::Building | Status::Starting | Status::Stopping
+        )
+    })
+}
+
 struct Keys {
     quit: char,
     search: char,
@@ -42,44 +105,135 @@ struct Keys {
     switch_tab_left: char,
     switch_tab_right: char,
     toggle: char,
+    snooze_idle: char,
+    dismiss_crash: char,
     refresh: char,
+    jobs: char,
+    bundles: char,
+    disk_usage: char,
+    ports: char,
+    cancel_job: char,
+    pull_updates: char,
+    rebuild: char,
+    rebuild_no_cache: char,
+    update_lock: char,
+    mark: char,
+    export_bundle: char,
+    data: char,
+    seeds: char,
+    toggle_pull_details: char,
+    pull_image: char,
+    remove_image: char,
+    prune_dangling: char,
+    snapshot: char,
+    dump: char,
+    wipe: char,
+    confirm: char,
+    run_pending_seeds: char,
+    keep_waiting: char,
+    force_kill: char,
 }
 
 impl Keys {
     fn from_app(app: &App) -> Self {
+        let app_keys = &app.keybinds.app;
+        let services = &app.keybinds.services;
+        let images = &app.keybinds.images;
+        let data = &app.keybinds.data;
+        let timeout_prompt = &app.keybinds.timeout_prompt;
         Self {
-            quit: app.keybinds.app.quit.chars().next().unwrap_or('q'),
-            search: app.keybinds.app.search.chars().next().unwrap_or('/'),
-            stop: app.keybinds.services.stop.chars().next().unwrap_or('s'),
-            start: app.keybinds.services.start.chars().next().unwrap_or('S'),
-            daemon: app.keybinds.app.daemon_menu.chars().next().unwrap_or('d'),
-            scroll_down: app.keybinds.app.scroll_down.chars().next().unwrap_or('j'),
-            scroll_up: app.keybinds.app.scroll_up.chars().next().unwrap_or('k'),
-            switch_tab_left: app
-                .keybinds
-                .app
-                .switch_tab_left
-                .chars()
-                .next()
-                .unwrap_or('['),
-            switch_tab_right: app
-                .keybinds
-                .app
-                .switch_tab_right
-                .chars()
-                .next()
-                .unwrap_or(']'),
-            toggle: app.keybinds.services.toggle.chars().next().unwrap_or(' '),
-            refresh: app.keybinds.app.refresh.chars().next().unwrap_or('r'),
+            quit: key(&app_keys.quit, 'q'),
+            search: key(&app_keys.search, '/'),
+            stop: key(&services.stop, 's'),
+            start: key(&services.start, 'S'),
+            daemon: key(&app_keys.daemon_menu, 'd'),
+            scroll_down: key(&app_keys.scroll_down, 'j'),
+            scroll_up: key(&app_keys.scroll_up, 'k'),
+            switch_tab_left: key(&app_keys.switch_tab_left, '['),
+            switch_tab_right: key(&app_keys.switch_tab_right, ']'),
+            toggle: key(&services.toggle, ' '),
+            snooze_idle: key(&services.snooze_idle, 'z'),
+            dismiss_crash: key(&services.dismiss_crash, 'x'),
+            refresh: key(&app_keys.refresh, 'r'),
+            jobs: key(&app_keys.jobs, 'J'),
+            bundles: key(&app_keys.bundles, 'O'),
+            disk_usage: key(&app_keys.disk_usage, 'U'),
+            ports: key(&app_keys.ports, 'M'),
+            cancel_job: key(&services.cancel_job, 'c'),
+            pull_updates: key(&services.pull_updates, 'u'),
+            rebuild: key(&services.rebuild, 'b'),
+            rebuild_no_cache: key(&services.rebuild_no_cache, 'B'),
+            update_lock: key(&services.update_lock, 'L'),
+            mark: key(&services.mark, 'm'),
+            export_bundle: key(&services.export_bundle, 'E'),
+            data: key(&services.data, 'D'),
+            seeds: key(&services.seeds, 'I'),
+            toggle_pull_details: key(&app.keybinds.logs.toggle_pull_details, 'p'),
+            pull_image: key(&images.pull, 'P'),
+            remove_image: key(&images.remove, 'X'),
+            prune_dangling: key(&images.prune_dangling, 'C'),
+            snapshot: key(&data.snapshot, 's'),
+            dump: key(&data.dump, 'd'),
+            wipe: key(&data.wipe, 'w'),
+            confirm: key(&data.confirm, 'y'),
+            run_pending_seeds: key(&app.keybinds.seeds.run_pending, 'a'),
+            keep_waiting: key(&timeout_prompt.keep_waiting, 'w'),
+            force_kill: key(&timeout_prompt.force_kill, 'f'),
         }
     }
 }
 
+/// First character of a configured binding, or `fallback` when it is empty.
+fn key(binding: &str, fallback: char) -> char {
+    binding.chars().next().unwrap_or(fallback)
+}
+
 fn handle_key(app: &mut App, code: KeyCode, keys: &Keys) -> bool {
     if matches!(code, KeyCode::Char(c) if c == keys.quit) && !in_overlay_mode(app) {
         return false;
     }
 
+    if app.timeout_prompt.is_some() && !app.search_mode && !app.daemon_start_mode {
+        match code {
+            KeyCode::Esc => app.keep_waiting(),
+            KeyCode::Char(c) if c == keys.keep_waiting => app.keep_waiting(),
+            KeyCode::Char(c) if c == keys.force_kill => app.force_kill_timed_out_job(),
+            KeyCode::Char(c) if c == keys.cancel_job => app.cancel_timed_out_job(),
+            _ => {}
+        }
+        return true;
+    }
+
+    if app.jobs_mode {
+        handle_jobs_mode(app, code, keys);
+        return true;
+    }
+
+    if app.bundles_mode {
+        handle_bundles_mode(app, code, keys);
+        return true;
+    }
+
+    if app.data_view.is_some() {
+        handle_data_mode(app, code, keys);
+        return true;
+    }
+
+    if app.seeds_view.is_some() {
+        handle_seeds_mode(app, code, keys);
+        return true;
+    }
+
+    if app.disk_view.is_some() {
+        handle_disk_mode(app, code, keys);
+        return true;
+    }
+
+    if app.ports_view.is_some() {
+        handle_ports_mode(app, code, keys);
+        return true;
+    }
+
     if matches!(code, KeyCode::Char(c) if c == keys.search)
         && !in_overlay_mode(app)
         && app.focus == Focus::Services
@@ -92,7 +246,7 @@ fn handle_key(app: &mut App, code: KeyCode, keys: &Keys) -> bool {
     if matches!(code, KeyCode::Char(c) if c == keys.stop) && !in_overlay_mode(app) {
         if app.focus == Focus::Services {
             if selected_service_transitioning(app) {
-                app.set_toast(ToastState::Info, "Service is busy, wait for transition", 2);
+                busy_toast(app, keys);
             } else {
                 app.stop_service();
             }
@@ -103,7 +257,7 @@ fn handle_key(app: &mut App, code: KeyCode, keys: &Keys) -> bool {
     if matches!(code, KeyCode::Char(c) if c == keys.start) && !in_overlay_mode(app) {
         if app.focus == Focus::Services {
             if selected_service_transitioning(app) {
-                app.set_toast(ToastState::Info, "Service is busy, wait for transition", 2);
+                busy_toast(app, keys);
             } else {
                 app.start_service();
             }
@@ -111,6 +265,27 @@ fn handle_key(app: &mut App, code: KeyCode, keys: &Keys) -> bool {
         return true;
     }
 
+    if matches!(code, KeyCode::Char(c) if c == keys.jobs) && !in_overlay_mode(app) {
+        app.jobs_mode = true;
+        app.jobs_selected = 0;
+        return true;
+    }
+
+    if matches!(code, KeyCode::Char(c) if c == keys.bundles) && !in_overlay_mode(app) {
+        app.open_bundles();
+        return true;
+    }
+
+    if matches!(code, KeyCode::Char(c) if c == keys.disk_usage) && !in_overlay_mode(app) {
+        app.open_disk_usage();
+        return true;
+    }
+
+    if matches!(code, KeyCode::Char(c) if c == keys.ports) && !in_overlay_mode(app) {
+        app.open_ports();
+        return true;
+    }
+
     if matches!(code, KeyCode::Char(c) if c == keys.daemon) && !in_overlay_mode(app) {
         app.daemon_menu_mode = true;
         app.daemon_action_selected = DaemonAction::Start;
@@ -169,25 +344,23 @@ fn handle_key(app: &mut App, code: KeyCode, keys: &Keys) -> bool {
 }
 
 fn handle_normal_mode(app: &mut App, code: KeyCode, keys: &Keys) {
+    let images_tab = app.log_tab == LogTab::Images;
     match code {
+        KeyCode::Char(c) if images_tab && c == keys.pull_image => app.pull_selected_image(),
+        KeyCode::Char(c) if images_tab && c == keys.remove_image => app.remove_selected_image(),
+        KeyCode::Char(c) if images_tab && c == keys.prune_dangling => {
+            app.prune_dangling_images()
+        }
         KeyCode::Char(c) if c == keys.scroll_down => move_down(app),
         KeyCode::Down => move_down(app),
         KeyCode::Char(c) if c == keys.scroll_up => move_up(app),
         KeyCode::Up => move_up(app),
-        KeyCode::Tab => {
-            if app.focus == Focus::Services {
-                app.next();
-            }
-        }
-        KeyCode::BackTab => {
-            if app.focus == Focus::Services {
-                app.previous();
-            }
-        }
+        KeyCode::Tab if app.focus == Focus::Services => app.next(),
+        KeyCode::BackTab if app.focus == Focus::Services => app.previous(),
         KeyCode::Char(c) if c == keys.toggle => {
             if app.focus == Focus::Services {
                 if selected_service_transitioning(app) {
-                    app.set_toast(ToastState::Info, "Service is busy, wait for transition", 2);
+                    busy_toast(app, keys);
                 } else {
                     app.toggle_service();
                 }
@@ -195,18 +368,202 @@ fn handle_normal_mode(app: &mut App, code: KeyCode, keys: &Keys) {
                 app.log_auto_scroll = !app.log_auto_scroll;
             }
         }
+        KeyCode::Char(c) if c == keys.snooze_idle => app.snooze_idle_projects(),
+        KeyCode::Char(c) if c == keys.dismiss_crash => app.dismiss_crash_report(),
+        KeyCode::Char(c) if c == keys.cancel_job => app.cancel_selected_job(),
+        KeyCode::Char(c) if c == keys.pull_updates => app.pull_updates(),
+        KeyCode::Char(c) if c == keys.rebuild => app.rebuild_service(false),
+        KeyCode::Char(c) if c == keys.rebuild_no_cache => app.rebuild_service(true),
+        KeyCode::Char(c) if c == keys.update_lock => app.update_lock(),
+        KeyCode::Char(c) if c == keys.mark => app.toggle_mark(),
+        KeyCode::Char(c) if c == keys.export_bundle => app.export_bundle(),
+        KeyCode::Char(c) if c == keys.data => app.open_data_view(),
+        KeyCode::Char(c) if c == keys.seeds => app.open_seeds(),
+        KeyCode::Char(c) if c == keys.toggle_pull_details => {
+            app.pull_details_expanded = !app.pull_details_expanded;
+        }
         KeyCode::Char(c) if c == keys.refresh => {
             app.refresh_statuses();
+            if images_tab {
+                app.refresh_images();
+            }
+            if app.log_tab == LogTab::Networks {
+                app.refresh_networks();
+            }
             app.set_toast(ToastState::Info, "Refreshed statuses", 3);
         }
-        KeyCode::Char(c) if c == keys.switch_tab_left => toggle_log_tab(app),
-        KeyCode::Char(c) if c == keys.switch_tab_right => toggle_log_tab(app),
+        KeyCode::Char(c) if c == keys.switch_tab_left => previous_log_tab(app),
+        KeyCode::Char(c) if c == keys.switch_tab_right => next_log_tab(app),
         _ => {}
     }
 }
 
+fn handle_jobs_mode(app: &mut App, code: KeyCode, keys: &Keys) {
+    let job_count = app.jobs.len();
+    match code {
+        KeyCode::Esc => app.jobs_mode = false,
+        KeyCode::Char(c) if c == keys.jobs => app.jobs_mode = false,
+        KeyCode::Char(c) if c == keys.scroll_down => jobs_next(app, job_count),
+        KeyCode::Down => jobs_next(app, job_count),
+        KeyCode::Char(c) if c == keys.scroll_up => {
+            app.jobs_selected = app.jobs_selected.saturating_sub(1)
+        }
+        KeyCode::Up => app.jobs_selected = app.jobs_selected.saturating_sub(1),
+        KeyCode::Char(c) if c == keys.cancel_job => app.cancel_overlay_selected_job(),
+        _ => {}
+    }
+}
+
+fn handle_bundles_mode(app: &mut App, code: KeyCode, keys: &Keys) {
+    let bundle_count = app.bundles.len();
+    match code {
+        KeyCode::Esc => app.bundles_mode = false,
+        KeyCode::Char(c) if c == keys.bundles => app.bundles_mode = false,
+        KeyCode::Char(c) if c == keys.scroll_down => bundles_next(app, bundle_count),
+        KeyCode::Down => bundles_next(app, bundle_count),
+        KeyCode::Char(c) if c == keys.scroll_up => {
+            app.bundles_selected = app.bundles_selected.saturating_sub(1)
+        }
+        KeyCode::Up => app.bundles_selected = app.bundles_selected.saturating_sub(1),
+        KeyCode::Enter => app.import_selected_bundle(),
+        _ => {}
+    }
+}
+
+/// Data overlay: snapshot, dump databases, `Enter` to restore the highlighted
+/// snapshot or dump, and wipe; the last two wait for the confirm key.
+fn handle_data_mode(app: &mut App, code: KeyCode, keys: &Keys) {
+    if app.data_view.as_ref().is_some_and(|view| view.confirm.is_some()) {
+        match code {
+            KeyCode::Char(c) if c == keys.confirm => app.confirm_data_action(),
+            _ => app.cancel_data_confirm(),
+        }
+        return;
+    }
+    match code {
+        KeyCode::Esc => app.close_data_view(),
+        KeyCode::Char(c) if c == keys.data => app.close_data_view(),
+        KeyCode::Char(c) if c == keys.scroll_down => app.data_select_next(),
+        KeyCode::Down => app.data_select_next(),
+        KeyCode::Char(c) if c == keys.scroll_up => app.data_select_previous(),
+        KeyCode::Up => app.data_select_previous(),
+        KeyCode::Char(c) if c == keys.refresh => {
+            if let Some(project) = app.data_view.as_ref().map(|view| view.project.clone()) {
+                app.refresh_data_view(&project);
+            }
+        }
+        KeyCode::Char(c) if c == keys.snapshot => app.snapshot_data(),
+        KeyCode::Char(c) if c == keys.dump => app.dump_databases(),
+        KeyCode::Char(c) if c == keys.wipe => app.request_wipe(),
+        KeyCode::Enter => app.request_restore(),
+        _ => {}
+    }
+}
+
+fn handle_seeds_mode(app: &mut App, code: KeyCode, keys: &Keys) {
+    match code {
+        KeyCode::Esc => app.close_seeds(),
+        KeyCode::Char(c) if c == keys.seeds => app.close_seeds(),
+        KeyCode::Char(c) if c == keys.scroll_down => app.seeds_select_next(),
+        KeyCode::Down => app.seeds_select_next(),
+        KeyCode::Char(c) if c == keys.scroll_up => app.seeds_select_previous(),
+        KeyCode::Up => app.seeds_select_previous(),
+        KeyCode::Char(c) if c == keys.refresh => {
+            if let Some(project) = app.seeds_view.as_ref().map(|view| view.project.clone()) {
+                app.refresh_seeds_view(&project);
+            }
+        }
+        KeyCode::Char(c) if c == keys.run_pending_seeds => app.run_pending_seeds(),
+        KeyCode::Enter => app.run_selected_seed(),
+        _ => {}
+    }
+}
+
+/// Disk usage overlay: `p` previews a prune, which `y` runs; `a` and `v`
+/// add unused images and managed volumes to it.
+fn handle_disk_mode(app: &mut App, code: KeyCode, keys: &Keys) {
+    let previewing = app
+        .disk_view
+        .as_ref()
+        .is_some_and(|view| view.plan.is_some());
+    match code {
+        KeyCode::Esc if previewing => app.cancel_prune_preview(),
+        KeyCode::Esc => app.close_disk_usage(),
+        KeyCode::Char(c) if c == keys.disk_usage => app.close_disk_usage(),
+        KeyCode::Char(c) if c == keys.scroll_down => app.disk_select_next(),
+        KeyCode::Down => app.disk_select_next(),
+        KeyCode::Char(c) if c == keys.scroll_up => app.disk_select_previous(),
+        KeyCode::Up => app.disk_select_previous(),
+        KeyCode::Char(c) if c == keys.refresh => app.refresh_disk_usage(),
+        KeyCode::Char('a') => app.toggle_prune_unused_images(),
+        KeyCode::Char('v') => app.toggle_prune_managed_volumes(),
+        KeyCode::Char('y') if previewing => app.confirm_prune(),
+        KeyCode::Char('p') if !previewing => app.preview_prune(),
+        _ => {}
+    }
+}
+
+/// Port map overlay: `e` types a new host port for the selected port, `u`
+/// removes the project's port overrides.
+fn handle_ports_mode(app: &mut App, code: KeyCode, keys: &Keys) {
+    let editing = app
+        .ports_view
+        .as_ref()
+        .is_some_and(|view| view.remap_input.is_some());
+    if editing {
+        match code {
+            KeyCode::Esc => app.cancel_port_remap(),
+            KeyCode::Enter => app.confirm_port_remap(),
+            KeyCode::Backspace => app.port_remap_pop(),
+            KeyCode::Char(c) => app.port_remap_push(c),
+            _ => {}
+        }
+        return;
+    }
+    match code {
+        KeyCode::Esc => app.close_ports(),
+        KeyCode::Char(c) if c == keys.ports => app.close_ports(),
+        KeyCode::Char(c) if c == keys.scroll_down => app.ports_select_next(),
+        KeyCode::Down => app.ports_select_next(),
+        KeyCode::Char(c) if c == keys.scroll_up => app.ports_select_previous(),
+        KeyCode::Up => app.ports_select_previous(),
+        KeyCode::Char(c) if c == keys.refresh => app.refresh_ports(),
+        KeyCode::Char('e') => app.start_port_remap(),
+        KeyCode::Char('u') => app.revert_port_remaps(),
+        _ => {}
+    }
+}
+
+fn bundles_next(app: &mut App, bundle_count: usize) {
+    if app.bundles_selected + 1 < bundle_count {
+        app.bundles_selected += 1;
+    }
+}
+
+fn jobs_next(app: &mut App, job_count: usize) {
+    if app.jobs_selected + 1 < job_count {
+        app.jobs_selected += 1;
+    }
+}
+
+fn busy_toast(app: &mut App, keys: &Keys) {
+    app.set_toast(
+        ToastState::Info,
+        format!("Service is busy, press {} to cancel", keys.cancel_job),
+        2,
+    );
+}
+
 fn in_overlay_mode(app: &App) -> bool {
-    app.search_mode || app.daemon_start_mode || app.daemon_menu_mode
+    app.search_mode
+        || app.daemon_start_mode
+        || app.daemon_menu_mode
+        || app.jobs_mode
+        || app.bundles_mode
+        || app.data_view.is_some()
+        || app.seeds_view.is_some()
+        || app.disk_view.is_some()
+        || app.ports_view.is_some()
 }
 
 fn selected_service_transitioning(app: &App) -> bool {
@@ -214,8 +571,8 @@ fn selected_service_transitioning(app: &App) -> bool {
         .selected()
         .map(|index| {
             matches!(
-                *app.services[index].status.lock().unwrap(),
-                Status::Pulling | Status::Starting | Status::Stopping
+                app.services[index].status,
+                Status::Pulling | Status::Building | Status::Starting | Status::Stopping
             )
         })
         .unwrap_or(false)
@@ -251,6 +608,8 @@ fn daemon_previous(app: &mut App) {
 fn move_down(app: &mut App) {
     if app.focus == Focus::Services {
         app.next();
+    } else if app.log_tab == LogTab::Images {
+        app.images_select_next();
     } else {
         app.log_scroll += 1;
         app.log_auto_scroll = false;
@@ -260,55 +619,69 @@ fn move_down(app: &mut App) {
 fn move_up(app: &mut App) {
     if app.focus == Focus::Services {
         app.previous();
+    } else if app.log_tab == LogTab::Images {
+        app.images_select_previous();
     } else {
         app.log_scroll = app.log_scroll.saturating_sub(1);
         app.log_auto_scroll = false;
     }
 }
 
-fn toggle_log_tab(app: &mut App) {
+fn next_log_tab(app: &mut App) {
     app.log_tab = match app.log_tab {
         LogTab::Events => LogTab::LiveLogs,
+        LogTab::LiveLogs => LogTab::Stats,
+        LogTab::Stats => LogTab::Images,
+        LogTab::Images => LogTab::Networks,
+        LogTab::Networks => LogTab::Events,
+    };
+
+    refresh_entered_tab(app);
+}
+
+fn previous_log_tab(app: &mut App) {
+    app.log_tab = match app.log_tab {
+        LogTab::Events => LogTab::Networks,
         LogTab::LiveLogs => LogTab::Events,
+        LogTab::Stats => LogTab::LiveLogs,
+        LogTab::Images => LogTab::Stats,
+        LogTab::Networks => LogTab::Images,
     };
 
-    if app.log_tab == LogTab::LiveLogs {
-        app.log_auto_scroll = true;
+    refresh_entered_tab(app);
+}
+
+fn refresh_entered_tab(app: &mut App) {
+    match app.log_tab {
+        LogTab::LiveLogs => app.log_auto_scroll = true,
+        LogTab::Images => app.refresh_images(),
+        LogTab::Networks => {
+            app.log_scroll = 0;
+            app.refresh_networks();
+        }
+        LogTab::Events | LogTab::Stats => {}
     }
 }
 
 fn refresh_if_transitioning(app: &mut App) {
-    const STATUS_REFRESH_COOLDOWN_TICKS: u8 = 24;
+    const STATUS_REFRESH_INTERVAL: Duration = Duration::from_millis(800);
 
-    if app.status_refresh_cooldown_ticks > 0 {
-        app.status_refresh_cooldown_ticks = app.status_refresh_cooldown_ticks.saturating_sub(1);
+    if Instant::now() < app.next_status_refresh {
         return;
     }
 
-    let needs_refresh = app.services.iter().any(|service| {
-        matches!(
-            *service.status.lock().unwrap(),
-            Status::Pulling | Status::Starting | Status::Stopping
-        )
-    });
-
-    if needs_refresh {
+    if any_service_transitioning(app) {
         app.refresh_statuses();
-        app.status_refresh_cooldown_ticks = STATUS_REFRESH_COOLDOWN_TICKS;
+        app.next_status_refresh = Instant::now() + STATUS_REFRESH_INTERVAL;
     }
 }
 
 fn update_toast_timer(app: &mut App) {
-    const TOAST_TICKS_PER_SECOND: u8 = 30;
-
-    if app.toast_timer > 0 {
-        app.toast_tick_accumulator = app.toast_tick_accumulator.saturating_add(1);
-        if app.toast_tick_accumulator >= TOAST_TICKS_PER_SECOND {
-            app.toast_tick_accumulator = 0;
-            app.toast_timer = app.toast_timer.saturating_sub(1);
-            if app.toast_timer == 0 {
-                app.toast = None;
-            }
-        }
+    if app
+        .toast_expires_at
+        .is_some_and(|expires_at| Instant::now() >= expires_at)
+    {
+        app.toast = None;
+        app.toast_expires_at = None;
     }
 }
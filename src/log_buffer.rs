@@ -0,0 +1,190 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::notify::Notifier;
+
+pub type SharedLogBuffer = Arc<Mutex<LogBuffer>>;
+
+#[derive(Clone, Copy, Debug)]
+pub struct LogLimits {
+    pub max_lines: usize,
+    pub max_bytes: usize,
+}
+
+/// Line-indexed ring buffer for log output.
+///
+/// Every line gets an absolute index that keeps increasing as old lines are
+/// evicted, so readers can remember how far they got and fetch only what is
+/// new. `generation` changes whenever the buffer is cleared, which tells those
+/// readers to start over. Every change also pokes the UI loop.
+pub struct LogBuffer {
+    lines: VecDeque<String>,
+    bytes: usize,
+    first_index: u64,
+    generation: u64,
+    limits: LogLimits,
+    notifier: Notifier,
+}
+
+impl LogBuffer {
+    pub fn new(limits: LogLimits, notifier: Notifier) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            bytes: 0,
+            first_index: 0,
+            generation: 0,
+            limits: LogLimits {
+                max_lines: limits.max_lines.max(1),
+                max_bytes: limits.max_bytes.max(1),
+            },
+            notifier,
+        }
+    }
+
+    pub fn shared(limits: LogLimits, notifier: Notifier) -> SharedLogBuffer {
+        Arc::new(Mutex::new(Self::new(limits, notifier)))
+    }
+
+    pub fn push_line(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        self.bytes += line.len();
+        self.lines.push_back(line);
+
+        while self.lines.len() > self.limits.max_lines
+            || (self.bytes > self.limits.max_bytes && self.lines.len() > 1)
+        {
+            if let Some(evicted) = self.lines.pop_front() {
+                self.bytes -= evicted.len();
+                self.first_index += 1;
+            }
+        }
+        self.notifier.notify();
+    }
+
+    /// Appends newline-separated text; a trailing segment without a newline
+    /// still becomes its own line.
+    pub fn push_str(&mut self, text: &str) {
+        for line in text.lines() {
+            self.push_line(line);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        // Callers clear idle buffers on every loop; skipping the no-op keeps
+        // that from waking the UI again.
+        if self.lines.is_empty() {
+            return;
+        }
+        self.first_index += self.lines.len() as u64;
+        self.lines.clear();
+        self.bytes = 0;
+        self.generation += 1;
+        self.notifier.notify();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Absolute index of the oldest retained line.
+    pub fn first_index(&self) -> u64 {
+        self.first_index
+    }
+
+    /// Absolute index one past the newest line.
+    pub fn end_index(&self) -> u64 {
+        self.first_index + self.lines.len() as u64
+    }
+
+    /// Lines by position within the retained window, clamped to its bounds.
+    pub fn range(&self, start: usize, end: usize) -> impl Iterator<Item = &str> {
+        let end = end.min(self.lines.len());
+        let start = start.min(end);
+        self.lines.range(start..end).map(String::as_str)
+    }
+
+    /// Lines from an absolute index onwards, starting at the oldest retained
+    /// line if that index has already been evicted.
+    pub fn since(&self, absolute: u64) -> impl Iterator<Item = &str> {
+        let start = absolute.saturating_sub(self.first_index) as usize;
+        self.range(start, self.lines.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(max_lines: usize, max_bytes: usize) -> LogBuffer {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        LogBuffer::new(
+            LogLimits {
+                max_lines,
+                max_bytes,
+            },
+            Notifier::new(sender),
+        )
+    }
+
+    fn since(buffer: &LogBuffer, absolute: u64) -> Vec<&str> {
+        buffer.since(absolute).collect()
+    }
+
+    #[test]
+    fn line_limit_evicts_the_oldest_lines() {
+        let mut buffer = buffer(3, 1024);
+        buffer.push_str("a\nb\nc\nd\ne");
+        assert_eq!(since(&buffer, 0), ["c", "d", "e"]);
+        assert_eq!(buffer.first_index(), 2);
+        assert_eq!(buffer.end_index(), 5);
+    }
+
+    #[test]
+    fn byte_limit_keeps_at_least_the_newest_line() {
+        let mut buffer = buffer(10, 4);
+        buffer.push_line("ab");
+        buffer.push_line("cd");
+        buffer.push_line("efgh");
+        buffer.push_line("too long");
+        assert_eq!(since(&buffer, 0), ["too long"]);
+    }
+
+    #[test]
+    fn absolute_indexes_continue_across_evictions() {
+        let mut buffer = buffer(2, 1024);
+        buffer.push_str("a\nb");
+        let seen = buffer.end_index();
+        buffer.push_str("c\nd");
+        assert_eq!(since(&buffer, seen), ["c", "d"]);
+
+        let seen = buffer.end_index();
+        buffer.push_line("e");
+        assert_eq!(since(&buffer, seen), ["e"]);
+        // A reader further behind than the window gets what is left.
+        assert_eq!(since(&buffer, 1), ["d", "e"]);
+    }
+
+    #[test]
+    fn clear_bumps_the_generation_and_keeps_indexes_moving() {
+        let mut buffer = buffer(10, 1024);
+        buffer.push_str("a\nb");
+        assert_eq!(buffer.generation(), 0);
+
+        buffer.clear();
+        assert_eq!(buffer.generation(), 1);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.first_index(), 2);
+
+        // Clearing an empty buffer is a no-op.
+        buffer.clear();
+        assert_eq!(buffer.generation(), 1);
+
+        buffer.push_line("c");
+        assert_eq!(buffer.first_index(), 2);
+        assert_eq!(since(&buffer, 0), ["c"]);
+    }
+}
@@ -2,15 +2,18 @@ mod app;
 mod config;
 mod docker;
 mod event_handler;
+mod log_buffer;
+mod notify;
 mod service;
 mod status;
 mod toast;
 mod ui;
+mod web;
 
 use std::io;
-use std::time::Duration;
 
 use ratatui::crossterm::{
+    event::EventStream,
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -18,7 +21,8 @@ use ratatui::prelude::CrosstermBackend;
 use ratatui::{DefaultTerminal, TerminalOptions, Viewport};
 
 use app::App;
-use config::Keybinds;
+use config::{Keybinds, Settings};
+use notify::Notifier;
 
 struct TerminalCleanup;
 
@@ -58,10 +62,12 @@ async fn main() -> io::Result<()> {
 }
 
 async fn run(mut terminal: DefaultTerminal) -> io::Result<()> {
-    const FRAME_DURATION: Duration = Duration::from_millis(33);
+    let (sender, mut messages) = tokio::sync::mpsc::unbounded_channel();
+    let mut input = EventStream::new();
 
     let keybinds = Keybinds::load();
-    let mut app = App::new(keybinds);
+    let settings = Settings::load();
+    let mut app = App::new(keybinds, settings, Notifier::new(sender));
     app.next();
 
     loop {
@@ -74,16 +80,18 @@ async fn run(mut terminal: DefaultTerminal) -> io::Result<()> {
 
         if let Some(err) = render_error {
             app.stop_event_listeners();
+            app.stop_web_dashboard();
             app.kill_all_live_logs();
             return Err(err);
         }
 
-        if !event_handler::handle_events(&mut app, FRAME_DURATION).await? {
+        if !event_handler::handle_events(&mut app, &mut input, &mut messages).await? {
             break;
         }
     }
 
     app.stop_event_listeners();
+    app.stop_web_dashboard();
     app.kill_all_live_logs();
 
     Ok(())
@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::jobs::{JobId, JobUpdate};
+use crate::app::services::StatusSnapshot;
+use crate::docker::crash::CrashReport;
+use crate::docker::disk::DiskUsage;
+use crate::docker::dumps::DbService;
+use crate::docker::events::ContainerEvent;
+use crate::docker::images::ImageInfo;
+use crate::docker::networks::NetworkInfo;
+use crate::docker::overrides::PortRemap;
+use crate::docker::ports::PortEntry;
+use crate::docker::stats::StatsSample;
+use crate::docker::volumes::DataMount;
+use crate::web::WebCommand;
+
+/// Updates background tasks send to the app, which is the only place that
+/// mutates project state.
+pub enum AppMessage {
+    /// A shared log buffer changed; nothing to apply, just redraw.
+    Changed,
+    Job {
+        id: JobId,
+        update: JobUpdate,
+    },
+    StatusSnapshot(StatusSnapshot),
+    ContainerEvent(ContainerEvent),
+    CrashReport {
+        service: String,
+        report: CrashReport,
+    },
+    Stats {
+        service: String,
+        container: String,
+        sample: StatsSample,
+    },
+    DaemonAction(Result<String, String>),
+    ToolsChecked {
+        docker_cli: bool,
+        docker_compose: bool,
+    },
+    Web(WebCommand),
+    DataMounts {
+        project: String,
+        mounts: Vec<DataMount>,
+        databases: Vec<DbService>,
+    },
+    Images(Result<Vec<ImageInfo>, String>),
+    ImageAction(Result<String, String>),
+    /// Bytes per mount key, measured by a helper container.
+    DataSizes {
+        project: String,
+        sizes: BTreeMap<String, u64>,
+    },
+    DiskUsage(Result<DiskUsage, String>),
+    DiskPrune(Result<String, String>),
+    Networks(Result<Vec<NetworkInfo>, String>),
+    Ports(Result<Vec<PortEntry>, String>),
+    /// Port remaps each project's ports override held at startup.
+    LoadedPortRemaps(Vec<(String, Vec<PortRemap>)>),
+    /// The project's port remaps after the ports override was rewritten.
+    PortRemaps {
+        project: String,
+        result: Result<Vec<PortRemap>, String>,
+    },
+}
+
+/// Handle background tasks use to report back to the UI loop.
+///
+/// Redraw requests from [`Notifier::notify`] collapse into a single `Changed`
+/// message until the loop acknowledges it, so a chatty log stream cannot flood
+/// the channel.
+#[derive(Clone)]
+pub struct Notifier {
+    sender: UnboundedSender<AppMessage>,
+    pending: Arc<AtomicBool>,
+}
+
+impl Notifier {
+    pub fn new(sender: UnboundedSender<AppMessage>) -> Self {
+        Self {
+            sender,
+            pending: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn notify(&self) {
+        if !self.pending.swap(true, Ordering::AcqRel) {
+            let _ = self.sender.send(AppMessage::Changed);
+        }
+    }
+
+    pub fn send(&self, message: AppMessage) {
+        let _ = self.sender.send(message);
+    }
+
+    /// Called by the UI loop before it redraws, so anything that changes
+    /// while it is drawing wakes it up again.
+    pub fn acknowledge(&self) {
+        self.pending.store(false, Ordering::Release);
+    }
+}
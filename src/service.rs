@@ -1,13 +1,34 @@
+use crate::docker::crash::CrashReport;
+use crate::docker::events::ContainerCrash;
+use crate::docker::overrides::PortRemap;
+use crate::docker::pull::PullProgress;
+use crate::docker::stats::ProjectStats;
+use crate::log_buffer::SharedLogBuffer;
 use crate::status::Status;
-use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::time::Instant;
+use tokio::task::JoinHandle;
 
-#[derive(Clone)]
 pub struct Service {
     pub name: String,
-    pub status: Arc<Mutex<Status>>,
-    pub pull_progress: Arc<Mutex<Option<String>>>,
-    pub events: Arc<Mutex<String>>,
-    pub logs: Arc<Mutex<String>>,
-    pub live_logs: Arc<Mutex<String>>,
-    pub logs_child: Arc<Mutex<Option<std::process::Child>>>,
+    pub status: Status,
+    pub pull_progress: Option<String>,
+    /// Per-image and per-layer detail of the current or last pull.
+    pub pull_layers: Option<PullProgress>,
+    pub events: SharedLogBuffer,
+    pub logs: SharedLogBuffer,
+    pub live_logs: SharedLogBuffer,
+    pub live_logs_task: Option<JoinHandle<()>>,
+    pub web_watchers: Arc<AtomicUsize>,
+    pub stats: ProjectStats,
+    pub crashes: Vec<ContainerCrash>,
+    pub crash_reports: Vec<CrashReport>,
+    /// Containers a `kill` event was seen for, so their exit reads as a stop.
+    pub kills: HashMap<String, Instant>,
+    /// Picked for actions that cover several projects, like bundle export.
+    pub marked: bool,
+    /// Host ports moved by the generated ports override.
+    pub port_remaps: Vec<PortRemap>,
 }
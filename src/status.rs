@@ -4,10 +4,13 @@ use std::fmt;
 pub enum Status {
     Running,
     Stopped,
+    Paused,
     Starting,
     Stopping,
     Pulling,
+    Building,
     Error,
+    CrashLoop,
     DaemonNotRunning,
 }
 
@@ -16,10 +19,13 @@ impl fmt::Display for Status {
         match self {
             Status::Running => write!(f, "running"),
             Status::Stopped => write!(f, "stopped"),
+            Status::Paused => write!(f, "paused"),
             Status::Starting => write!(f, "starting"),
             Status::Stopping => write!(f, "stopping"),
             Status::Pulling => write!(f, "pulling images"),
+            Status::Building => write!(f, "building"),
             Status::Error => write!(f, "error"),
+            Status::CrashLoop => write!(f, "crash loop"),
             Status::DaemonNotRunning => write!(f, "daemon not running"),
         }
     }
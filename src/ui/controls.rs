@@ -68,6 +68,69 @@ fn controls_line(app: &App) -> Line<'static> {
         Color::Blue,
     );
     spans.push(sep());
+    push_key(
+        &mut spans,
+        "Snooze",
+        service_keys.snooze_idle.clone(),
+        Color::Magenta,
+    );
+    spans.push(sep());
+    push_key(
+        &mut spans,
+        "Dismiss",
+        service_keys.dismiss_crash.clone(),
+        Color::LightRed,
+    );
+    spans.push(sep());
+    push_key(&mut spans, "Jobs", app_keys.jobs.clone(), Color::Cyan);
+    spans.push(sep());
+    push_key(
+        &mut spans,
+        "Cancel",
+        service_keys.cancel_job.clone(),
+        Color::LightRed,
+    );
+    spans.push(sep());
+    push_key(
+        &mut spans,
+        "Updates",
+        service_keys.pull_updates.clone(),
+        Color::Green,
+    );
+    spans.push(sep());
+    push_key(
+        &mut spans,
+        "Rebuild",
+        format!("{}/{}", service_keys.rebuild, service_keys.rebuild_no_cache),
+        Color::Magenta,
+    );
+    spans.push(sep());
+    push_key(
+        &mut spans,
+        "Lock",
+        service_keys.update_lock.clone(),
+        Color::Yellow,
+    );
+    spans.push(sep());
+    push_key(&mut spans, "Mark", service_keys.mark.clone(), Color::White);
+    spans.push(sep());
+    push_key(
+        &mut spans,
+        "Export",
+        service_keys.export_bundle.clone(),
+        Color::Cyan,
+    );
+    spans.push(sep());
+    push_key(&mut spans, "Bundles", app_keys.bundles.clone(), Color::Cyan);
+    spans.push(sep());
+    push_key(&mut spans, "Disk", app_keys.disk_usage.clone(), Color::Cyan);
+    spans.push(sep());
+    push_key(&mut spans, "Ports", app_keys.ports.clone(), Color::Cyan);
+    spans.push(sep());
+    push_key(&mut spans, "Data", service_keys.data.clone(), Color::Cyan);
+    spans.push(sep());
+    push_key(&mut spans, "Seeds", service_keys.seeds.clone(), Color::Cyan);
+    spans.push(sep());
     push_key(
         &mut spans,
         "Down",
@@ -89,6 +152,13 @@ fn controls_line(app: &App) -> Line<'static> {
         Color::Green,
     );
     spans.push(sep());
+    push_key(
+        &mut spans,
+        "Layers",
+        log_keys.toggle_pull_details.clone(),
+        Color::Cyan,
+    );
+    spans.push(sep());
     push_key(
         &mut spans,
         "Tab<-",
@@ -0,0 +1,144 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::{App, Focus};
+use crate::docker::units::format_bytes;
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect, block: Block<'_>) {
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [summary_area, header_area, list_area, hints_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
+
+    let summary = if let Some(error) = &app.images_error {
+        Line::from(Span::styled(
+            format!("Cannot list images: {}", error),
+            Style::default().fg(Color::LightRed),
+        ))
+    } else if app.images_loading && app.images.is_empty() {
+        Line::from(Span::styled(
+            "Loading images...",
+            Style::default().fg(Color::DarkGray),
+        ))
+    } else {
+        let total: u64 = app.images.iter().map(|image| image.size).sum();
+        let managed = app
+            .images
+            .iter()
+            .filter(|image| !image.projects.is_empty())
+            .count();
+        let dangling = app
+            .images
+            .iter()
+            .filter(|image| image.is_dangling())
+            .count();
+        Line::from(Span::styled(
+            format!(
+                "{} images, {} total  |  {} used by projects  |  {} dangling",
+                app.images.len(),
+                format_bytes(total as f64),
+                managed,
+                dangling
+            ),
+            Style::default().fg(Color::Gray),
+        ))
+    };
+    frame.render_widget(Paragraph::new(summary), summary_area);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!(
+                "   {:<44}{:<14}{:>10}  {:<18}{}",
+                "IMAGE", "DIGEST", "SIZE", "CREATED", "PROJECTS"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))),
+        header_area,
+    );
+
+    let selected_project = app
+        .state
+        .selected()
+        .and_then(|index| app.services.get(index))
+        .map(|service| service.name.as_str());
+    let items: Vec<ListItem> = app
+        .images
+        .iter()
+        .map(|image| {
+            let name_color = if image.is_dangling() {
+                Color::DarkGray
+            } else if image.projects.is_empty() {
+                Color::Gray
+            } else {
+                Color::White
+            };
+            let projects_color = if selected_project
+                .is_some_and(|project| image.projects.iter().any(|p| p == project))
+            {
+                Color::Cyan
+            } else {
+                Color::Gray
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<44}", truncate(image.name(), 43)),
+                    Style::default().fg(name_color),
+                ),
+                Span::styled(
+                    format!("{:<14}", image.short_digest()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{:>10}  ", format_bytes(image.size as f64)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!("{:<18}", image.created_display()),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(
+                    image.projects.join(", "),
+                    Style::default().fg(projects_color),
+                ),
+            ]))
+        })
+        .collect();
+    let mut state = ListState::default();
+    if !items.is_empty() && app.focus == Focus::Logs {
+        state.select(Some(app.images_selected.min(items.len() - 1)));
+    }
+    let list = List::new(items)
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol("-> ");
+    frame.render_stateful_widget(list, list_area, &mut state);
+
+    let keys = &app.keybinds.images;
+    frame.render_widget(
+        Paragraph::new(format!(
+            "{}: pull   {}: remove   {}: prune dangling   {}: refresh",
+            keys.pull, keys.remove, keys.prune_dangling, app.keybinds.app.refresh
+        ))
+        .style(Style::default().fg(Color::DarkGray)),
+        hints_area,
+    );
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut short: String = text.chars().take(width.saturating_sub(1)).collect();
+    short.push('…');
+    short
+}
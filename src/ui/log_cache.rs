@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use ratatui::text::Line;
+
+use crate::app::LogTab;
+use crate::log_buffer::LogBuffer;
+
+/// Styled copy of the log buffer currently on screen.
+///
+/// Lines are styled once when they are appended and dropped again when the
+/// buffer evicts them, so a frame only pays for what changed since the last one.
+#[derive(Default)]
+pub struct StyledLogCache {
+    key: Option<(String, LogTab, u64)>,
+    lines: VecDeque<Line<'static>>,
+    first_index: u64,
+}
+
+impl StyledLogCache {
+    pub fn sync(
+        &mut self,
+        service: &str,
+        tab: LogTab,
+        buffer: &LogBuffer,
+        style: fn(&str) -> Line<'static>,
+    ) {
+        let key = (service.to_string(), tab, buffer.generation());
+        if self.key.as_ref() != Some(&key) || self.end_index() < buffer.first_index() {
+            self.key = Some(key);
+            self.lines.clear();
+            self.first_index = buffer.first_index();
+        }
+
+        let end_index = self.end_index();
+        self.lines.extend(buffer.since(end_index).map(style));
+
+        while self.first_index < buffer.first_index() && self.lines.pop_front().is_some() {
+            self.first_index += 1;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Up to `count` cached lines starting at `start`, indexed from the oldest.
+    pub fn window(&self, start: usize, count: usize) -> impl Iterator<Item = &Line<'static>> {
+        let start = start.min(self.lines.len());
+        let end = start.saturating_add(count).min(self.lines.len());
+        self.lines.range(start..end)
+    }
+
+    fn end_index(&self) -> u64 {
+        self.first_index + self.lines.len() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_buffer::LogLimits;
+    use crate::notify::Notifier;
+
+    fn plain(line: &str) -> Line<'static> {
+        Line::from(line.to_string())
+    }
+
+    #[test]
+    fn window_follows_evictions_and_clamps_to_the_cache() {
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+        let limits = LogLimits {
+            max_lines: 5,
+            max_bytes: 1024,
+        };
+        let mut buffer = LogBuffer::new(limits, Notifier::new(sender));
+        for i in 0..8 {
+            buffer.push_line(format!("line {}", i));
+        }
+
+        let mut cache = StyledLogCache::default();
+        cache.sync("db", LogTab::Events, &buffer, plain);
+        assert_eq!(cache.len(), 5);
+
+        let window: Vec<String> = cache.window(1, 2).map(|line| line.to_string()).collect();
+        assert_eq!(window, ["line 4", "line 5"]);
+        assert_eq!(cache.window(4, 10).count(), 1);
+        assert_eq!(cache.window(9, 3).count(), 0);
+
+        buffer.push_line("line 8");
+        cache.sync("db", LogTab::Events, &buffer, plain);
+        let last: Vec<String> = cache.window(4, 1).map(|line| line.to_string()).collect();
+        assert_eq!(last, ["line 8"]);
+    }
+}
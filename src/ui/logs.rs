@@ -7,10 +7,13 @@ use ratatui::{
 };
 
 use crate::app::{App, Focus, LogTab};
+use crate::docker::crash::CrashReport;
+use crate::docker::pull::{LayerState, PullProgress};
+use crate::docker::units::format_bytes;
 use crate::status::Status;
+use crate::ui::log_cache::StyledLogCache;
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
-    let logs_content = selected_logs(app);
     let title = logs_title(app);
     let border_color = if app.focus == Focus::Logs {
         Color::Blue
@@ -18,67 +21,129 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         Color::White
     };
 
-    if app.log_auto_scroll {
-        let total_lines = logs_content.lines.len() as u16;
-        let visible_lines = area.height.saturating_sub(2);
-        app.log_scroll = total_lines.saturating_sub(visible_lines);
+    if app.log_tab == LogTab::Stats {
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+        super::stats::render(frame, app, area, block);
+        return;
     }
 
-    let logs_widget = Paragraph::new(logs_content)
+    if app.log_tab == LogTab::Images {
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+        super::images::render(frame, app, area, block);
+        return;
+    }
+
+    if app.log_tab == LogTab::Networks {
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+        super::networks::render(frame, app, area, block);
+        return;
+    }
+
+    let header = sync_selected_logs(app);
+    let visible_lines = area.height.saturating_sub(2) as usize;
+    let total_lines = header.len() + app.log_cache.len();
+    let max_scroll = total_lines.saturating_sub(visible_lines);
+    if app.log_auto_scroll || app.log_scroll > max_scroll {
+        app.log_scroll = max_scroll;
+    }
+
+    // Only the visible slice is cloned out of the cache and handed to the widget.
+    let header_len = header.len();
+    let mut visible: Vec<Line<'static>> = header
+        .into_iter()
+        .skip(app.log_scroll)
+        .take(visible_lines)
+        .collect();
+    let cache_start = app.log_scroll.saturating_sub(header_len);
+    let remaining = visible_lines - visible.len();
+    visible.extend(app.log_cache.window(cache_start, remaining).cloned());
+
+    let logs_widget = Paragraph::new(Text::from(visible))
         .block(
             Block::default()
                 .title(title)
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color)),
         )
-        .style(Style::default().fg(Color::Gray))
-        .scroll((app.log_scroll, 0));
+        .style(Style::default().fg(Color::Gray));
 
     frame.render_widget(logs_widget, area);
 }
 
-fn selected_logs(app: &App) -> Text<'static> {
-    if let Some(index) = app.state.selected() {
-        let service = &app.services[index];
-        match app.log_tab {
-            LogTab::Events => {
-                let logs = service.events.lock().unwrap().clone();
-                let status = service.status.lock().unwrap().clone();
-                let pull_progress = service.pull_progress.lock().unwrap().clone();
-
-                let mut content = if logs.is_empty() {
-                    Text::from(vec![Line::from(vec![Span::styled(
-                        "No events yet - start the service to see events",
-                        Style::default().fg(Color::DarkGray),
-                    )])])
-                } else {
-                    colorize_events(logs)
-                };
-
-                if let Some(progress_line) =
-                    event_progress_line(&status, pull_progress.as_deref(), app.animation_tick)
+/// Brings the styled-line cache up to date with the selected buffer and
+/// returns the lines shown above it (progress, crash reports, placeholders).
+fn sync_selected_logs(app: &mut App) -> Vec<Line<'static>> {
+    let Some(index) = app.state.selected() else {
+        app.log_cache = StyledLogCache::default();
+        return vec![Line::from("Select a service to view logs")];
+    };
+
+    let service = &app.services[index];
+    match app.log_tab {
+        LogTab::Events => {
+            let logs = service.events.lock().unwrap();
+            app.log_cache
+                .sync(&service.name, LogTab::Events, &logs, style_event_line);
+            let empty = logs.is_empty();
+            drop(logs);
+
+            let mut header = Vec::new();
+            if !service.crash_reports.is_empty() {
+                header.extend(crash_report_lines(
+                    &service.crash_reports,
+                    &app.keybinds.services.dismiss_crash,
+                ));
+            }
+
+            if let Some(progress_line) = event_progress_line(
+                &service.status,
+                service.pull_progress.as_deref(),
+                app.animation_tick,
+            ) {
+                header.push(progress_line);
+                if service.status == Status::Pulling
+                    && let Some(pull) = &service.pull_layers
                 {
-                    let mut lines = vec![progress_line, Line::from("")];
-                    lines.extend(content.lines);
-                    content = Text::from(lines);
+                    header.extend(pull_detail_lines(
+                        pull,
+                        app.pull_details_expanded,
+                        &app.keybinds.logs.toggle_pull_details,
+                    ));
                 }
+                header.push(Line::from(""));
+            }
 
-                content
+            if empty {
+                header.push(Line::from(vec![Span::styled(
+                    "No events yet - start the service to see events",
+                    Style::default().fg(Color::DarkGray),
+                )]));
             }
-            LogTab::LiveLogs => {
-                let logs = service.live_logs.lock().unwrap().clone();
-                if logs.is_empty() {
-                    Text::from(vec![Line::from(vec![Span::styled(
-                        "No live logs yet - start the service to see logs",
-                        Style::default().fg(Color::DarkGray),
-                    )])])
-                } else {
-                    colorize_logs(logs)
-                }
+            header
+        }
+        LogTab::LiveLogs => {
+            let logs = service.live_logs.lock().unwrap();
+            app.log_cache
+                .sync(&service.name, LogTab::LiveLogs, &logs, style_log_line);
+            if logs.is_empty() {
+                vec![Line::from(vec![Span::styled(
+                    "No live logs yet - start the service to see logs",
+                    Style::default().fg(Color::DarkGray),
+                )])]
+            } else {
+                Vec::new()
             }
         }
-    } else {
-        Text::from("Select a service to view logs")
+        LogTab::Stats | LogTab::Images | LogTab::Networks => Vec::new(),
     }
 }
 
@@ -110,6 +175,21 @@ fn logs_title(app: &App) -> Line<'static> {
         spans.push(Span::styled("Events", Style::default().fg(Color::White)));
     }
 
+    let crash_count = app
+        .state
+        .selected()
+        .and_then(|index| app.services.get(index))
+        .map(|service| service.crash_reports.len())
+        .unwrap_or(0);
+    if crash_count > 0 {
+        spans.push(Span::styled(
+            format!(" ✖{}", crash_count),
+            Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     spans.push(Span::styled("  |  ", Style::default().fg(Color::DarkGray)));
     if app.log_tab == LogTab::LiveLogs {
         spans.push(Span::styled(
@@ -122,6 +202,42 @@ fn logs_title(app: &App) -> Line<'static> {
         spans.push(Span::styled("Live Logs", Style::default().fg(Color::White)));
     }
 
+    spans.push(Span::styled("  |  ", Style::default().fg(Color::DarkGray)));
+    if app.log_tab == LogTab::Stats {
+        spans.push(Span::styled(
+            "[Stats]",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        spans.push(Span::styled("Stats", Style::default().fg(Color::White)));
+    }
+
+    spans.push(Span::styled("  |  ", Style::default().fg(Color::DarkGray)));
+    if app.log_tab == LogTab::Images {
+        spans.push(Span::styled(
+            "[Images]",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        spans.push(Span::styled("Images", Style::default().fg(Color::White)));
+    }
+
+    spans.push(Span::styled("  |  ", Style::default().fg(Color::DarkGray)));
+    if app.log_tab == LogTab::Networks {
+        spans.push(Span::styled(
+            "[Networks]",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        spans.push(Span::styled("Networks", Style::default().fg(Color::White)));
+    }
+
     if app.focus == Focus::Logs && app.log_auto_scroll {
         spans.push(Span::styled(
             " [AUTO]",
@@ -140,7 +256,7 @@ fn event_progress_line(
     tick: u64,
 ) -> Option<Line<'static>> {
     match status {
-        Status::Pulling => {
+        Status::Pulling | Status::Building => {
             let progress = pull_progress.unwrap_or("in progress");
             let bar = progress_bar(tick, parse_progress_percent(progress));
             Some(Line::from(vec![
@@ -202,6 +318,165 @@ fn event_progress_line(
     }
 }
 
+/// One bar per image, plus one per layer when expanded.
+fn pull_detail_lines(pull: &PullProgress, expanded: bool, toggle_key: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let name_width = pull.images.keys().map(String::len).max().unwrap_or(0);
+
+    for (name, image) in &pull.images {
+        let (current, total) = image.bytes();
+        let percent = image.percent();
+        let color = if image.done {
+            Color::Green
+        } else {
+            Color::Cyan
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<width$} ", name, width = name_width),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled("[", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                progress_bar(0, Some(percent.unwrap_or(0))),
+                Style::default().fg(color),
+            ),
+            Span::styled("] ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                match percent {
+                    Some(percent) => format!(
+                        "{:>3}% {}/{}",
+                        percent,
+                        format_bytes(current as f64),
+                        format_bytes(total as f64)
+                    ),
+                    None => "waiting".to_string(),
+                },
+                Style::default().fg(Color::Gray),
+            ),
+        ]));
+
+        if !expanded {
+            continue;
+        }
+        for (id, layer) in &image.layers {
+            let percent = if layer.state == LayerState::Done {
+                100
+            } else if layer.total > 0 {
+                ((layer.current as f64 / layer.total as f64) * 100.0) as u8
+            } else {
+                0
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {} ", id), Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("{:<12}", layer.state),
+                    Style::default().fg(layer_state_color(layer.state)),
+                ),
+                Span::styled(
+                    progress_bar(0, Some(percent)),
+                    Style::default().fg(layer_state_color(layer.state)),
+                ),
+                Span::styled(
+                    if layer.total > 0 {
+                        format!(
+                            " {}/{}",
+                            format_bytes(layer.current as f64),
+                            format_bytes(layer.total as f64)
+                        )
+                    } else {
+                        String::new()
+                    },
+                    Style::default().fg(Color::Gray),
+                ),
+            ]));
+        }
+    }
+
+    let (done_layers, layers) = pull.layer_counts();
+    lines.push(Line::from(Span::styled(
+        format!(
+            "  {}/{} layers done - press {} to {} layers",
+            done_layers,
+            layers,
+            toggle_key,
+            if expanded { "hide" } else { "show" }
+        ),
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines
+}
+
+fn layer_state_color(state: LayerState) -> Color {
+    match state {
+        LayerState::Waiting => Color::DarkGray,
+        LayerState::Downloading => Color::Cyan,
+        LayerState::Verifying | LayerState::Extracting => Color::Yellow,
+        LayerState::Done => Color::Green,
+    }
+}
+
+fn crash_report_lines(reports: &[CrashReport], dismiss_key: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+
+    for report in reports.iter().rev() {
+        let mut header = vec![
+            Span::styled(
+                "✖ crash report ",
+                Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{} ", report.container),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("exit {}", report.exit_code),
+                Style::default().fg(Color::Red),
+            ),
+        ];
+        if report.oom_killed {
+            header.push(Span::styled(
+                " OOMKilled",
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        header.push(Span::styled(
+            format!("  at {}", report.captured_at),
+            Style::default().fg(Color::DarkGray),
+        ));
+        lines.push(Line::from(header));
+
+        if report.log_tail.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  │ (no log output)",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for log_line in &report.log_tail {
+            lines.push(Line::from(vec![
+                Span::styled("  │ ", Style::default().fg(Color::LightRed)),
+                Span::styled(
+                    log_line.clone(),
+                    Style::default().fg(classify_log_body_color(log_line)),
+                ),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!("  press {} to dismiss the newest report", dismiss_key),
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines.push(Line::from(""));
+    lines
+}
+
 fn pulling_spinner(tick: u64) -> &'static str {
     const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
     SPINNER[((tick / 2) % SPINNER.len() as u64) as usize]
@@ -250,57 +525,51 @@ fn progress_bar(tick: u64, percent: Option<u8>) -> String {
     bar.into_iter().collect()
 }
 
-fn colorize_logs(logs: String) -> Text<'static> {
-    let mut lines = Vec::new();
-
-    for raw_line in logs.lines() {
-        let line_str = raw_line.to_string();
+fn style_log_line(raw_line: &str) -> Line<'static> {
+    let line_str = raw_line.to_string();
 
-        if line_str.starts_with("Pull output:") {
-            lines.push(Line::from(vec![Span::styled(
-                "Pull output:",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )]));
-        } else if line_str.starts_with("Up output:") {
-            lines.push(Line::from(vec![Span::styled(
-                "Up output:",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            )]));
-        } else if line_str.starts_with("Down output:") {
-            lines.push(Line::from(vec![Span::styled(
-                "Down output:",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            )]));
-        } else if line_str.contains("failed")
-            || line_str.contains("Failed")
-            || line_str.contains("error")
-            || line_str.contains("Error")
-        {
-            lines.push(Line::from(vec![Span::styled(
-                line_str,
-                Style::default().fg(Color::Red),
-            )]));
-        } else if line_str.contains("success")
-            || line_str.contains("Success")
-            || line_str.contains("done")
-            || line_str.contains("Done")
-        {
-            lines.push(Line::from(vec![Span::styled(
-                line_str,
-                Style::default().fg(Color::Green),
-            )]));
-        } else if line_str.trim().is_empty() {
-            lines.push(Line::from(""));
-        } else {
-            lines.push(colorize_runtime_log_line(&line_str));
-        }
+    if line_str.starts_with("Pull output:") {
+        Line::from(vec![Span::styled(
+            "Pull output:",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )])
+    } else if line_str.starts_with("Up output:") {
+        Line::from(vec![Span::styled(
+            "Up output:",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )])
+    } else if line_str.starts_with("Down output:") {
+        Line::from(vec![Span::styled(
+            "Down output:",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )])
+    } else if line_str.contains("failed")
+        || line_str.contains("Failed")
+        || line_str.contains("error")
+        || line_str.contains("Error")
+    {
+        Line::from(vec![Span::styled(
+            line_str,
+            Style::default().fg(Color::Red),
+        )])
+    } else if line_str.contains("success")
+        || line_str.contains("Success")
+        || line_str.contains("done")
+        || line_str.contains("Done")
+    {
+        Line::from(vec![Span::styled(
+            line_str,
+            Style::default().fg(Color::Green),
+        )])
+    } else if line_str.trim().is_empty() {
+        Line::from("")
+    } else {
+        colorize_runtime_log_line(&line_str)
     }
-
-    Text::from(lines)
 }
 
 fn colorize_runtime_log_line(line: &str) -> Line<'static> {
@@ -447,55 +716,50 @@ fn log_marker_color(marker: &str) -> Color {
     }
 }
 
-fn colorize_events(logs: String) -> Text<'static> {
-    let mut lines = Vec::new();
+fn style_event_line(raw_line: &str) -> Line<'static> {
+    if raw_line.trim().is_empty() {
+        return Line::from("");
+    }
 
-    for raw_line in logs.lines() {
-        if raw_line.trim().is_empty() {
-            lines.push(Line::from(""));
-            continue;
-        }
+    if raw_line.starts_with("[watchdog] ") {
+        return Line::from(vec![Span::styled(
+            raw_line.to_string(),
+            Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        )]);
+    }
 
-        if let Some(rest) = raw_line.strip_prefix("[event] ") {
-            if let Some(runtime_payload) = rest.strip_prefix("runtime ") {
-                lines.push(colorize_runtime_event("service", runtime_payload));
-                continue;
-            }
+    if let Some(rest) = raw_line.strip_prefix("[event] ") {
+        if let Some(runtime_payload) = rest.strip_prefix("runtime ") {
+            return colorize_runtime_event("service", runtime_payload);
+        }
 
-            if let Some((scope, details)) = rest.split_once(" runtime ") {
-                lines.push(colorize_runtime_event(scope, details));
-                continue;
-            }
+        if let Some((scope, details)) = rest.split_once(" runtime ") {
+            return colorize_runtime_event(scope, details);
+        }
 
-            if let Some((scope, action)) = rest.rsplit_once(' ') {
-                lines.push(Line::from(vec![
-                    Span::styled("[event] ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(
-                        format!("{} ", scope),
-                        Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        action.to_string(),
-                        Style::default().fg(event_action_color(action)),
-                    ),
-                ]));
-            } else {
-                lines.push(Line::from(vec![Span::styled(
-                    raw_line.to_string(),
-                    Style::default().fg(Color::Gray),
-                )]));
-            }
-        } else {
-            lines.push(Line::from(vec![Span::styled(
-                raw_line.to_string(),
-                Style::default().fg(Color::Gray),
-            )]));
+        if let Some((scope, action)) = rest.rsplit_once(' ') {
+            return Line::from(vec![
+                Span::styled("[event] ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!("{} ", scope),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    action.to_string(),
+                    Style::default().fg(event_action_color(action)),
+                ),
+            ]);
         }
     }
 
-    Text::from(lines)
+    Line::from(vec![Span::styled(
+        raw_line.to_string(),
+        Style::default().fg(Color::Gray),
+    )])
 }
 
 fn colorize_runtime_event(scope: &str, details: &str) -> Line<'static> {
@@ -538,6 +802,7 @@ fn event_action_color(action: &str) -> Color {
         "start" | "running (snapshot)" | "health_status: healthy" => Color::Green,
         "create" | "restart" | "unpause" => Color::Yellow,
         "stop" | "destroy" | "pause" | "die" => Color::Red,
+        "auto-stop" | "auto-pause" | "idle-snoozed" => Color::Magenta,
         "kill" | "health_status: unhealthy" => Color::LightRed,
         _ => Color::Gray,
     }
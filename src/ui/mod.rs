@@ -5,10 +5,14 @@ use ratatui::Frame;
 use crate::app::{App, Focus};
 
 mod controls;
+mod images;
 mod layout;
+pub mod log_cache;
 mod logs;
+mod networks;
 mod overlays;
 mod services;
+mod stats;
 mod status_bar;
 
 pub fn render_ui(frame: &mut Frame, app: &mut App) -> io::Result<()> {
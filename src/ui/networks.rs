@@ -0,0 +1,154 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Paragraph},
+};
+
+use crate::app::App;
+
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect, block: Block<'_>) {
+    let managed: Vec<String> = app
+        .services
+        .iter()
+        .map(|service| service.name.clone())
+        .collect();
+    let lines = network_lines(app, &managed);
+
+    let visible_lines = area.height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(visible_lines);
+    app.log_scroll = app.log_scroll.min(max_scroll);
+
+    let visible: Vec<Line<'static>> = lines
+        .into_iter()
+        .skip(app.log_scroll)
+        .take(visible_lines)
+        .collect();
+    frame.render_widget(Paragraph::new(Text::from(visible)).block(block), area);
+}
+
+fn network_lines(app: &App, managed: &[String]) -> Vec<Line<'static>> {
+    if let Some(error) = &app.networks_error {
+        return vec![Line::from(Span::styled(
+            format!("Cannot inspect networks: {}", error),
+            Style::default().fg(Color::LightRed),
+        ))];
+    }
+    if app.networks.is_empty() {
+        let message = if app.networks_loading {
+            "Inspecting networks..."
+        } else {
+            "No networks of managed projects"
+        };
+        return vec![Line::from(Span::styled(
+            message,
+            Style::default().fg(Color::DarkGray),
+        ))];
+    }
+
+    let orphaned = app
+        .networks
+        .iter()
+        .filter(|network| network.orphaned_on_down(managed))
+        .count();
+    let foreign: usize = app
+        .networks
+        .iter()
+        .map(|network| network.foreign(managed).count())
+        .sum();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "{} networks  |  {} outlive their project's down  |  {} foreign containers",
+                app.networks.len(),
+                orphaned,
+                foreign
+            ),
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(""),
+    ];
+
+    for network in &app.networks {
+        let owner = match (&network.project, &network.key) {
+            (Some(project), Some(key)) if *key != network.name => {
+                format!("{} ({})", project, key)
+            }
+            (Some(project), _) => project.clone(),
+            (None, _) => "external".to_string(),
+        };
+        let subnets = if network.subnets.is_empty() {
+            "no subnet".to_string()
+        } else {
+            network.subnets.join(", ")
+        };
+        let mut header = vec![
+            Span::styled(
+                format!("{:<32}", network.name),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{:<10}", network.driver),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::styled(format!("{:<20}", subnets), Style::default().fg(Color::Cyan)),
+            Span::styled(owner, Style::default().fg(Color::Gray)),
+        ];
+        if network.internal {
+            header.push(Span::styled(
+                "  internal",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        if network.orphaned_on_down(managed) {
+            header.push(Span::styled(
+                "  ⚠ stays after down: other containers attached",
+                Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        lines.push(Line::from(header));
+
+        if network.containers.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "    no running containers attached",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for attachment in &network.containers {
+            let foreign = network.is_foreign(attachment, managed);
+            let mut spans = vec![
+                Span::styled(
+                    format!("    {:<36}", attachment.container),
+                    Style::default().fg(if foreign { Color::Yellow } else { Color::White }),
+                ),
+                Span::styled(
+                    format!("{:<20}", attachment.address),
+                    Style::default().fg(Color::Cyan),
+                ),
+            ];
+            if !attachment.aliases.is_empty() {
+                spans.push(Span::styled(
+                    format!("aliases: {}", attachment.aliases.join(", ")),
+                    Style::default().fg(Color::Gray),
+                ));
+            }
+            if foreign {
+                spans.push(Span::styled(
+                    format!(
+                        "  [from {}]",
+                        attachment.project.as_deref().unwrap_or("outside compose")
+                    ),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
@@ -3,10 +3,20 @@ use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
 
+use crate::app::data::{BackupKind, DataConfirm, DataView};
+use crate::app::disk::DiskView;
+use crate::app::jobs::{Job, JobOutcome};
+use crate::app::ports::PortsView;
+use crate::app::seeds::{SeedKind, SeedsView};
 use crate::app::{App, DaemonAction};
+use crate::docker::disk::{UsageItem, UsageKind};
+use crate::docker::overrides::PortRemap;
+use crate::docker::ports::{Availability, PortEntry};
+use crate::docker::units::format_bytes;
+use crate::docker::volumes::DataMount;
 
 pub fn render(frame: &mut Frame, app: &App) {
     if app.daemon_menu_mode {
@@ -17,6 +27,34 @@ pub fn render(frame: &mut Frame, app: &App) {
         render_password_prompt(frame, app);
     }
 
+    if app.jobs_mode {
+        render_jobs(frame, app);
+    }
+
+    if app.bundles_mode {
+        render_bundles(frame, app);
+    }
+
+    if let Some(view) = &app.data_view {
+        render_data(frame, app, view);
+    }
+
+    if let Some(view) = &app.seeds_view {
+        render_seeds(frame, app, view);
+    }
+
+    if let Some(view) = &app.disk_view {
+        render_disk(frame, app, view);
+    }
+
+    if let Some(view) = &app.ports_view {
+        render_ports(frame, app, view);
+    }
+
+    if let Some(job) = app.timeout_prompt_job() {
+        render_timeout_prompt(frame, app, job);
+    }
+
     if let Some(toast) = &app.toast {
         let area = Rect {
             x: frame.area().width.saturating_sub(51),
@@ -191,6 +229,877 @@ fn render_password_prompt(frame: &mut Frame, app: &App) {
     );
 }
 
+fn render_jobs(frame: &mut Frame, app: &App) {
+    let area = centered_rect(96, 20, frame.area());
+    frame.render_widget(Clear, area);
+
+    let popup = Block::default()
+        .title(" Jobs ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = popup.inner(area);
+    frame.render_widget(popup, area);
+
+    let [list_area, hints_area] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(inner);
+
+    let items: Vec<ListItem> = app.jobs_newest_first().map(job_line).collect();
+    if items.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No jobs yet").style(Style::default().fg(Color::DarkGray)),
+            list_area,
+        );
+    } else {
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(app.jobs_selected.min(items.len() - 1)));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol("-> ");
+        frame.render_stateful_widget(list, list_area, &mut state);
+    }
+
+    frame.render_widget(
+        Paragraph::new(format!(
+            "j/k or Up/Down: move   {}: cancel job   Esc: close",
+            app.keybinds.services.cancel_job
+        ))
+        .style(Style::default().fg(Color::DarkGray)),
+        hints_area,
+    );
+}
+
+fn render_bundles(frame: &mut Frame, app: &App) {
+    let area = centered_rect(96, 16, frame.area());
+    frame.render_widget(Clear, area);
+
+    let popup = Block::default()
+        .title(format!(" Bundles in {} ", app.settings.bundles.dir))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = popup.inner(area);
+    frame.render_widget(popup, area);
+
+    let [list_area, hints_area] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(2)]).areas(inner);
+
+    if app.bundles.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No bundles yet").style(Style::default().fg(Color::DarkGray)),
+            list_area,
+        );
+    } else {
+        let items: Vec<ListItem> = app
+            .bundles
+            .iter()
+            .map(|bundle| {
+                let age = bundle
+                    .modified
+                    .and_then(|modified| modified.elapsed().ok())
+                    .map(|age| format_age(age.as_secs()))
+                    .unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<56}", bundle.name),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("{:>10}  ", format_bytes(bundle.size as f64)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::styled(age, Style::default().fg(Color::Gray)),
+                ]))
+            })
+            .collect();
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(app.bundles_selected.min(items.len() - 1)));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol("-> ");
+        frame.render_stateful_widget(list, list_area, &mut state);
+    }
+
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from("j/k or Up/Down: move   Enter: import and verify   Esc: close"),
+            Line::from(format!(
+                "{}: mark projects   {}: export marked (or selected) projects",
+                app.keybinds.services.mark, app.keybinds.services.export_bundle
+            )),
+        ])
+        .style(Style::default().fg(Color::DarkGray)),
+        hints_area,
+    );
+}
+
+fn render_data(frame: &mut Frame, app: &App, view: &DataView) {
+    let area = centered_rect(96, 24, frame.area());
+    frame.render_widget(Clear, area);
+
+    let popup = Block::default()
+        .title(format!(" Data of {} ", view.project))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = popup.inner(area);
+    frame.render_widget(popup, area);
+
+    let [mounts_area, databases_area, backups_area, hints_area] = Layout::vertical([
+        Constraint::Length(8),
+        Constraint::Length(2),
+        Constraint::Min(3),
+        Constraint::Length(3),
+    ])
+    .areas(inner);
+
+    let mut lines = vec![Line::from(Span::styled(
+        "Mounts",
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    match &view.mounts {
+        None => lines.push(Line::from(Span::styled(
+            "Reading compose file...",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        Some(mounts) if mounts.is_empty() => lines.push(Line::from(Span::styled(
+            "No named volumes or bind-mounted directories",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        Some(mounts) => {
+            for mount in mounts {
+                let size = match &view.sizes {
+                    None => "...".to_string(),
+                    Some(sizes) => sizes
+                        .get(&mount.key)
+                        .map(|bytes| format_bytes(*bytes as f64))
+                        .unwrap_or_else(|| "absent".to_string()),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{:<8}", mount.kind()),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::styled(
+                        format!("{:<40}", mount.label),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("{:<28}", mount.services.join(", ")),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::styled(format!("{:>10}", size), Style::default().fg(Color::Cyan)),
+                ]));
+            }
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), mounts_area);
+
+    let databases = match &view.databases {
+        None => Span::styled("...", Style::default().fg(Color::DarkGray)),
+        Some(databases) if databases.is_empty() => {
+            Span::styled("none recognised", Style::default().fg(Color::DarkGray))
+        }
+        Some(databases) => Span::styled(
+            databases
+                .iter()
+                .map(|db| format!("{} ({})", db.service, db.engine))
+                .collect::<Vec<_>>()
+                .join(", "),
+            Style::default().fg(Color::White),
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("Databases: ", Style::default().add_modifier(Modifier::BOLD)),
+            databases,
+        ])),
+        databases_area,
+    );
+
+    let [title_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(backups_area);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            "Snapshots and dumps",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        title_area,
+    );
+    if view.backups.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No snapshots or dumps yet").style(Style::default().fg(Color::DarkGray)),
+            list_area,
+        );
+    } else {
+        let items: Vec<ListItem> = view
+            .backups
+            .iter()
+            .map(|backup| {
+                let age = backup
+                    .archive
+                    .modified
+                    .and_then(|modified| modified.elapsed().ok())
+                    .map(|age| format_age(age.as_secs()))
+                    .unwrap_or_default();
+                let kind = match &backup.kind {
+                    BackupKind::Snapshot => "snapshot".to_string(),
+                    BackupKind::Dump(service) => format!("dump {}", service),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<16}", kind), Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!("{:<48}", backup.archive.name),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("{:>10}  ", format_bytes(backup.archive.size as f64)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::styled(age, Style::default().fg(Color::Gray)),
+                ]))
+            })
+            .collect();
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(view.selected.min(items.len() - 1)));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol("-> ");
+        frame.render_stateful_widget(list, list_area, &mut state);
+    }
+
+    let keys = &app.keybinds.data;
+    let hints = match &view.confirm {
+        Some(DataConfirm::Restore(path)) => Paragraph::new(format!(
+            "Replace the data of {} with {}? {}: restore   any other key: cancel",
+            view.project,
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            keys.confirm
+        ))
+        .style(Style::default().fg(Color::LightRed)),
+        Some(DataConfirm::LoadDump { path, service }) => Paragraph::new(format!(
+            "Load {} into {}, replacing its data? {}: load   any other key: cancel",
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            service,
+            keys.confirm
+        ))
+        .style(Style::default().fg(Color::LightRed)),
+        Some(DataConfirm::Wipe) => Paragraph::new(wipe_confirm_text(view, &keys.confirm))
+            .style(Style::default().fg(Color::LightRed))
+            .wrap(Wrap { trim: true }),
+        None => Paragraph::new(vec![
+            Line::from(format!(
+                "j/k: move   {}: snapshot   {}: dump databases   Enter: restore   {}: wipe   {}: refresh",
+                keys.snapshot, keys.dump, keys.wipe, app.keybinds.app.refresh
+            )),
+            Line::from(
+                "Esc: close   Snapshot restores and wipes need the project stopped, dumps need it running",
+            ),
+        ])
+        .style(Style::default().fg(Color::DarkGray)),
+    };
+    frame.render_widget(hints, hints_area);
+}
+
+/// Names every volume and path a wipe deletes, and the binds it keeps.
+fn wipe_confirm_text(view: &DataView, confirm: &str) -> String {
+    let mounts = view.mounts.as_deref().unwrap_or_default();
+    let describe = |mount: &DataMount| format!("{} {}", mount.kind(), mount.target());
+    let deleted: Vec<String> = mounts.iter().filter(|m| m.erasable).map(describe).collect();
+    let kept: Vec<String> = mounts
+        .iter()
+        .filter(|m| !m.erasable)
+        .map(describe)
+        .collect();
+
+    let mut text = if deleted.is_empty() {
+        format!("{} has no data inside the project to delete.", view.project)
+    } else {
+        format!(
+            "Delete everything in {}? This cannot be undone.",
+            deleted.join(", ")
+        )
+    };
+    if !kept.is_empty() {
+        text.push_str(&format!(" Kept (outside project): {}.", kept.join(", ")));
+    }
+    text.push_str(&format!(" {}: wipe   any other key: cancel", confirm));
+    text
+}
+
+fn render_seeds(frame: &mut Frame, app: &App, view: &SeedsView) {
+    let area = centered_rect(96, 18, frame.area());
+    frame.render_widget(Clear, area);
+
+    let popup = Block::default()
+        .title(format!(" Seeds of {} ", view.project))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = popup.inner(area);
+    frame.render_widget(popup, area);
+
+    let [list_area, hints_area] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(2)]).areas(inner);
+
+    if view.seeds.is_empty() {
+        frame.render_widget(
+            Paragraph::new(format!(
+                "No .sql or .sh files in containers/{}/seeds/",
+                view.project
+            ))
+            .style(Style::default().fg(Color::DarkGray)),
+            list_area,
+        );
+    } else {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs());
+        let items: Vec<ListItem> = view
+            .seeds
+            .iter()
+            .map(|seed| {
+                let (state, color) = match view.state.applied.get(&seed.id) {
+                    Some(at) => (
+                        format!("applied {}", format_age(now.saturating_sub(*at))),
+                        Color::Green,
+                    ),
+                    None => ("pending".to_string(), Color::Yellow),
+                };
+                let kind = match seed.kind {
+                    SeedKind::Sql => "sql",
+                    SeedKind::Shell => "sh",
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<48}", seed.id),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(format!("{:<5}", kind), Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        format!("{:<16}", seed.service.as_deref().unwrap_or("database")),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::styled(state, Style::default().fg(color)),
+                ]))
+            })
+            .collect();
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(view.selected.min(items.len() - 1)));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol("-> ");
+        frame.render_stateful_widget(list, list_area, &mut state);
+    }
+
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(format!(
+                "j/k or Up/Down: move   Enter: run (again)   {}: run pending   {}: refresh   Esc: close",
+                app.keybinds.seeds.run_pending, app.keybinds.app.refresh
+            )),
+            Line::from("Seeds run inside the running project; output goes to its log"),
+        ])
+        .style(Style::default().fg(Color::DarkGray)),
+        hints_area,
+    );
+}
+
+fn render_disk(frame: &mut Frame, app: &App, view: &DiskView) {
+    let area = centered_rect(104, 30, frame.area());
+    frame.render_widget(Clear, area);
+
+    let title = if view.plan.is_some() {
+        " Prune preview "
+    } else {
+        " Disk usage "
+    };
+    let popup = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = popup.inner(area);
+    frame.render_widget(popup, area);
+
+    let [summary_area, list_area, hints_area] = Layout::vertical([
+        Constraint::Length(7),
+        Constraint::Min(3),
+        Constraint::Length(2),
+    ])
+    .areas(inner);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:<14}{:>8}{:>8}{:>12}{:>14}",
+            "TYPE", "TOTAL", "ACTIVE", "SIZE", "RECLAIMABLE"
+        ),
+        Style::default().add_modifier(Modifier::BOLD),
+    ))];
+    match (&view.usage, &view.error) {
+        (_, Some(error)) => lines.push(Line::from(Span::styled(
+            format!("Cannot read disk usage: {}", error),
+            Style::default().fg(Color::LightRed),
+        ))),
+        (None, None) => lines.push(Line::from(Span::styled(
+            "Reading docker system df...",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        (Some(usage), None) => {
+            for kind in UsageKind::ALL {
+                let total = usage.total(kind);
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{:<14}", kind.label()),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(
+                        format!("{:>8}{:>8}", total.count, total.active),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::styled(
+                        format!("{:>12}", format_bytes(total.size as f64)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::styled(
+                        format!("{:>14}", format_bytes(total.reclaimable as f64)),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                ]));
+            }
+            lines.push(Line::from(Span::styled(
+                format!("{:<30}{:>12}", "Total", format_bytes(usage.size() as f64)),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), summary_area);
+
+    let [title_area, rows_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(list_area);
+    let heading = match &view.plan {
+        Some(plan) => {
+            let freed: u64 = plan.iter().map(|item| item.size).sum();
+            format!(
+                "{} items to remove, about {}",
+                plan.len(),
+                format_bytes(freed as f64)
+            )
+        }
+        None => "Items, largest first within each type".to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            heading,
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        title_area,
+    );
+
+    let rows = view.rows();
+    if rows.is_empty() {
+        let empty = if view.plan.is_some() {
+            "Nothing to prune"
+        } else {
+            "Nothing to show"
+        };
+        frame.render_widget(
+            Paragraph::new(empty).style(Style::default().fg(Color::DarkGray)),
+            rows_area,
+        );
+    } else {
+        let items: Vec<ListItem> = rows.iter().map(disk_row).collect();
+        let mut state = ratatui::widgets::ListState::default();
+        state.select(Some(view.selected.min(items.len() - 1)));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol("-> ");
+        frame.render_stateful_widget(list, rows_area, &mut state);
+    }
+
+    let switch = |on: bool| if on { "on" } else { "off" };
+    let options = format!(
+        "a: unused images [{}]   v: managed volumes [{}]",
+        switch(view.options.unused_images),
+        if view.options.managed_volumes {
+            "included"
+        } else {
+            "protected"
+        }
+    );
+    let hints = if view.pruning {
+        Paragraph::new("Pruning...").style(Style::default().fg(Color::Yellow))
+    } else if view.plan.is_some() {
+        Paragraph::new(vec![
+            Line::from(Span::styled(
+                "y: remove everything listed   Esc: back",
+                Style::default().fg(Color::LightRed),
+            )),
+            Line::from(options),
+        ])
+        .style(Style::default().fg(Color::DarkGray))
+    } else {
+        Paragraph::new(vec![
+            Line::from(format!(
+                "j/k: move   p: preview prune   {}: refresh   Esc: close",
+                app.keybinds.app.refresh
+            )),
+            Line::from(options),
+        ])
+        .style(Style::default().fg(Color::DarkGray))
+    };
+    frame.render_widget(hints, hints_area);
+}
+
+fn disk_row(item: &UsageItem) -> ListItem<'static> {
+    let (state, color) = if item.in_use {
+        ("in use", Color::Green)
+    } else if item.stopped {
+        ("stopped", Color::Yellow)
+    } else if item.protected {
+        ("protected", Color::Cyan)
+    } else if item.is_dangling() {
+        ("dangling", Color::Yellow)
+    } else {
+        ("unused", Color::Gray)
+    };
+    let name: String = item.name.chars().take(47).collect();
+    ListItem::new(Line::from(vec![
+        Span::styled(
+            format!("{:<12}", item.kind.label()),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(format!("{:<48}", name), Style::default().fg(Color::White)),
+        Span::styled(
+            format!("{:<18}", item.project.as_deref().unwrap_or("-")),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(
+            format!("{:>10}  ", format_bytes(item.size as f64)),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(state, Style::default().fg(color)),
+    ]))
+}
+
+fn render_ports(frame: &mut Frame, app: &App, view: &PortsView) {
+    let area = centered_rect(112, 28, frame.area());
+    frame.render_widget(Clear, area);
+
+    let popup = Block::default()
+        .title(" Port map ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = popup.inner(area);
+    frame.render_widget(popup, area);
+
+    let [summary_area, header_area, list_area, hints_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(2),
+    ])
+    .areas(inner);
+
+    let summary = match (&view.entries, &view.error) {
+        (_, Some(error)) => Span::styled(
+            format!("Cannot read ports: {}", error),
+            Style::default().fg(Color::LightRed),
+        ),
+        (None, None) => Span::styled("Scanning ports...", Style::default().fg(Color::DarkGray)),
+        (Some(entries), None) => {
+            let conflicts = entries.iter().filter(|e| !e.conflicts.is_empty()).count();
+            let unavailable = entries
+                .iter()
+                .filter(|e| e.availability == Availability::InUse)
+                .count();
+            let bound = entries.iter().filter(|e| e.bound).count();
+            Span::styled(
+                format!(
+                    "{} ports, {} bound, {} in conflict, {} held by other processes",
+                    entries.len(),
+                    bound,
+                    conflicts,
+                    unavailable
+                ),
+                Style::default().fg(if conflicts + unavailable > 0 {
+                    Color::Yellow
+                } else {
+                    Color::Gray
+                }),
+            )
+        }
+    };
+    frame.render_widget(Paragraph::new(summary), summary_area);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            format!(
+                "   {:<16}{:<28}{:<10}{:<7}{:<24}{}",
+                "PROJECT", "CONTAINER", "PORT", "PROTO", "HOST", "STATE"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        header_area,
+    );
+
+    match &view.entries {
+        Some(entries) if !entries.is_empty() => {
+            let items: Vec<ListItem> = entries
+                .iter()
+                .map(|entry| port_row(entry, port_remap(app, entry)))
+                .collect();
+            let mut state = ratatui::widgets::ListState::default();
+            state.select(Some(view.selected.min(items.len() - 1)));
+
+            let list = List::new(items)
+                .highlight_style(Style::default().bg(Color::DarkGray))
+                .highlight_symbol("-> ");
+            frame.render_stateful_widget(list, list_area, &mut state);
+        }
+        Some(_) => frame.render_widget(
+            Paragraph::new("No project declares or publishes a port")
+                .style(Style::default().fg(Color::DarkGray)),
+            list_area,
+        ),
+        None => {}
+    }
+
+    let hints = match (&view.remap_input, view.selected_entry()) {
+        (Some(input), Some(entry)) => Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!(
+                    "New host port for {}/{} {}/{}: {}_",
+                    entry.project, entry.service, entry.container_port, entry.protocol, input
+                ),
+                Style::default().fg(Color::Yellow),
+            )),
+            Line::from("Enter: write override   Esc: cancel"),
+        ]),
+        _ => Paragraph::new(vec![
+            Line::from(format!(
+                "j/k: move   e: remap host port   u: revert project overrides   {}: refresh   Esc: close",
+                app.keybinds.app.refresh
+            )),
+            Line::from(
+                "Remaps go to a generated override, not the compose file, and apply on the next start",
+            ),
+        ]),
+    };
+    frame.render_widget(
+        hints.style(Style::default().fg(Color::DarkGray)),
+        hints_area,
+    );
+}
+
+/// The remap behind `entry`, when the ports override moved it.
+fn port_remap<'a>(app: &'a App, entry: &PortEntry) -> Option<&'a PortRemap> {
+    app.services
+        .iter()
+        .find(|service| service.name == entry.project)?
+        .port_remaps
+        .iter()
+        .find(|remap| remap.matches(&entry.service, &entry.container_port, &entry.protocol))
+}
+
+fn port_row(entry: &PortEntry, remap: Option<&PortRemap>) -> ListItem<'static> {
+    let (state, color) = if !entry.conflicts.is_empty() {
+        let state = if entry.bound {
+            "bound, conflicts with"
+        } else {
+            "conflicts with"
+        };
+        (
+            format!("{} {}", state, entry.conflicts.join(", ")),
+            Color::LightRed,
+        )
+    } else if entry.availability == Availability::InUse {
+        (
+            "unavailable, held by another process".to_string(),
+            Color::Yellow,
+        )
+    } else if entry.bound && !entry.declared {
+        ("bound, not in compose file".to_string(), Color::Cyan)
+    } else if entry.bound {
+        ("bound".to_string(), Color::Green)
+    } else if entry.availability == Availability::Free {
+        ("free".to_string(), Color::Gray)
+    } else {
+        ("not bound".to_string(), Color::Gray)
+    };
+    let highlight = color == Color::LightRed || color == Color::Yellow;
+    let host_style = if highlight {
+        Style::default().fg(color).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let container: String = entry.container.chars().take(27).collect();
+    let project: String = entry.project.chars().take(15).collect();
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("{:<16}", project), Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!("{:<28}", container),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            format!("{:<10}{:<7}", entry.container_port, entry.protocol),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(format!("{:<24}", entry.host_display()), host_style),
+        Span::styled(state, Style::default().fg(color)),
+        Span::styled(
+            remap
+                .map(|remap| match remap.from {
+                    Some(from) => format!("  remapped from {}", from),
+                    None => "  remapped".to_string(),
+                })
+                .unwrap_or_default(),
+            Style::default().fg(Color::Magenta),
+        ),
+    ]))
+}
+
+fn format_age(secs: u64) -> String {
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+fn render_timeout_prompt(frame: &mut Frame, app: &App, job: &Job) {
+    let area = centered_rect(72, 9, frame.area());
+    frame.render_widget(Clear, area);
+
+    let popup = Block::default()
+        .title(" Operation Timed Out ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = popup.inner(area);
+    frame.render_widget(popup, area);
+
+    let [summary_area, hints_area] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(2)]).areas(inner);
+
+    let elapsed = job.elapsed().as_secs();
+    let reason = job
+        .timeout
+        .map(|timeout| timeout.to_string())
+        .unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled(
+                    format!("{} {}", job.kind, job.service),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  phase {}", job.phase),
+                    Style::default().fg(Color::Gray),
+                ),
+            ]),
+            Line::from(Span::styled(reason, Style::default().fg(Color::LightRed))),
+            Line::from(Span::styled(
+                format!("Running for {}:{:02}", elapsed / 60, elapsed % 60),
+                Style::default().fg(Color::Gray),
+            )),
+        ]),
+        summary_area,
+    );
+
+    frame.render_widget(
+        Paragraph::new(format!(
+            "{}/Esc: keep waiting   {}: cancel   {}: force kill containers",
+            app.keybinds.timeout_prompt.keep_waiting,
+            app.keybinds.services.cancel_job,
+            app.keybinds.timeout_prompt.force_kill
+        ))
+        .style(Style::default().fg(Color::DarkGray)),
+        hints_area,
+    );
+}
+
+fn job_line(job: &Job) -> ListItem<'static> {
+    let outcome_color = match job.outcome {
+        JobOutcome::Running if job.timeout.is_some() => Color::LightRed,
+        JobOutcome::Running => Color::Yellow,
+        JobOutcome::Succeeded => Color::Green,
+        JobOutcome::Failed(_) => Color::Red,
+        JobOutcome::Cancelled => Color::Magenta,
+    };
+    let elapsed = job.elapsed().as_secs();
+
+    ListItem::new(Line::from(vec![
+        Span::styled(
+            format!("#{:<4}", job.id),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!("{:<16}", job.service),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            format!("{:<14}", job.kind),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(
+            format!("{:<8}", job.phase),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(
+            format!("{:>4}:{:02}  ", elapsed / 60, elapsed % 60),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(
+            match job.timeout {
+                Some(timeout) if job.is_active() => format!("timed out: {}", timeout),
+                _ => job.outcome.to_string(),
+            },
+            Style::default()
+                .fg(outcome_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            job.progress
+                .as_deref()
+                .filter(|_| job.is_active())
+                .map(|progress| format!("  {}", progress))
+                .unwrap_or_default(),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]))
+}
+
 fn action_label(action: DaemonAction) -> &'static str {
     match action {
         DaemonAction::Start => "Start",
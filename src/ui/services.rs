@@ -7,6 +7,7 @@ use ratatui::{
 };
 
 use crate::app::{App, Focus};
+use crate::docker::units::format_bytes;
 use crate::service::Service;
 use crate::status::Status;
 
@@ -47,10 +48,43 @@ pub fn render(frame: &mut Frame, app: &mut App, list_area: Rect, search_area: Op
     let items: Vec<ListItem> = filtered_services
         .iter()
         .map(|service| {
-            let status = service.status.lock().unwrap().clone();
-            let style = status_style(&status);
-            let indicator = status_indicator(&status, app.animation_tick);
-            let line = format!("{} {}  {}", indicator, service.name, status);
+            let status = service.status.clone();
+            let timed_out = app
+                .active_job(&service.name)
+                .is_some_and(|job| job.timeout.is_some());
+            let (style, indicator) = if timed_out {
+                (Style::default().fg(Color::LightRed), "⧗")
+            } else {
+                (
+                    status_style(&status),
+                    status_indicator(&status, app.animation_tick),
+                )
+            };
+            let mark = if service.marked { " ✓" } else { "" };
+            let mut line = format!("{} {}{}  {}", indicator, service.name, mark, status);
+            if timed_out {
+                line.push_str("  (timed out)");
+            }
+            if !service.port_remaps.is_empty() {
+                let remaps: Vec<String> = service
+                    .port_remaps
+                    .iter()
+                    .map(|remap| match remap.from {
+                        Some(from) => format!("{}->{}", from, remap.to),
+                        None => remap.to.to_string(),
+                    })
+                    .collect();
+                line.push_str(&format!("  (ports {})", remaps.join(" ")));
+            }
+            if status == Status::Running
+                && let Some(totals) = service.stats.totals()
+            {
+                line.push_str(&format!(
+                    "  cpu {:.1}% mem {}",
+                    totals.cpu_percent,
+                    format_bytes(totals.mem_bytes)
+                ));
+            }
             ListItem::new(line).style(style)
         })
         .collect();
@@ -58,7 +92,7 @@ pub fn render(frame: &mut Frame, app: &mut App, list_area: Rect, search_area: Op
     let running_count = app
         .services
         .iter()
-        .filter(|service| *service.status.lock().unwrap() == Status::Running)
+        .filter(|service| service.status == Status::Running)
         .count();
     let title = services_title(app.focus, running_count, app.services.len());
 
@@ -85,9 +119,14 @@ fn status_style(status: &Status) -> Style {
         Status::Starting => Style::default().fg(Color::Yellow),
         Status::Stopping => Style::default().fg(Color::Red),
         Status::Pulling => Style::default().fg(Color::Cyan),
+        Status::Building => Style::default().fg(Color::Magenta),
         Status::Running => Style::default().fg(Color::Green),
         Status::Stopped => Style::default().fg(Color::Gray),
+        Status::Paused => Style::default().fg(Color::LightBlue),
         Status::Error => Style::default().fg(Color::White),
+        Status::CrashLoop => Style::default()
+            .fg(Color::LightRed)
+            .add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
         Status::DaemonNotRunning => Style::default().fg(Color::White),
     }
 }
@@ -104,10 +143,10 @@ fn services_title(_focus: Focus, running_count: usize, total_count: usize) -> Li
 
 fn selected_style(app: &App) -> Style {
     if let Some(index) = app.state.selected() {
-        let status = app.services[index].status.lock().unwrap().clone();
+        let status = app.services[index].status.clone();
         if matches!(
             status,
-            Status::Starting | Status::Stopping | Status::Pulling
+            Status::Starting | Status::Stopping | Status::Pulling | Status::Building
         ) {
             let bg = if (app.animation_tick / 3).is_multiple_of(2) {
                 Color::Yellow
@@ -131,6 +170,7 @@ fn status_indicator(status: &Status, tick: u64) -> &'static str {
     match status {
         Status::Running => "●",
         Status::Pulling => "◌",
+        Status::Building => "⚒",
         Status::Starting => {
             const FRAMES: [&str; 4] = ["◜", "◠", "◝", "◞"];
             FRAMES[((tick / 2) % FRAMES.len() as u64) as usize]
@@ -140,7 +180,9 @@ fn status_indicator(status: &Status, tick: u64) -> &'static str {
             FRAMES[((tick / 2) % FRAMES.len() as u64) as usize]
         }
         Status::Stopped => "○",
+        Status::Paused => "‖",
         Status::Error => "✖",
+        Status::CrashLoop => "↻",
         Status::DaemonNotRunning => "○",
     }
 }
@@ -0,0 +1,182 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Sparkline},
+};
+
+use crate::app::App;
+use crate::docker::stats::ContainerStats;
+use crate::docker::units::format_bytes;
+
+const CONTAINER_HEIGHT: u16 = 6;
+const LABEL_WIDTH: u16 = 22;
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect, block: Block<'_>) {
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(service) = app
+        .state
+        .selected()
+        .and_then(|index| app.services.get(index))
+    else {
+        frame.render_widget(Paragraph::new("Select a service to view stats"), inner);
+        return;
+    };
+
+    if !app.settings.stats.enabled {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Stats are disabled in settings.toml",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            inner,
+        );
+        return;
+    }
+
+    let stats = &service.stats;
+    let containers: Vec<(&String, &ContainerStats)> = stats
+        .containers
+        .iter()
+        .filter(|(_, container)| container.is_fresh())
+        .collect();
+
+    if containers.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "No stats yet - start the service to see resource usage",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            inner,
+        );
+        return;
+    }
+
+    let totals_line = stats.totals().map(|totals| {
+        Line::from(vec![
+            Span::styled("total ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("cpu {:.1}%", totals.cpu_percent),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled("  ", Style::default()),
+            Span::styled(
+                format!("mem {}", format_bytes(totals.mem_bytes)),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::styled("  ", Style::default()),
+            Span::styled(
+                format!(
+                    "net {} / {}",
+                    format_bytes(totals.net_rx),
+                    format_bytes(totals.net_tx)
+                ),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled("  ", Style::default()),
+            Span::styled(
+                format!(
+                    "blk {} / {}",
+                    format_bytes(totals.block_read),
+                    format_bytes(totals.block_write)
+                ),
+                Style::default().fg(Color::Yellow),
+            ),
+        ])
+    });
+
+    let [totals_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+    if let Some(line) = totals_line {
+        frame.render_widget(Paragraph::new(line), totals_area);
+    }
+
+    let mut constraints: Vec<Constraint> = containers
+        .iter()
+        .map(|_| Constraint::Length(CONTAINER_HEIGHT))
+        .collect();
+    constraints.push(Constraint::Min(0));
+    let rows = Layout::vertical(constraints).split(list_area);
+
+    for ((name, container), row) in containers.iter().zip(rows.iter()) {
+        if row.height < 3 {
+            break;
+        }
+        render_container(frame, name, container, *row);
+    }
+}
+
+fn render_container(frame: &mut Frame, name: &str, container: &ContainerStats, area: Rect) {
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {} ", name),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(latest) = container.latest() else {
+        return;
+    };
+
+    let rows = [
+        (
+            format!("cpu {:>6.1}%", latest.cpu_percent),
+            container.cpu_series(),
+            Color::Green,
+        ),
+        (
+            format!(
+                "mem {}/{}",
+                format_bytes(latest.mem_bytes),
+                format_bytes(latest.mem_limit)
+            ),
+            container.mem_series(),
+            Color::Magenta,
+        ),
+        (
+            format!(
+                "net {}/{}",
+                format_bytes(latest.net_rx),
+                format_bytes(latest.net_tx)
+            ),
+            container.net_series(),
+            Color::Cyan,
+        ),
+        (
+            format!(
+                "blk {}/{}",
+                format_bytes(latest.block_read),
+                format_bytes(latest.block_write)
+            ),
+            container.block_series(),
+            Color::Yellow,
+        ),
+    ];
+
+    let areas = Layout::vertical([Constraint::Length(1); 4]).split(inner);
+    for ((label, series, color), row) in rows.into_iter().zip(areas.iter()) {
+        let [label_area, spark_area] =
+            Layout::horizontal([Constraint::Length(LABEL_WIDTH), Constraint::Min(0)]).areas(*row);
+        frame.render_widget(
+            Paragraph::new(label).style(Style::default().fg(color)),
+            label_area,
+        );
+
+        // Keep the newest samples when the history is wider than the panel.
+        let visible = series.len().saturating_sub(spark_area.width as usize);
+        frame.render_widget(
+            Sparkline::default()
+                .data(&series[visible..])
+                .style(Style::default().fg(color)),
+            spark_area,
+        );
+    }
+}
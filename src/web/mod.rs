@@ -0,0 +1,491 @@
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::log_buffer::{LogBuffer, SharedLogBuffer};
+use crate::notify::{AppMessage, Notifier};
+use crate::status::Status;
+
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+const SSE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_HEADER_LINES: usize = 64;
+
+/// Browsers cannot send this header cross-origin without a CORS preflight,
+/// which the server never answers, so it doubles as CSRF protection.
+const COMMAND_HEADER: &str = "x-docker-manager";
+
+pub enum WebCommand {
+    Start(String),
+    Stop(String),
+}
+
+/// Log buffers and the watcher count for one service. Statuses are not read
+/// from here but from the snapshots the app publishes.
+#[derive(Clone)]
+pub struct WebServiceTarget {
+    pub name: String,
+    pub events: SharedLogBuffer,
+    pub live_logs: SharedLogBuffer,
+    pub web_watchers: Arc<AtomicUsize>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct WebServiceState {
+    pub name: String,
+    pub status: Status,
+    pub progress: Option<String>,
+}
+
+pub struct WebServerHandle {
+    pub addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    accept_task: JoinHandle<()>,
+}
+
+impl WebServerHandle {
+    pub fn signal_shutdown(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        self.accept_task.abort();
+    }
+}
+
+#[derive(Serialize)]
+struct ServiceSnapshot<'a> {
+    name: &'a str,
+    status: String,
+    running: bool,
+    busy: bool,
+    progress: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct LogChunk<'a> {
+    tab: &'a str,
+    reset: bool,
+    text: &'a str,
+}
+
+struct Shared {
+    /// Port the server listens on, for checking `Host` headers.
+    port: u16,
+    targets: Vec<WebServiceTarget>,
+    states: watch::Receiver<Vec<WebServiceState>>,
+    notifier: Notifier,
+    shutdown: Arc<AtomicBool>,
+}
+
+pub fn spawn_dashboard_server(
+    port: u16,
+    targets: Vec<WebServiceTarget>,
+    states: watch::Receiver<Vec<WebServiceState>>,
+    notifier: Notifier,
+) -> std::io::Result<WebServerHandle> {
+    // Bind synchronously so a busy port is reported to the caller right away.
+    let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+    let addr = listener.local_addr()?;
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shared = Arc::new(Shared {
+        port: addr.port(),
+        targets,
+        states,
+        notifier,
+        shutdown: Arc::clone(&shutdown),
+    });
+
+    let accept_task = tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let shared = Arc::clone(&shared);
+            tokio::spawn(async move {
+                let _ = handle_connection(stream, &shared).await;
+            });
+        }
+    });
+
+    Ok(WebServerHandle {
+        addr,
+        shutdown,
+        accept_task,
+    })
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: String,
+    host: Option<String>,
+    has_command_header: bool,
+}
+
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).await? == 0 {
+        return Ok(None);
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("").to_string();
+    let target = parts.next().unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut host = None;
+    let mut has_command_header = false;
+    for _ in 0..MAX_HEADER_LINES {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 {
+            break;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+        if name.eq_ignore_ascii_case(COMMAND_HEADER) {
+            has_command_header = true;
+        } else if name.eq_ignore_ascii_case("host") {
+            host = Some(value.trim().to_string());
+        }
+    }
+
+    Ok(Some(Request {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        host,
+        has_command_header,
+    }))
+}
+
+async fn handle_connection(mut stream: TcpStream, shared: &Shared) -> std::io::Result<()> {
+    let request = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await {
+        Ok(request) => request?,
+        Err(_) => return Ok(()),
+    };
+    let Some(request) = request else {
+        return Ok(());
+    };
+
+    // A page on another origin that rebinds its DNS name to 127.0.0.1 is
+    // same-origin to the browser; only its Host header gives it away.
+    if !allowed_host(request.host.as_deref(), shared.port) {
+        return write_response(
+            &mut stream,
+            "421 Misdirected Request",
+            "text/plain",
+            "unexpected Host header",
+        )
+        .await;
+    }
+
+    let Some(segments) = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect::<Option<Vec<String>>>()
+    else {
+        return write_response(&mut stream, "400 Bad Request", "text/plain", "bad path").await;
+    };
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", []) => {
+            write_response(
+                &mut stream,
+                "200 OK",
+                "text/html; charset=utf-8",
+                DASHBOARD_HTML,
+            )
+            .await
+        }
+        ("GET", ["api", "services"]) => {
+            let body = snapshot_json(&shared.states.borrow());
+            write_response(&mut stream, "200 OK", "application/json", &body).await
+        }
+        ("GET", ["events"]) => {
+            let service = query_param(&request.query, "service");
+            stream_events(stream, shared, service.as_deref()).await
+        }
+        ("POST", ["api", "services", name, action]) => {
+            if !request.has_command_header {
+                return write_response(
+                    &mut stream,
+                    "403 Forbidden",
+                    "text/plain",
+                    "missing x-docker-manager header",
+                )
+                .await;
+            }
+            if !shared.targets.iter().any(|target| target.name == *name) {
+                return write_response(
+                    &mut stream,
+                    "404 Not Found",
+                    "text/plain",
+                    "unknown service",
+                )
+                .await;
+            }
+            let command = match *action {
+                "start" => WebCommand::Start(name.to_string()),
+                "stop" => WebCommand::Stop(name.to_string()),
+                _ => {
+                    return write_response(
+                        &mut stream,
+                        "404 Not Found",
+                        "text/plain",
+                        "unknown action",
+                    )
+                    .await;
+                }
+            };
+            shared.notifier.send(AppMessage::Web(command));
+            write_response(&mut stream, "202 Accepted", "application/json", "{}").await
+        }
+        _ => write_response(&mut stream, "404 Not Found", "text/plain", "not found").await,
+    }
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await
+}
+
+/// Whether a request's `Host` names the loopback address the server is
+/// bound to; requests without one are refused.
+fn allowed_host(host: Option<&str>, port: u16) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    let host = host.to_ascii_lowercase();
+    [format!("127.0.0.1:{}", port), format!("localhost:{}", port)].contains(&host)
+}
+
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| percent_decode(&name.replace('+', " ")).as_deref() == Some(key))
+        .and_then(|(_, value)| percent_decode(&value.replace('+', " ")))
+        .filter(|value| !value.is_empty())
+}
+
+/// Decodes `%XX` escapes as produced by `encodeURIComponent`; `None` for a
+/// malformed escape or text that is not UTF-8 once decoded.
+fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            // `from_str_radix` takes a leading sign, so `%+F` must be refused here.
+            let hex = bytes.get(i + 1..i + 3)?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            decoded.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+fn snapshot_json(states: &[WebServiceState]) -> String {
+    let snapshots: Vec<ServiceSnapshot<'_>> = states
+        .iter()
+        .map(|state| ServiceSnapshot {
+            name: &state.name,
+            running: state.status == Status::Running,
+            busy: matches!(
+                state.status,
+                Status::Pulling | Status::Building | Status::Starting | Status::Stopping
+            ),
+            status: state.status.to_string(),
+            progress: state.progress.as_deref(),
+        })
+        .collect();
+    serde_json::to_string(&snapshots).unwrap_or_default()
+}
+
+/// Tracks how much of a shared log buffer has already been sent to a client.
+struct LogCursor {
+    next_index: u64,
+    generation: Option<u64>,
+}
+
+impl LogCursor {
+    fn new() -> Self {
+        Self {
+            next_index: 0,
+            generation: None,
+        }
+    }
+
+    /// Returns the text the client is missing. `reset` asks it to drop what it
+    /// has, either because the buffer was cleared or because this is the first
+    /// chunk; lines evicted before they were sent are simply skipped.
+    fn next_chunk(&mut self, buffer: &LogBuffer) -> Option<(bool, String)> {
+        let reset = self.generation != Some(buffer.generation());
+        if !reset && self.next_index >= buffer.end_index() {
+            return None;
+        }
+
+        let start = if reset { 0 } else { self.next_index };
+        let mut text = String::new();
+        for line in buffer.since(start) {
+            text.push_str(line);
+            text.push('\n');
+        }
+
+        self.generation = Some(buffer.generation());
+        self.next_index = buffer.end_index();
+        Some((reset, text))
+    }
+}
+
+async fn stream_events(
+    mut stream: TcpStream,
+    shared: &Shared,
+    service: Option<&str>,
+) -> std::io::Result<()> {
+    stream
+        .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\n")
+        .await?;
+    stream.flush().await?;
+
+    let target = service.and_then(|name| shared.targets.iter().find(|target| target.name == name));
+    let _watch = target
+        .map(|target| WatchGuard::new(Arc::clone(&target.web_watchers), shared.notifier.clone()));
+
+    let mut states = shared.states.clone();
+    states.mark_changed();
+    let mut events_cursor = LogCursor::new();
+    let mut live_cursor = LogCursor::new();
+
+    while !shared.shutdown.load(Ordering::Relaxed) {
+        if states.has_changed().unwrap_or(false) {
+            let snapshot = snapshot_json(&states.borrow_and_update());
+            write_sse(&mut stream, "services", &snapshot).await?;
+        }
+
+        if let Some(target) = target {
+            for (tab, buffer, cursor) in [
+                ("events", &target.events, &mut events_cursor),
+                ("live", &target.live_logs, &mut live_cursor),
+            ] {
+                let chunk = {
+                    let buffer = buffer.lock().unwrap();
+                    cursor.next_chunk(&buffer)
+                };
+                if let Some((reset, text)) = chunk {
+                    let payload = serde_json::to_string(&LogChunk {
+                        tab,
+                        reset,
+                        text: &text,
+                    })
+                    .unwrap_or_default();
+                    write_sse(&mut stream, "log", &payload).await?;
+                }
+            }
+        }
+
+        // Comment lines keep proxies from timing out and surface dead clients.
+        stream.write_all(b":\n\n").await?;
+        stream.flush().await?;
+        tokio::time::sleep(SSE_POLL_INTERVAL).await;
+    }
+
+    Ok(())
+}
+
+async fn write_sse(stream: &mut TcpStream, event: &str, data: &str) -> std::io::Result<()> {
+    stream
+        .write_all(format!("event: {}\ndata: {}\n\n", event, data).as_bytes())
+        .await
+}
+
+/// Keeps the live log follower for a service attached while a browser watches it.
+/// The UI loop is woken on both ends, since it is what starts and stops followers.
+struct WatchGuard {
+    watchers: Arc<AtomicUsize>,
+    notifier: Notifier,
+}
+
+impl WatchGuard {
+    fn new(watchers: Arc<AtomicUsize>, notifier: Notifier) -> Self {
+        watchers.fetch_add(1, Ordering::Relaxed);
+        notifier.notify();
+        Self { watchers, notifier }
+    }
+}
+
+impl Drop for WatchGuard {
+    fn drop(&mut self) {
+        self.watchers.fetch_sub(1, Ordering::Relaxed);
+        self.notifier.notify();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_param_decodes_values() {
+        let query = "service=my%20db&tab=live&empty=&plus=a+b";
+        assert_eq!(query_param(query, "service").as_deref(), Some("my db"));
+        assert_eq!(query_param(query, "tab").as_deref(), Some("live"));
+        assert_eq!(query_param(query, "plus").as_deref(), Some("a b"));
+        assert_eq!(query_param(query, "empty"), None);
+        assert_eq!(query_param(query, "missing"), None);
+    }
+
+    #[test]
+    fn percent_decode_rejects_malformed_escapes() {
+        assert_eq!(percent_decode("a%2Fb%C3%A9").as_deref(), Some("a/bé"));
+        assert_eq!(percent_decode("plain").as_deref(), Some("plain"));
+        assert_eq!(percent_decode("bad%2"), None);
+        assert_eq!(percent_decode("bad%zz"), None);
+        assert_eq!(percent_decode("bad%+F"), None);
+        assert_eq!(percent_decode("%FF"), None);
+    }
+
+    #[test]
+    fn only_loopback_hosts_on_our_port_are_allowed() {
+        assert!(allowed_host(Some("127.0.0.1:8080"), 8080));
+        assert!(allowed_host(Some("LOCALHOST:8080"), 8080));
+        assert!(!allowed_host(Some("localhost:8081"), 8080));
+        assert!(!allowed_host(Some("evil.example:8080"), 8080));
+        assert!(!allowed_host(None, 8080));
+    }
+}